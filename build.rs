@@ -4,5 +4,7 @@ fn main() {
     #[cfg(target_os = "linux")]
     println!("cargo:rustc-link-lib=Xft");
     #[cfg(target_os = "linux")]
-    println!("cargo:rustc-link-lib=cairo")
+    println!("cargo:rustc-link-lib=cairo");
+    #[cfg(target_os = "linux")]
+    println!("cargo:rustc-link-lib=fontconfig")
 }
\ No newline at end of file
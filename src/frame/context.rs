@@ -4,6 +4,8 @@ use crate::map::Map;
 use crate::render::image::ImageRender;
 #[cfg(feature = "gpu")]
 use crate::text::render::TextRender;
+use crate::widgets::toast::Toasts;
+use crate::widgets::tooltip::Tooltips;
 use crate::window::ime::IMEData;
 use crate::window::{ClipboardData, WindowId, WindowType};
 #[cfg(feature = "gpu")]
@@ -12,6 +14,7 @@ use crate::{Font, NumCastExt};
 #[cfg(feature = "gpu")]
 use glyphon::Viewport;
 use std::fmt::Debug;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 #[cfg(feature = "gpu")]
 use crate::render::circle::CircleRender;
@@ -130,6 +133,14 @@ pub struct Context {
     pub updates: Map<String, ContextUpdate>,
     pub user_update: (WindowId, UpdateType),
     pub new_window: Option<Box<dyn App>>,
+    pub(crate) tooltips: Tooltips,
+    pub(crate) toasts: Toasts,
+    ///打开的模态子窗口数量
+    pub(crate) modal_children: Arc<AtomicUsize>,
+    ///new_window是否为模态子窗口
+    pub(crate) new_modal: bool,
+    ///模态子窗口的父窗口及其模态子窗口数量，关闭时减少
+    pub(crate) modal_parent: Option<(Arc<AtomicUsize>, Arc<WindowType>)>,
 }
 
 pub struct Render {
@@ -7,6 +7,7 @@ pub enum Key {
     Backspace,
     Enter,
     Space,
+    Tab,
     Home,
     End,
     Delete,
@@ -17,31 +18,47 @@ pub enum Key {
     DownArrow,
     LShift,
     RShift,
+    LCtrl,
+    RCtrl,
     CapsLock,
     CtrlC,
     CtrlV,
     CtrlX,
     CtrlA,
+    CtrlF,
+    PageUp,
+    PageDown,
+    F2,
+    F3,
+    ShiftF3,
+    Escape,
 }
 
 impl Key {
     pub fn from_c_ulong(keycode: c_uint, buffer: &[i8]) -> Key {
         println!("key-{}", keycode);
         match keycode {
+            9 => Key::Escape,
             22 => Key::Backspace,
+            23 => Key::Tab,
             36 => Key::Enter,
+            37 => Key::LCtrl,
             50 => Key::LShift,
             62 => Key::RShift,
             65 => Key::Space,
             66 => Key::CapsLock,
+            68 => Key::F2,
             104 => Key::Enter,
+            105 => Key::RCtrl,
             119 => Key::Delete,
             110 => Key::Home,
             111 => Key::UpArrow,
+            112 => Key::PageUp,
             113 => Key::LeftArrow,
             114 => Key::RightArrow,
             115 => Key::End,
             116 => Key::DownArrow,
+            117 => Key::PageDown,
             _ => {
                 if buffer.len() == 0 { return Key::Unknown; }
                 let slice = buffer.iter().map(|x| *x as u8).collect::<Vec<_>>();
@@ -50,4 +67,10 @@ impl Key {
             }
         }
     }
-}
\ No newline at end of file
+}
+///鼠标或键盘事件发生时的修饰键状态
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+}
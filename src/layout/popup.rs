@@ -6,6 +6,7 @@ use crate::size::rect::Rect;
 use crate::size::Geometry;
 use crate::widgets::{WidgetSize, WidgetState};
 use crate::*;
+use std::mem;
 
 pub struct Popup {
     pub(crate) id: String,
@@ -74,6 +75,12 @@ impl Popup {
         self.open
     }
 
+    ///重建弹窗时保留原弹窗的打开状态及尚未处理的请求
+    pub(crate) fn inherit_state(&mut self, previous: &mut Popup) {
+        self.requests = mem::take(&mut previous.requests);
+        if self.requests.is_empty() { self.requests.push(previous.open); }
+    }
+
     pub fn request_state(&mut self, state: bool) {
         self.requests.push(state);
     }
@@ -18,6 +18,12 @@ pub struct RecycleLayout {
     display: Range<usize>, //显示范围
     item_height: f32, //每一个item的高度
     size: WidgetSize,
+    floating: Option<LayoutItem>, //浮动在所有item之上绘制的item，如拖动时的影像
+    floating_y: f32,
+    headers: Vec<usize>, //分组头所在的位置，升序排列
+    header_height: f32,
+    header_items: Vec<LayoutItem>, //分组头的控件，与items分别复用
+    sticky: Option<LayoutItem>, //固定在顶部的当前分组头
 }
 
 impl RecycleLayout {
@@ -33,6 +39,12 @@ impl RecycleLayout {
             display: 0..0,
             item_height: 38.0,
             size: WidgetSize::same(0.0, 0.0),
+            floating: None,
+            floating_y: 0.0,
+            headers: vec![],
+            header_height: 26.0,
+            header_items: vec![],
+            sticky: None,
         }
     }
 
@@ -84,10 +96,10 @@ impl RecycleLayout {
     }
 
     pub fn update_display(&mut self) {
-        let item_total_h = self.item_height + self.item_space;
-
-        let mut start = (-self.offset.y / item_total_h).floor().max(0.0) as usize;
-        let mut end = ((-self.offset.y + self.size.dh) / item_total_h).ceil().max(0.0) as usize;
+        let mut start = self.index_at(-self.offset.y);
+        let bottom = -self.offset.y + self.size.dh;
+        let mut end = self.index_at(bottom);
+        if self.row_y(end) < bottom { end += 1; }
 
         if start > self.total_count {
             start = self.total_count;
@@ -118,6 +130,140 @@ impl RecycleLayout {
         self.update_display();
     }
 
+    pub fn with_header_height(mut self, h: f32) -> Self {
+        self.header_height = h;
+        self
+    }
+
+    ///设置分组头的位置，位置包含在total_count中，分组头与item使用各自的控件
+    /// ```
+    /// use xlui::*;
+    ///
+    /// let mut layout = RecycleLayout::new().with_item_height(20.0).with_header_height(10.0).with_space(0.0);
+    /// layout.set_total_count(6);
+    /// //第0项和第3项为分组头
+    /// layout.set_headers(vec![0, 3]);
+    /// assert_eq!(layout.row_y(3), 50.0);
+    /// assert_eq!(layout.index_at(55.0), 3);
+    /// ```
+    pub fn set_headers(&mut self, headers: Vec<usize>) {
+        self.headers = headers;
+        self.set_total_count(self.total_count);
+    }
+
+    pub fn headers(&self) -> &Vec<usize> {
+        &self.headers
+    }
+
+    pub fn is_header(&self, index: usize) -> bool {
+        self.headers.binary_search(&index).is_ok()
+    }
+
+    pub fn add_header_item(&mut self, item: LayoutItem) {
+        self.header_items.push(item);
+    }
+
+    pub fn header_items_mut(&mut self) -> &mut Vec<LayoutItem> {
+        &mut self.header_items
+    }
+
+    ///设置固定在顶部的分组头控件
+    pub fn set_sticky(&mut self, item: Option<LayoutItem>) {
+        self.sticky = item;
+    }
+
+    pub fn sticky_mut(&mut self) -> Option<&mut LayoutItem> {
+        self.sticky.as_mut()
+    }
+
+    ///第index项顶部相对布局顶部的位置
+    pub fn row_y(&self, index: usize) -> f32 {
+        let headers = self.headers.partition_point(|x| *x < index);
+        (index - headers) as f32 * (self.item_height + self.item_space) + headers as f32 * (self.header_height + self.item_space)
+    }
+
+    fn row_height(&self, index: usize) -> f32 {
+        if self.is_header(index) { self.header_height } else { self.item_height }
+    }
+
+    ///y(相对布局顶部)处的项，即顶部不大于y的最后一项
+    pub fn index_at(&self, y: f32) -> usize {
+        let (mut low, mut high) = (0, self.total_count);
+        while low < high {
+            let middle = (low + high).div_ceil(2);
+            if self.row_y(middle) <= y { low = middle; } else { high = middle - 1; }
+        }
+        low
+    }
+
+    ///显示范围内第一个非分组头项在item中的序号
+    pub fn first_item(&self) -> usize {
+        self.display.start - self.first_header()
+    }
+
+    ///显示范围内第一个分组头的序号
+    pub fn first_header(&self) -> usize {
+        self.headers.partition_point(|x| *x < self.display.start)
+    }
+
+    ///顶部所在的分组，即应固定在顶部的分组头序号
+    pub fn sticky_header(&self) -> Option<usize> {
+        let top = self.index_at(-self.offset.y);
+        self.headers.partition_point(|x| *x <= top).checked_sub(1)
+    }
+
+    ///使第index项显示在顶部所需的滚动量，向上滚动为正
+    pub fn top_delta(&self, index: usize) -> f32 {
+        -self.offset.y - self.row_y(index)
+    }
+
+    ///使第index项完整显示所需的滚动量，向上滚动为正，已完整显示时为0
+    pub fn scroll_delta(&self, index: usize) -> f32 {
+        let top = self.row_y(index);
+        let bottom = top + self.row_height(index);
+        let scrolled = -self.offset.y;
+        if top < scrolled {
+            scrolled - top
+        } else if bottom > scrolled + self.size.dh {
+            scrolled + self.size.dh - bottom
+        } else {
+            0.0
+        }
+    }
+
+    ///可显示的完整item数
+    pub fn page_count(&self) -> usize {
+        ((self.size.dh / (self.item_height + self.item_space)).floor() as usize).max(1)
+    }
+
+    ///y(相对布局顶部)处的插入位置，即中心在y之上的item数
+    pub fn insert_index(&self, y: f32) -> usize {
+        let item_total_h = self.item_height + self.item_space;
+        let index = ((y - self.offset.y - self.item_height / 2.0) / item_total_h).ceil().max(0.0) as usize;
+        index.min(self.total_count)
+    }
+
+    ///设置浮动绘制的item，绘制在所有item之上
+    pub fn set_floating(&mut self, item: Option<LayoutItem>) {
+        self.floating = item;
+    }
+
+    ///浮动item顶部的y坐标
+    pub fn set_floating_y(&mut self, y: f32) {
+        self.floating_y = y;
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.total_count
+    }
+
+    ///重设item总数，已有的item保留，只改变可滚动范围
+    pub fn set_total_count(&mut self, count: usize) {
+        self.total_count = count;
+        self.size.rh = self.row_y(count);
+        self.update_display();
+    }
+
     pub fn draw_count(&self) -> usize {
         self.draw_count
     }
@@ -140,16 +286,30 @@ impl Layout for RecycleLayout {
                 ui.draw_rect.set_y_min(previous_rect.dy().min + self.padding.top);
                 ui.draw_rect.set_y_max(previous_rect.dy().max - self.padding.bottom);
 
-                let item_total_h = self.item_height + self.item_space;
-                let first_offset = -(-self.offset.y - self.display.start as f32 * item_total_h);
+                let first_offset = self.offset.y + self.row_y(self.display.start);
                 ui.draw_rect.set_x_min(ui.draw_rect.dx().min + self.offset.x);
                 ui.draw_rect.set_y_min(previous_rect.dy().min + first_offset);
-                let mut start = self.display.start;
-                for item in self.items.iter_mut() {
+                //分组头与item按位置交替取用各自的控件
+                let mut items = self.items.iter_mut();
+                let mut header_items = self.header_items.iter_mut();
+                for index in self.display.start..self.total_count {
+                    let item = if self.headers.binary_search(&index).is_ok() { header_items.next() } else { items.next() };
+                    let Some(item) = item else { break; };
                     let resp = item.update(ui);
                     ui.draw_rect.add_min_y(resp.size.dh + self.item_space);
-                    start += 1;
-                    if start >= self.total_count { break; }
+                }
+                if let UpdateType::Draw = ui.update_type && let Some(header) = self.sticky_header() {
+                    //下一个分组头到达时向上推出
+                    let push = match self.headers.get(header + 1) {
+                        Some(next) => (self.row_y(*next) + self.offset.y - self.header_height - self.item_space).min(0.0),
+                        None => 0.0,
+                    };
+                    ui.draw_rect.set_y_min(previous_rect.dy().min + self.padding.top + push);
+                    if let Some(ref mut item) = self.sticky { item.update(ui); }
+                }
+                if let UpdateType::Draw = ui.update_type && let Some(ref mut item) = self.floating {
+                    ui.draw_rect.set_y_min(self.floating_y);
+                    item.update(ui);
                 }
             }
         }
@@ -74,6 +74,7 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::SystemTime;
+use crate::key::{Key, KeyModifiers};
 
 mod widgets;
 mod align;
@@ -96,24 +97,29 @@ pub mod shape;
 
 #[cfg(all(not(feature = "winit"), target_os = "windows"))]
 pub use window::win32::tray::{Tray, TrayMenu};
-pub use window::{attribute::WindowAttribute, inner::InnerWindow};
+pub use window::{attribute::WindowAttribute, inner::InnerWindow, dialog::{MessageBox, MessageButton, file::FileDialog}};
 pub use layout::{horizontal::HorizontalLayout, vertical::VerticalLayout,
                  popup::Popup, LayoutKind, recycle::RecycleLayout};
 pub use size::font::{Font, FontSlant, FontWeight};
 pub use size::{border::Border, padding::Padding, radius::Radius, rect::Rect, pos::Pos, Size, margin::Margin};
-pub use widgets::{label::Label, scroll::ScrollWidget, listview::ListView, Widget, radio::RadioButton,
+pub use widgets::{label::Label, scroll::ScrollWidget, listview::ListView, listview::selection::ListSelectionMode, Widget, radio::RadioButton,
                   image::Image, button::Button, checkbox::CheckBox, slider::Slider, processbar::ProcessBar,
                   select::SelectItem, textedit::TextEdit, spinbox::SpinBox, combo::ComboBox,
                   rectangle::Rectangle, circle::Circle, triangle::Triangle, tab::TabWidget,
-                  combo::check::CheckComboBox, table::TableExt, table::TableView, table::column::TableColumn,
+                  combo::check::CheckComboBox, table::TableExt, table::TableView, table::column::{TableColumn, SortOrder, TableColumnLayout, TableEditor, TableFilterMode}, table::selection::TableSelectionMode, table::value::{CellValue, SortKey}, table::column::TableCellKind,
+                  tree::{TreeView, TreeProvider}, model::{ListModel, TableModel, VecModel, ModelChange, ModelNotifier},
+                  calendar::{Calendar, date::{Date, Time}, picker::DatePicker, time::TimeEdit},
+                  color::{ColorPicker, button::ColorButton}, tooltip::WidgetExt,
+                  toast::{Toast, ToastKind}, toolbar::{ToolBar, ToolButtonStyle},
+                  textedit::find::FindOptions, textedit::code::{CodeEdit, Highlighter, KeywordHighlighter},
 };
 pub use error::{UiResult, UiError};
-pub use text::{rich::RichTextExt, TextWrap, rich::RichText};
+pub use text::{rich::RichTextExt, TextWrap, rich::RichText, TextSpan, TextDecoration, DecorationSpan};
 pub use ui::Ui;
 pub use style::{ClickStyle, BorderStyle, FillStyle, color::Color, Shadow, FrameStyle};
 pub use frame::{App, context::UpdateType};
 pub use align::Align;
-pub use render::{VisualStyle, WidgetStyle};
+pub use render::{VisualStyle, WidgetStyle, image::ImageSource};
 
 pub trait NumCastExt: Sized {
     fn as_f32(&self) -> f32;
@@ -268,6 +274,7 @@ pub struct MouseInput {
     pressed: bool,
     pressed_pos: MousePos,
     pressed_time: u128,
+    modifiers: KeyModifiers,
 
     clicked: AtomicBool,
     a: f32,
@@ -309,6 +316,12 @@ impl MouseInput {
 
     pub fn pressed(&self) -> bool { self.pressed }
 
+    ///最近一次按下或释放鼠标时Shift是否按下
+    pub fn shift_pressed(&self) -> bool { self.modifiers.shift }
+
+    ///最近一次按下或释放鼠标时Ctrl是否按下
+    pub fn ctrl_pressed(&self) -> bool { self.modifiers.ctrl }
+
     pub fn mouse_press(&mut self) {
         self.previous = self.lastest.clone();
         self.pressed_pos = self.lastest.clone();
@@ -327,7 +340,8 @@ impl MouseInput {
 
 pub struct DeviceInput {
     pub mouse: MouseInput,
-
+    ///键盘当前的修饰键状态，由Shift、Ctrl键的按下和释放更新
+    key_modifiers: KeyModifiers,
 }
 
 impl DeviceInput {
@@ -341,11 +355,31 @@ impl DeviceInput {
                 pressed: false,
                 clicked: AtomicBool::new(false),
                 pressed_time: 0,
+                modifiers: KeyModifiers::default(),
                 a: 0.0,
-            }
+            },
+            key_modifiers: KeyModifiers::default(),
+        }
+    }
+
+    ///键盘事件发生时的修饰键状态
+    pub fn key_modifiers(&self) -> KeyModifiers {
+        self.key_modifiers
+    }
+
+    pub(crate) fn update_key(&mut self, key: &Key, pressed: bool) {
+        match key {
+            Key::LShift | Key::RShift => self.key_modifiers.shift = pressed,
+            Key::LCtrl | Key::RCtrl => self.key_modifiers.ctrl = pressed,
+            _ => {}
         }
     }
 
+    ///后端直接提供修饰键状态时调用，如winit的ModifiersChanged
+    pub(crate) fn set_key_modifiers(&mut self, modifiers: KeyModifiers) {
+        self.key_modifiers = modifiers;
+    }
+
     pub fn click_at(&self, rect: &Rect) -> bool {
         if !self.mouse.clicked.load(Ordering::SeqCst) { return false; }
 
@@ -80,6 +80,14 @@ impl<K: Clone + Eq + Hash, V> Map<K, V> {
         (res.key, res.value)
     }
 
+    pub fn retain(&mut self, mut f: impl FnMut(&K, &V) -> bool) {
+        self.values.retain(|x| f(&x.key, &x.value));
+        self.keys.clear();
+        self.values.iter().enumerate().for_each(|(i, v)| {
+            self.keys.insert(v.key.clone(), i);
+        });
+    }
+
     pub fn len(&self) -> usize {
         self.values.len()
     }
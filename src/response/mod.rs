@@ -1,12 +1,56 @@
 use crate::ui::Ui;
 use std::any::Any;
+use std::cell::RefCell;
+use std::mem;
 use std::ops::DerefMut;
+use std::rc::Rc;
+use std::sync::Arc;
 use crate::frame::App;
 use crate::InnerWindow;
 use crate::widgets::WidgetSize;
 use crate::widgets::button::Button;
+use crate::widgets::calendar::date::{Date, Time};
+use crate::style::color::Color;
 
 pub type InnerCallB = Box<dyn FnMut()>;
+pub type InnerTextCallB = Box<dyn FnMut(&str)>;
+pub type InnerValuesCallB = Arc<dyn Fn(&[String])>;
+pub type AppCallB = Arc<dyn Fn(&mut Box<dyn App>, &mut Ui)>;
+pub type ListReorderCallB = Box<dyn Fn(&mut Box<dyn App>, &mut Ui, usize, usize)>;
+pub type DateCallB = Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, Date)>;
+pub type TimeCallB = Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, Time)>;
+pub type ColorCallB = Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, Color)>;
+pub type ActionCallB = Box<dyn FnMut(&mut Box<dyn App>, &mut Ui)>;
+pub type TableEditCallB = Arc<dyn Fn(&mut Box<dyn App>, &mut Ui, usize, usize, &str) -> bool>;
+
+///App::update中产生的改变(如选择)的通知，由控件在布局更新时调用回调
+#[derive(Default)]
+pub(crate) struct AppNotify {
+    changed: bool,
+    callback: Option<AppCallB>,
+}
+
+pub(crate) type AppNotifies = Rc<RefCell<AppNotify>>;
+
+impl AppNotify {
+    pub(crate) fn set_callback(&mut self, callback: AppCallB) {
+        self.callback = Some(callback);
+    }
+
+    pub(crate) fn notify(&mut self) {
+        self.changed = true;
+    }
+
+    ///ui.app可用且已改变时调用回调
+    pub(crate) fn emit(notify: &AppNotifies, ui: &mut Ui) {
+        if ui.app.is_none() || !mem::take(&mut notify.borrow_mut().changed) { return; }
+        let Some(callback) = notify.borrow().callback.clone() else { return; };
+        let app = ui.app.take().unwrap();
+        callback(app, ui);
+        ui.app = Some(app);
+    }
+}
+
 pub struct Callback;
 
 impl Callback {
@@ -66,6 +110,46 @@ impl Callback {
         })
     }
 
+    pub(crate) fn create_date<A: 'static>(f: fn(&mut A, &mut Ui, Date)) -> DateCallB {
+        Box::new(move |box_app, uim, value| {
+            let app = box_app.deref_mut() as &mut dyn Any;
+            let t = app.downcast_mut::<A>().unwrap();
+            f(t, uim, value)
+        })
+    }
+
+    pub(crate) fn create_time<A: 'static>(f: fn(&mut A, &mut Ui, Time)) -> TimeCallB {
+        Box::new(move |box_app, uim, value| {
+            let app = box_app.deref_mut() as &mut dyn Any;
+            let t = app.downcast_mut::<A>().unwrap();
+            f(t, uim, value)
+        })
+    }
+
+    pub(crate) fn create_color<A: 'static>(f: fn(&mut A, &mut Ui, Color)) -> ColorCallB {
+        Box::new(move |box_app, uim, value| {
+            let app = box_app.deref_mut() as &mut dyn Any;
+            let t = app.downcast_mut::<A>().unwrap();
+            f(t, uim, value)
+        })
+    }
+
+    pub(crate) fn create_table_edit<A: 'static>(f: fn(&mut A, &mut Ui, usize, usize, &str) -> bool) -> TableEditCallB {
+        Arc::new(move |box_app, uim, row, column, value| {
+            let app = box_app.deref_mut() as &mut dyn Any;
+            let t = app.downcast_mut::<A>().unwrap();
+            f(t, uim, row, column, value)
+        })
+    }
+
+    pub(crate) fn create_list_reorder<A: 'static>(f: impl Fn(&mut A, &mut Ui, usize, usize) + 'static) -> ListReorderCallB {
+        Box::new(move |box_app, uim, from, to| {
+            let app = box_app.deref_mut() as &mut dyn Any;
+            let t = app.downcast_mut::<A>().unwrap();
+            f(t, uim, from, to);
+        })
+    }
+
     pub(crate) fn create_list<A: 'static>(f: impl Fn(&mut A, &mut Ui) + 'static) -> Box<dyn Fn(&mut Box<dyn App>, &mut Ui)> {
         Box::new(move |box_app, uim| {
             let app = box_app.deref_mut() as &mut dyn Any;
@@ -73,6 +157,14 @@ impl Callback {
             f(t, uim);
         })
     }
+
+    pub(crate) fn create_action<A: 'static>(f: fn(&mut A, &mut Ui)) -> ActionCallB {
+        Box::new(move |box_app, uim| {
+            let app = box_app.deref_mut() as &mut dyn Any;
+            let t = app.downcast_mut::<A>().unwrap();
+            f(t, uim);
+        })
+    }
 }
 
 pub struct Response<'a> {
@@ -1,4 +1,5 @@
 use crate::text::cchar::{CChar, LineChar};
+use crate::text::FontMetrics;
 #[cfg(all(target_os = "linux", not(feature = "gpu")))]
 use crate::window::x11::font::X11Font;
 use crate::*;
@@ -36,6 +37,8 @@ pub enum FontWeight {
 /// * 在wgpu模式下支持使用自定义字体ttf文件和bytes
 /// * 在native模式下仅支持调用系统已有的字体
 ///
+/// * 字体缺少字符时按回退链依次查找，回退链为自定义回退字体加系统回退字体
+///
 /// ### Font示例
 /// ```rust
 /// use std::fs;
@@ -43,6 +46,7 @@ pub enum FontWeight {
 ///
 /// fn draw(){
 ///     let font=Font::from_family("微软雅黑");
+///     let font=Font::from_family("微软雅黑").unwrap().with_fallback("Noto Color Emoji");
 ///     #[cfg(feature = "gpu")]
 ///     let font=Font::from_file("1.ttf");
 ///     #[cfg(feature = "gpu")]
@@ -53,6 +57,10 @@ pub enum FontWeight {
 pub struct Font {
     family: String,
     size: f32,
+    fallbacks: Vec<String>,
+    ///None时首次使用再查找
+    system_fallbacks: Option<Vec<String>>,
+    system_fallback: bool,
     #[cfg(feature = "gpu")]
     font_system: cosmic_text::FontSystem,
 }
@@ -67,17 +75,105 @@ impl Font {
         Ok(Font {
             family: family.to_string(),
             size: 14.0,
+            fallbacks: vec![],
+            system_fallbacks: None,
+            system_fallback: true,
             #[cfg(feature = "gpu")]
             font_system: cosmic_text::FontSystem::new(),
         })
     }
 
+    ///等宽字体，native模式下使用系统等宽字体别名，wgpu模式下查找首个等宽字体
+    pub fn monospace() -> UiResult<Font> {
+        let mut font = Font::from_family("")?;
+        font.family = font.monospace_family();
+        Ok(font)
+    }
+
+    #[cfg(not(feature = "gpu"))]
+    pub(crate) fn monospace_family(&self) -> String {
+        if cfg!(target_os = "windows") { "Consolas".to_string() } else { "Monospace".to_string() }
+    }
+
+    #[cfg(feature = "gpu")]
+    pub(crate) fn monospace_family(&self) -> String {
+        let face = self.font_system.db().faces().find(|x| x.monospaced && !x.families.is_empty());
+        match face {
+            None => self.family.clone(),
+            Some(face) => face.families[0].0.clone(),
+        }
+    }
+
     ///设置全局字体大小
     pub fn with_size(mut self, font_size: f32) -> Self {
         self.size = font_size;
         self
     }
 
+    ///添加回退字体，按添加顺序查找缺失的字符
+    pub fn with_fallback(mut self, family: impl ToString) -> Self {
+        self.fallbacks.push(family.to_string());
+        self
+    }
+
+    pub fn with_fallbacks(mut self, families: Vec<impl ToString>) -> Self {
+        self.fallbacks.extend(families.iter().map(|x| x.to_string()));
+        self
+    }
+
+    ///设置系统回退字体，代替自动查找的结果
+    pub fn with_system_fallbacks(mut self, families: Vec<impl ToString>) -> Self {
+        self.system_fallbacks = Some(families.iter().map(|x| x.to_string()).collect());
+        self
+    }
+
+    ///不使用系统回退字体
+    pub fn without_system_fallback(mut self) -> Self {
+        self.system_fallback = false;
+        self
+    }
+
+    pub fn set_fallbacks(&mut self, families: Vec<impl ToString>) {
+        self.fallbacks = families.iter().map(|x| x.to_string()).collect();
+    }
+
+    pub fn fallbacks(&self) -> &[String] {
+        &self.fallbacks
+    }
+
+    ///系统回退字体，Linux下通过fontconfig查找
+    pub fn system_fallbacks(&mut self) -> &[String] {
+        let family = self.family.clone();
+        self.system_fallbacks.get_or_insert_with(|| Font::find_system_fallbacks(&family))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn find_system_fallbacks(family: &str) -> Vec<String> {
+        crate::size::fontconfig::fallback_families(family, 16)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn find_system_fallbacks(_: &str) -> Vec<String> {
+        ["Microsoft YaHei", "Segoe UI Emoji", "Segoe UI Symbol", "SimSun"].map(String::from).to_vec()
+    }
+
+    #[cfg(target_os = "macos")]
+    fn find_system_fallbacks(_: &str) -> Vec<String> {
+        ["PingFang SC", "Apple Color Emoji", "Apple Symbols"].map(String::from).to_vec()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    fn find_system_fallbacks(_: &str) -> Vec<String> {
+        vec![]
+    }
+
+    ///完整回退链，依次为自定义回退字体和系统回退字体
+    pub(crate) fn fallback_chain(&mut self) -> Vec<String> {
+        let mut res = self.fallbacks.clone();
+        if self.system_fallback { res.extend_from_slice(self.system_fallbacks()); }
+        res
+    }
+
     pub fn family(&self) -> &str {
         &self.family
     }
@@ -121,6 +217,9 @@ impl From<&Font> for Font {
         Font {
             family: value.family.clone(),
             size: value.size,
+            fallbacks: value.fallbacks.clone(),
+            system_fallbacks: value.system_fallbacks.clone(),
+            system_fallback: value.system_fallback,
             #[cfg(feature = "gpu")]
             font_system: cosmic_text::FontSystem::new(),
         }
@@ -133,6 +232,8 @@ pub(crate) struct WGpuFont {
     family: String,
     size: f32,
     font: Option<Arc<cosmic_text::Font>>,
+    ///与RichText::fallbacks对应，未找到的字体为None
+    fallbacks: Vec<Option<Arc<cosmic_text::Font>>>,
 }
 
 #[cfg(feature = "gpu")]
@@ -144,6 +245,7 @@ impl WGpuFont {
             family: "".to_string(),
             size: 0.0,
             font: None,
+            fallbacks: vec![],
         }
     }
 
@@ -163,6 +265,9 @@ impl WGpuFont {
         let size = text.size.get_or_insert_with(|| ui.context.font.size());
         self.family = family.to_string();
         self.size = *size;
+        text.resolve_fallbacks(&mut ui.context.font);
+        let system = ui.context.font.system_mut();
+        self.fallbacks = text.fallbacks.iter().map(|x| self.get_font_by_family(system, x).ok()).collect();
         // self.buffer.set_metrics(ui.context.font.system_mut(), Metrics::new(self.size, text.height));
         self.init(ui)?;
         Ok(())
@@ -202,7 +307,10 @@ impl WGpuFont {
         let glyph_positions = glyph_buffer.glyph_positions();
         for (position, ch) in glyph_positions.iter().zip(line.chars()) {
             let x_advance = position.x_advance as f32 / font_scale + 0.0;
-            let cchar = CChar::new(ch, x_advance * self.size);
+            let cchar = match font.rustybuzz().glyph_index(ch) {
+                None => self.measure_fallback(ch).unwrap_or(CChar::new(ch, x_advance * self.size)),
+                Some(_) => CChar::new(ch, x_advance * self.size),
+            };
             if wrap && line_char.width + cchar.width >= max_wrap {
                 let mut line = mem::take(&mut line_char);
                 line.auto_wrap = true;
@@ -218,7 +326,21 @@ impl WGpuFont {
         Ok(res)
     }
 
+    ///使用首个包含字符的回退字体测量
+    fn measure_fallback(&self, ch: char) -> Option<CChar> {
+        for (index, font) in self.fallbacks.iter().enumerate() {
+            let Some(font) = font else { continue; };
+            let face = font.rustybuzz();
+            let Some(glyph) = face.glyph_index(ch) else { continue; };
+            let x_advance = face.glyph_hor_advance(glyph).unwrap_or(0) as f32 / face.units_per_em() as f32;
+            return Some(CChar::new(ch, x_advance * self.size).with_font(index + 1));
+        }
+        None
+    }
+
     pub fn measure_char(&self, ch: char) -> UiResult<CChar> {
+        let font = self.font.as_ref().ok_or(UiError::OptNone)?;
+        if font.rustybuzz().glyph_index(ch).is_none() && let Some(cchar) = self.measure_fallback(ch) { return Ok(cchar); }
         let mut buffer = UnicodeBuffer::default();
         buffer.set_direction(Direction::LeftToRight);
         buffer.push_str(ch.to_string().as_str());
@@ -231,6 +353,23 @@ impl WGpuFont {
         Ok(CChar::new(ch, x_advance * self.size))
     }
 
+    ///装饰线度量，取自字体的post及OS/2表
+    pub(crate) fn metrics(&self) -> UiResult<FontMetrics> {
+        let scale_font = self.glyph_font.as_ref().ok_or(UiError::OptNone)?.as_scaled(PxScale::from(self.size));
+        let mut metrics = FontMetrics::estimate(scale_font.ascent(), -scale_font.descent());
+        let face = self.font.as_ref().ok_or(UiError::OptNone)?.rustybuzz();
+        let scale = self.size / face.units_per_em() as f32;
+        if let Some(underline) = face.underline_metrics() {
+            metrics.underline_position = -underline.position as f32 * scale;
+            metrics.underline_thickness = (underline.thickness as f32 * scale).max(1.0);
+        }
+        if let Some(strikeout) = face.strikeout_metrics() {
+            metrics.strikeout_position = strikeout.position as f32 * scale;
+            metrics.strikeout_thickness = (strikeout.thickness as f32 * scale).max(1.0);
+        }
+        Ok(metrics)
+    }
+
     pub(crate) fn line_height(&self) -> UiResult<f32> {
         let scale = PxScale::from(self.size);
         let scale_font = self.glyph_font.as_ref().ok_or(UiError::OptNone)?.as_scaled(scale);
@@ -271,6 +410,18 @@ impl FontKind {
         }
     }
 
+    ///需在set_family_size之后调用
+    pub(crate) fn metrics(&self) -> UiResult<FontMetrics> {
+        match self {
+            #[cfg(feature = "gpu")]
+            FontKind::WGpu(font) => font.metrics(),
+            #[cfg(all(target_os = "linux", not(feature = "gpu")))]
+            FontKind::X11(font) => font.metrics(),
+            #[cfg(all(target_os = "windows", not(feature = "gpu")))]
+            FontKind::Win32(font) => font.metrics(),
+        }
+    }
+
     pub(crate) fn set_family_size(&mut self, ui: &mut Ui, text: &mut RichText) -> UiResult<()> {
         match self {
             #[cfg(feature = "gpu")]
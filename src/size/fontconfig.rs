@@ -0,0 +1,53 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::null_mut;
+
+const FC_MATCH_PATTERN: c_int = 0;
+const FC_RESULT_MATCH: c_int = 0;
+const FC_TRUE: c_int = 1;
+
+#[allow(dead_code)]
+#[repr(C)]
+struct FcFontSet {
+    nfont: c_int,
+    sfont: c_int,
+    fonts: *mut *mut c_void,
+}
+
+unsafe extern "C" {
+    fn FcNameParse(name: *const u8) -> *mut c_void;
+    fn FcConfigSubstitute(config: *mut c_void, pattern: *mut c_void, kind: c_int) -> c_int;
+    fn FcDefaultSubstitute(pattern: *mut c_void);
+    fn FcFontSort(config: *mut c_void, pattern: *mut c_void, trim: c_int, csp: *mut *mut c_void, result: *mut c_int) -> *mut FcFontSet;
+    fn FcPatternGetString(pattern: *mut c_void, object: *const c_char, n: c_int, value: *mut *mut u8) -> c_int;
+    fn FcFontSetDestroy(set: *mut FcFontSet);
+    fn FcPatternDestroy(pattern: *mut c_void);
+}
+
+///通过fontconfig查询family的回退字体，按匹配程度排序，不包含family本身
+pub(crate) fn fallback_families(family: &str, limit: usize) -> Vec<String> {
+    let mut res = vec![];
+    let Ok(name) = CString::new(family) else { return res; };
+    let object = c"family";
+    unsafe {
+        let pattern = FcNameParse(name.as_ptr() as *const u8);
+        if pattern.is_null() { return res; }
+        FcConfigSubstitute(null_mut(), pattern, FC_MATCH_PATTERN);
+        FcDefaultSubstitute(pattern);
+        let mut result = 0;
+        let set = FcFontSort(null_mut(), pattern, FC_TRUE, null_mut(), &mut result);
+        FcPatternDestroy(pattern);
+        if set.is_null() { return res; }
+        let fonts = std::slice::from_raw_parts((*set).fonts, (*set).nfont as usize);
+        for font in fonts {
+            let mut value = null_mut();
+            if FcPatternGetString(*font, object.as_ptr(), 0, &mut value) != FC_RESULT_MATCH || value.is_null() { continue; }
+            let name = CStr::from_ptr(value as *const c_char).to_string_lossy().to_string();
+            if name == family || res.contains(&name) { continue; }
+            res.push(name);
+            if res.len() >= limit { break; }
+        }
+        FcFontSetDestroy(set);
+    }
+    res
+}
@@ -8,6 +8,8 @@ pub mod pos;
 pub mod radius;
 pub mod font;
 pub mod margin;
+#[cfg(target_os = "linux")]
+pub(crate) mod fontconfig;
 
 #[derive(Clone, Debug, Copy, PartialEq)]
 #[cfg_attr(feature = "gpu", repr(C))]
@@ -178,6 +180,12 @@ impl Geometry {
         self.width
     }
 
+    ///返回item允许的最大宽度(fix或max)，不含padding和margin
+    pub(crate) fn limit_width(&self) -> Option<f32> {
+        let width = self.fix_width.or(self.max_width)?;
+        Some(width - self.padding.horizontal() - self.margin.horizontal())
+    }
+
     ///返回item的宽度，含padding和margin
     pub(crate) fn margin_width(&self) -> f32 {
         self.context_width() + self.padding.horizontal() + self.margin.horizontal()
@@ -10,7 +10,7 @@
 /// ```
 ///
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -64,6 +64,77 @@ impl Color {
         Color { r, g, b, a }
     }
 
+    ///由色相(0-360)、饱和度(0-1)、明度(0-1)及透明度创建颜色
+    /// ```
+    /// use xlui::*;
+    ///
+    /// let color = Color::from_hsva(120.0, 1.0, 1.0, 255);
+    /// assert_eq!(color, Color::GREEN);
+    /// assert_eq!(color.to_hsv(), (120.0, 1.0, 1.0));
+    /// assert_eq!(Color::from_hex("#FFA500"), Some(Color::ORANGE));
+    /// assert_eq!(Color::rgba(255, 0, 0, 128).to_hex(), "#FF000080");
+    /// ```
+    pub fn from_hsva(h: f32, s: f32, v: f32, a: u8) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = v - c;
+        let channel = |x: f32| ((x + m) * 255.0).round() as u8;
+        Color { r: channel(r), g: channel(g), b: channel(b), a }
+    }
+
+    ///(色相,饱和度,明度)，灰色的色相为0
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let [r, g, b] = self.as_gamma_rgb();
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        (h, s, max)
+    }
+
+    ///#RRGGBB，透明时为#RRGGBBAA
+    pub fn to_hex(&self) -> String {
+        match self.a {
+            255 => format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b),
+            _ => format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a),
+        }
+    }
+
+    ///解析#RGB、#RRGGBB或#RRGGBBAA，#可以省略
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.chars().all(|x| x.is_ascii_hexdigit()) { return None; }
+        let channel = |index: usize| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok();
+        match hex.len() {
+            3 => {
+                let value = u16::from_str_radix(hex, 16).ok()?;
+                let channel = |shift: u16| ((value >> shift) & 0xF) as u8 * 17;
+                Some(Color::rgb(channel(8), channel(4), channel(0)))
+            }
+            6 => Some(Color::rgb(channel(0)?, channel(1)?, channel(2)?)),
+            8 => Some(Color::rgba(channel(0)?, channel(1)?, channel(2)?, channel(3)?)),
+            _ => None,
+        }
+    }
+
     pub fn as_rgb_u32(&self) -> u32 {
         ((self.b as u32) << 16) | ((self.g as u32) << 8) | (self.r as u32)
     }
@@ -91,4 +162,20 @@ impl Color {
     pub(crate) fn a_f64(&self) -> f64 {
         self.a as f64 / 255.0
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Color;
+
+    #[test]
+    fn from_hex_rejects_non_hex_digits() {
+        assert_eq!(Color::from_hex("#1E90FF").map(|x| x.to_hex()), Some("#1E90FF".to_string()));
+        assert_eq!(Color::from_hex("f80").map(|x| x.to_hex()), Some("#FF8800".to_string()));
+        assert_eq!(Color::from_hex("#11223344").map(|x| x.a), Some(0x44));
+        assert!(Color::from_hex("+12").is_none());
+        assert!(Color::from_hex("#+12345").is_none());
+        assert!(Color::from_hex("#12 456").is_none());
+        assert!(Color::from_hex("#12345").is_none());
+    }
+}
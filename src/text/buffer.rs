@@ -1,11 +1,21 @@
 #[cfg(feature = "gpu")]
 use cosmic_text::Metrics;
 use crate::align::Align;
-use crate::error::UiResult;
+use crate::error::{UiError, UiResult};
 use crate::size::Geometry;
 use crate::text::cchar::LineChar;
 use crate::text::rich::RichText;
-use crate::text::TextWrap;
+use crate::text::{FontMetrics, TextSpan, TextWrap};
+#[cfg(feature = "gpu")]
+use crate::render::{RenderParam, VisualStyle};
+#[cfg(feature = "gpu")]
+use crate::shape::Shape;
+#[cfg(feature = "gpu")]
+use crate::size::rect::Rect;
+#[cfg(feature = "gpu")]
+use crate::style::color::Color;
+#[cfg(feature = "gpu")]
+use crate::text::TextDecoration;
 use crate::ui::Ui;
 use crate::Padding;
 #[cfg(feature = "gpu")]
@@ -15,6 +25,7 @@ use glyphon::Shaping;
 #[cfg(feature = "gpu")]
 use wgpu::MultisampleState;
 use crate::size::font::FontKind;
+use std::ops::Range;
 
 pub struct TextBuffer {
     pub(crate) text: RichText,
@@ -23,11 +34,20 @@ pub struct TextBuffer {
     pub(crate) render: Option<glyphon::TextRenderer>,
     #[cfg(feature = "gpu")]
     buffer: cosmic_text::Buffer,
+    ///装饰线及背景，glyphon不支持装饰，使用矩形绘制
+    #[cfg(feature = "gpu")]
+    decorations: Vec<RenderParam>,
+    ///延迟测量时glyphon中仅设置可视的行，None时需重新设置
+    #[cfg(feature = "gpu")]
+    glyphon_window: Option<Range<usize>>,
     pub(crate) clip_x: f32,
     pub(crate) clip_y: f32,
     pub(crate) change: bool,
     pub(crate) lines: Vec<LineChar>,
     font_buffer: FontKind,
+    elided: bool,
+    ///延迟测量，仅保存各行字符，使用时再按需测量
+    pub(crate) lazy: bool,
 }
 
 impl TextBuffer {
@@ -39,29 +59,93 @@ impl TextBuffer {
             render: None,
             #[cfg(feature = "gpu")]
             buffer: cosmic_text::Buffer::new_empty(Metrics::new(14.0, 10.0)),
+            #[cfg(feature = "gpu")]
+            decorations: vec![],
+            #[cfg(feature = "gpu")]
+            glyphon_window: None,
             clip_x: 0.0,
             clip_y: 0.0,
             change: false,
             lines: vec![],
             font_buffer: FontKind::new(),
+            elided: false,
+            lazy: false,
         }
     }
 
     fn reset(&mut self) {
         let wrap = self.geometry.is_fix_width() && self.text.wrap.is_wrap();
-        self.lines = self.font_buffer.measure_text(&self.text, wrap, self.geometry.context_width()).unwrap();
+        self.lines = if self.lazy {
+            let mut lines: Vec<LineChar> = self.text.text.replace("\r\n", "\n").split('\n').map(LineChar::unmeasured).collect();
+            if let Some(last) = lines.last_mut() { last.auto_wrap = true; }
+            lines
+        } else {
+            self.font_buffer.measure_text(&self.text, wrap, self.geometry.context_width()).unwrap()
+        };
+        self.elided = self.elide().unwrap();
         self.text.width = self.lines.iter().map(|x| x.width).reduce(f32::max).unwrap_or(self.geometry.context_width());
     }
 
+    ///按最大行数和省略方式处理已测量的行，返回是否发生了省略
+    fn elide(&mut self) -> UiResult<bool> {
+        let limit_width = self.geometry.limit_width();
+        let mut elided = false;
+        if let Some(max_lines) = self.text.max_lines && max_lines != 0 && self.lines.len() > max_lines {
+            let ellipsis = self.font_buffer.measure_char('…')?;
+            //被截断的行及换行符都计入最后一行的省略字符数
+            let rest: usize = self.lines[max_lines - 1..].iter().map(|x| x.len() + if x.auto_wrap { 0 } else { 1 }).sum();
+            self.lines.truncate(max_lines);
+            let last = self.lines.last_mut().ok_or(UiError::OptNone)?;
+            let rest = rest - last.len();
+            last.auto_wrap = true;
+            last.elide_end(ellipsis, limit_width.unwrap_or(f32::MAX));
+            if let Some((_, removed)) = last.elided.as_mut() { *removed += rest; }
+            elided = true;
+        }
+        if let Some(limit_width) = limit_width && self.text.wrap.is_elide() {
+            let ellipsis = self.font_buffer.measure_char('…')?;
+            for line in self.lines.iter_mut() {
+                elided |= line.elide(&self.text.wrap, ellipsis.clone(), limit_width);
+            }
+        }
+        Ok(elided)
+    }
+
+    ///重新测量同一逻辑行内的lines[range]，返回测量后的行数
+    pub(crate) fn measure_range(&mut self, range: Range<usize>) -> UiResult<usize> {
+        let wrap = self.geometry.is_fix_width() && self.text.wrap.is_wrap();
+        let auto_wrap = self.lines.get(range.end - 1).ok_or(UiError::OptNone)?.auto_wrap;
+        let text: String = self.lines[range.clone()].iter().flat_map(|x| x.chars.iter().map(|c| c.cchar)).collect();
+        let mut lines = self.font_buffer.measure_text(&RichText::new(text), wrap, self.geometry.context_width())?;
+        lines.last_mut().ok_or(UiError::OptNone)?.auto_wrap = auto_wrap;
+        let count = lines.len();
+        self.lines.splice(range, lines);
+        #[cfg(feature = "gpu")]
+        { self.glyphon_window = None; }
+        Ok(count)
+    }
+
+    ///文本是否被省略显示
+    pub fn is_elided(&self) -> bool {
+        self.elided
+    }
+
+    ///实际显示的文本，省略时与完整文本不同
+    pub fn display_text(&self) -> String {
+        if !self.elided { return self.text.text.clone(); }
+        self.lines.iter().map(|x| x.line_text.as_str()).collect::<Vec<_>>().join("\n")
+    }
+
     pub fn init(&mut self, ui: &mut Ui) {
         self.text.height = self.line_height(ui).unwrap();
+        self.text.metrics = self.font_buffer.metrics().unwrap_or(FontMetrics::estimate(self.text.height * 0.8, self.text.height * 0.2));
         self.reset();
         #[cfg(feature = "gpu")]
         {
             let font_system = ui.context.font.system_mut();
             self.buffer.set_metrics(font_system, Metrics::new(self.text.font_size(), self.text.height));
             self.buffer.set_wrap(font_system, self.text.wrap.as_gamma());
-            self.buffer.set_text(font_system, &self.text.text, &self.text.font_family(), Shaping::Advanced);
+            self.set_glyphon_text(font_system);
             let render = glyphon::TextRenderer::new(&mut ui.context.render.text.atlas, &ui.device.device, MultisampleState {
                 count: SAMPLE_COUNT,
                 mask: !0,
@@ -74,8 +158,95 @@ impl TextBuffer {
         self.buffer.set_size(ui.context.font.system_mut(), Some(self.geometry.context_width()), Some(self.geometry.context_height()));
     }
 
+    ///可视行的背景及装饰线矩形，返回(背景,装饰线)
+    #[cfg(feature = "gpu")]
+    fn decoration_rects(&self) -> (Vec<(Rect, Color)>, Vec<(Rect, Color)>) {
+        let mut backgrounds = vec![];
+        let mut lines = vec![];
+        if !self.text.has_decoration() { return (backgrounds, lines); }
+        let left = self.geometry.context_left() + self.clip_x;
+        let right_bound = self.geometry.context_right();
+        let rect = |x_min: f32, x_max: f32, y_min: f32, y_max: f32| {
+            let mut rect = Rect::new();
+            rect.set_x_min(x_min.max(self.geometry.context_left()));
+            rect.set_x_max(x_max.min(right_bound));
+            rect.set_y_min(y_min);
+            rect.set_y_max(y_max);
+            rect
+        };
+        let first = if self.text.height > 0.0 { ((-self.clip_y / self.text.height).floor().max(0.0) as usize).min(self.lines.len()) } else { 0 };
+        let mut start = LineChar::start_of(&self.lines, first);
+        for (index, line) in self.lines.iter().enumerate().skip(first) {
+            let top = self.geometry.context_top() + self.clip_y + index as f32 * self.text.height;
+            let line_start = start;
+            start += line.source_len() + if line.auto_wrap { 0 } else { 1 };
+            if top + self.text.height < self.geometry.context_top() { continue; }
+            if top > self.geometry.context_bottom() { break; }
+            if line.width <= 0.0 { continue; }
+            let baseline = top + self.text.metrics.ascent;
+            for (x_min, x_max, decoration) in line.decorations(line_start, &self.text) {
+                let (x_min, x_max) = (left + x_min, left + x_max);
+                if let Some(background) = &decoration.background {
+                    backgrounds.push((rect(x_min, x_max, top, top + self.text.height), background.clone()));
+                }
+                let color = decoration.color.clone().unwrap_or(self.text.color.clone());
+                for (offset, thickness, wavy) in decoration.lines(&self.text.metrics) {
+                    let y = baseline + offset;
+                    if !wavy {
+                        lines.push((rect(x_min, x_max, y - thickness / 2.0, y + thickness / 2.0), color.clone()));
+                        continue;
+                    }
+                    //波浪线按折线采样为小矩形
+                    let points = TextDecoration::wave_points(x_min, x_max, y, thickness);
+                    for pair in points.windows(2) {
+                        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                        let mut x = x0;
+                        while x < x1 {
+                            let py = y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+                            lines.push((rect(x, (x + 1.0).min(x1), py - thickness / 2.0, py + thickness / 2.0), color.clone()));
+                            x += 1.0;
+                        }
+                    }
+                }
+            }
+        }
+        (backgrounds, lines)
+    }
+
+    #[cfg(feature = "gpu")]
+    fn draw_decorations(&mut self, ui: &mut Ui, rects: Vec<(Rect, Color)>, offset: usize) -> usize {
+        for (index, (rect, color)) in rects.iter().enumerate() {
+            if self.decorations.len() <= offset + index { self.decorations.push(RenderParam::new(Shape::rectangle())); }
+            let render = &mut self.decorations[offset + index];
+            render.set_style(VisualStyle::same((color.clone(), 0.0, 0).into()));
+            *render.rect_mut() = rect.clone();
+            render.draw(ui, false, false, false);
+        }
+        offset + rects.len()
+    }
+
+    ///延迟测量时按滚动位置设置glyphon中的可视行，返回首个可视行的y偏移
+    #[cfg(feature = "gpu")]
+    fn update_glyphon_window(&mut self, ui: &mut Ui) -> f32 {
+        let height = self.text.height;
+        if !self.lazy || height <= 0.0 { return 0.0; }
+        let first = ((-self.clip_y / height).floor().max(0.0) as usize).min(self.lines.len());
+        let count = (self.geometry.context_height() / height).ceil() as usize + 1;
+        let window = first..(first + count).min(self.lines.len());
+        if self.glyphon_window.as_ref() != Some(&window) {
+            let font_system = ui.context.font.system_mut();
+            self.buffer.set_size(font_system, Some(self.geometry.context_width()), Some(count as f32 * height));
+            self.set_glyphon_lines(font_system, window.clone());
+            self.glyphon_window = Some(window);
+        }
+        first as f32 * height
+    }
+
     #[cfg(feature = "gpu")]
     pub(crate) fn redraw(&mut self, ui: &mut Ui) {
+        let window_top = self.update_glyphon_window(ui);
+        let (backgrounds, lines) = self.decoration_rects();
+        let offset = self.draw_decorations(ui, backgrounds, 0);
         let bounds = glyphon::TextBounds {
             left: self.geometry.x_i32(),
             top: 0,
@@ -85,7 +256,7 @@ impl TextBuffer {
         let area = glyphon::TextArea {
             buffer: &self.buffer,
             left: self.geometry.context_left() + self.clip_x,
-            top: self.geometry.context_top() + self.clip_y,
+            top: self.geometry.context_top() + self.clip_y + window_top,
             scale: 1.0,
             bounds,
             default_color: self.text.color.as_glyphon_color(),
@@ -98,13 +269,14 @@ impl TextBuffer {
             &ui.context.viewport, vec![area],
             &mut ui.context.render.text.cache).unwrap();
         let pass = &mut ui.paint.as_mut().unwrap().pass;
-        self.render.as_mut().unwrap().render(&mut ui.context.render.text.atlas, &ui.context.viewport, pass).unwrap()
+        self.render.as_mut().unwrap().render(&mut ui.context.render.text.atlas, &ui.context.viewport, pass).unwrap();
+        self.draw_decorations(ui, lines, offset);
     }
 
     #[cfg(all(windows, not(feature = "gpu")))]
     pub(crate) fn redraw(&mut self, ui: &mut Ui) {
         let hdc = ui.paint.as_mut().unwrap().hdc;
-        ui.context.window.win32().paint_text(hdc, &self.lines, &self.text, self.geometry.context_rect()).unwrap();
+        ui.context.window.win32().paint_text(hdc, &self.lines, &self.text, self.geometry.context_rect(), self.clip_x, self.clip_y).unwrap();
     }
 
     pub(crate) fn line_height(&mut self, ui: &mut Ui) -> UiResult<f32> {
@@ -112,6 +284,12 @@ impl TextBuffer {
         self.font_buffer.line_height()
     }
 
+    ///测量单行文本的宽度，需先调用line_height设置字体
+    pub(crate) fn text_width(&self, text: &str) -> f32 {
+        let lines = self.font_buffer.measure_text(&RichText::new(text), false, 0.0);
+        lines.map(|lines| lines.iter().map(|x| x.width).fold(0.0, f32::max)).unwrap_or(0.0)
+    }
+
     #[cfg(all(target_os = "linux", not(feature = "gpu")))]
     pub(crate) fn redraw(&mut self, ui: &mut Ui) {
         let param = &mut ui.paint.as_mut().unwrap();
@@ -149,17 +327,69 @@ impl TextBuffer {
     pub fn update_buffer(&mut self, ui: &mut Ui) {
         if !self.change { return; }
         self.change = false;
-        #[cfg(feature = "gpu")]
-        self.buffer.set_text(ui.context.font.system_mut(), &self.text.text, &self.text.font_family(), Shaping::Advanced);
         self.reset();
+        #[cfg(feature = "gpu")]
+        self.set_glyphon_text(ui.context.font.system_mut());
         self.geometry.set_context_width(self.text.width)
     }
 
+    ///设置每行的样式范围，spans与lines按索引对应
+    pub(crate) fn set_spans(&mut self, ui: &mut Ui, first: usize, spans: Vec<Vec<TextSpan>>) {
+        for (line, spans) in self.lines.iter_mut().skip(first).zip(spans) {
+            line.spans = spans;
+        }
+        #[cfg(feature = "gpu")]
+        self.set_glyphon_text(ui.context.font.system_mut());
+        #[cfg(not(feature = "gpu"))]
+        let _ = ui;
+    }
+
+    ///按行的样式及字体设置glyphon文本，全部为普通行时直接设置文本。
+    ///延迟测量时只在redraw中设置可视的行
+    #[cfg(feature = "gpu")]
+    fn set_glyphon_text(&mut self, font_system: &mut glyphon::FontSystem) {
+        if self.lazy {
+            self.glyphon_window = None;
+            return;
+        }
+        let default_attrs = self.text.font_family();
+        if self.lines.iter().all(|x| x.is_plain()) {
+            self.buffer.set_text(font_system, &self.display_text(), &default_attrs, Shaping::Advanced);
+            return;
+        }
+        self.set_glyphon_lines(font_system, 0..self.lines.len());
+    }
+
+    ///设置lines[range]为glyphon文本，延迟测量时各行已按宽度拆分，自动换行的行之间也需换行
+    #[cfg(feature = "gpu")]
+    fn set_glyphon_lines(&mut self, font_system: &mut glyphon::FontSystem, range: Range<usize>) {
+        let default_attrs = self.text.font_family();
+        let mut texts = vec![];
+        let last = range.end.saturating_sub(1);
+        for (index, line) in self.lines[range.clone()].iter().enumerate() {
+            for (range, color, font) in line.runs() {
+                let mut attrs = default_attrs.clone();
+                if font != 0 { attrs = attrs.family(glyphon::Family::Name(self.text.family_by_index(font))); }
+                if let Some(color) = color { attrs = attrs.color(color.as_glyphon_color()); }
+                texts.push((line.text_by_range(range), attrs));
+            }
+            let wrap = !line.auto_wrap || (self.lazy && range.start + index != last);
+            if wrap { texts.push(("\n".to_string(), default_attrs.clone())); }
+        }
+        let spans = texts.iter().map(|(text, attrs)| (text.as_str(), attrs.clone()));
+        self.buffer.set_rich_text(font_system, spans, &default_attrs, Shaping::Advanced, None);
+    }
+
     pub fn set_wrap(&mut self, wrap: TextWrap) {
         self.change = self.text.wrap == wrap;
         self.text.wrap = wrap;
     }
 
+    pub fn set_max_lines(&mut self, max_lines: usize) {
+        self.change = self.text.max_lines != Some(max_lines);
+        self.text.max_lines = Some(max_lines);
+    }
+
     pub fn fix_width(mut self, w: f32) -> Self {
         self.geometry.set_fix_width(w);
         self
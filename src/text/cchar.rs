@@ -1,24 +1,44 @@
-#[cfg(not(feature = "winit"))]
 use std::ops::Range;
+use crate::style::color::Color;
+use crate::text::{TextDecoration, TextSpan, TextWrap};
+use crate::text::rich::RichText;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct CChar {
     pub(crate) cchar: char,
     pub(crate) width: f32,
+    ///使用的字体，0为主字体，其余为RichText::fallbacks[font-1]
+    pub(crate) font: usize,
 }
 
 impl CChar {
     pub fn new(cchar: char, width: f32) -> CChar {
-        CChar { cchar, width }
+        CChar { cchar, width, font: 0 }
+    }
+
+    pub fn with_font(mut self, font: usize) -> CChar {
+        self.font = font;
+        self
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct LineChar {
     pub(crate) chars: Vec<CChar>,
     pub(crate) line_text: String,
     pub(crate) auto_wrap: bool,
     pub(crate) width: f32,
+    pub(crate) spans: Vec<TextSpan>,
+    ///字符宽度是否已测量，延迟测量的行宽度为0
+    pub(crate) measured: bool,
+    ///省略信息(省略号位置, 被省略的原字符数)
+    pub(crate) elided: Option<(usize, usize)>,
+}
+
+impl Default for LineChar {
+    fn default() -> LineChar {
+        LineChar::new()
+    }
 }
 
 impl LineChar {
@@ -28,6 +48,19 @@ impl LineChar {
             line_text: "".to_string(),
             auto_wrap: true,
             width: 0.0,
+            spans: vec![],
+            measured: true,
+            elided: None,
+        }
+    }
+
+    ///未测量的行，仅保存字符
+    pub(crate) fn unmeasured(line: &str) -> LineChar {
+        LineChar {
+            chars: line.chars().map(|x| CChar::new(x, 0.0)).collect(),
+            auto_wrap: false,
+            measured: false,
+            ..LineChar::new()
         }
     }
 
@@ -36,24 +69,141 @@ impl LineChar {
         self.chars.push(cchar);
     }
 
-    pub fn raw_text(&self) -> String {
-        let mut res: String = self.chars.iter().map(|x| x.cchar.to_string()).collect();
-        if !self.auto_wrap { res += "\n"; }
-        res
-    }
-
     pub fn get_width_in_char(&self, index: usize) -> f32 {
         let mut width = 0.0;
         self.chars[..index].iter().for_each(|x| width += x.width);
         width
     }
 
-    #[cfg(not(feature = "winit"))]
-    pub fn get_text_by_range(&self, r: Range<usize>) -> String {
-        let mut res: String = self.chars[r.clone()].iter().map(|x| x.cchar.to_string()).collect();
-        if !self.auto_wrap && r.end == self.len() { res += "\n"; }
+    pub fn len(&self) -> usize { self.chars.len() }
+
+    ///该行在原文本中的字符数
+    pub(crate) fn source_len(&self) -> usize {
+        match self.elided {
+            Some((_, removed)) => self.len() - 1 + removed,
+            None => self.len(),
+        }
+    }
+
+    ///lines[..index]在原文本中的字符数，即第index行首字符的索引
+    pub(crate) fn start_of(lines: &[LineChar], index: usize) -> usize {
+        lines[..index.min(lines.len())].iter().map(|x| x.source_len() + if x.auto_wrap { 0 } else { 1 }).sum()
+    }
+
+    ///该行与各装饰范围相交的部分，start为行首字符在原文本中的索引，返回(相对行首的起始x,结束x,装饰)
+    pub(crate) fn decorations<'a>(&self, start: usize, text: &'a RichText) -> Vec<(f32, f32, &'a TextDecoration)> {
+        let mut res = vec![];
+        let end = start + self.source_len();
+        for (range, decoration) in text.decoration_spans() {
+            if range.start >= end || range.end <= start || decoration.is_empty() { continue; }
+            let col = |index: usize| (0..self.len()).find(|x| start + self.source_horiz(*x) >= index).unwrap_or(self.len());
+            let (from, to) = (col(range.start), col(range.end));
+            if from >= to { continue; }
+            res.push((self.get_width_in_char(from), self.get_width_in_char(to), decoration));
+        }
         res
     }
 
-    pub fn len(&self) -> usize { self.chars.len() }
+    ///显示列转换为原文本中的列，省略号之后的列需加上被省略的字符数
+    pub(crate) fn source_horiz(&self, horiz: usize) -> usize {
+        match self.elided {
+            Some((pos, removed)) if horiz > pos => horiz - 1 + removed,
+            _ => horiz,
+        }
+    }
+
+    ///按样式范围切分整行，未设置样式的部分颜色为None
+    pub(crate) fn segments(&self) -> Vec<(Range<usize>, Option<&Color>)> {
+        let mut res = vec![];
+        let mut start = 0;
+        let mut spans: Vec<&TextSpan> = self.spans.iter().collect();
+        spans.sort_by_key(|x| x.range.start);
+        for span in spans {
+            let end = span.range.end.min(self.len());
+            if span.range.start < start || span.range.start >= end { continue; }
+            if span.range.start > start { res.push((start..span.range.start, None)); }
+            res.push((span.range.start..end, Some(&span.color)));
+            start = end;
+        }
+        if start < self.len() { res.push((start..self.len(), None)); }
+        res
+    }
+
+    ///在样式范围的基础上按字体继续切分，返回(范围,颜色,字体)
+    pub(crate) fn runs(&self) -> Vec<(Range<usize>, Option<&Color>, usize)> {
+        let mut res = vec![];
+        for (range, color) in self.segments() {
+            let mut start = range.start;
+            for index in range.start + 1..=range.end {
+                if index < range.end && self.chars[index].font == self.chars[start].font { continue; }
+                res.push((start..index, color, self.chars[start].font));
+                start = index;
+            }
+        }
+        res
+    }
+
+    ///整行仅使用主字体且没有样式
+    pub(crate) fn is_plain(&self) -> bool {
+        self.spans.is_empty() && self.chars.iter().all(|x| x.font == 0)
+    }
+
+    pub(crate) fn text_by_range(&self, r: Range<usize>) -> String {
+        self.chars[r].iter().map(|x| x.cchar).collect()
+    }
+
+    fn reset_text(&mut self) {
+        self.width = self.chars.iter().map(|x| x.width).sum();
+        self.line_text = self.chars.iter().map(|x| x.cchar.to_string()).collect();
+    }
+
+    ///末尾强制添加省略号，并保证总宽度不超过max_width
+    pub(crate) fn elide_end(&mut self, ellipsis: CChar, max_width: f32) {
+        let len = self.chars.len();
+        while self.width + ellipsis.width > max_width && let Some(c) = self.chars.pop() {
+            self.width -= c.width;
+        }
+        self.elided = Some((self.chars.len(), len - self.chars.len()));
+        self.chars.push(ellipsis);
+        self.reset_text();
+    }
+
+    ///宽度超出max_width时按省略方式省略，返回是否发生了省略
+    pub(crate) fn elide(&mut self, wrap: &TextWrap, ellipsis: CChar, max_width: f32) -> bool {
+        if self.width <= max_width { return false; }
+        let available = max_width - ellipsis.width;
+        match wrap {
+            TextWrap::ElideStart => {
+                //先找到截断位置再一次移除
+                let mut width = self.width;
+                let cut = self.chars.iter().position(|c| {
+                    if width <= available { return true; }
+                    width -= c.width;
+                    false
+                }).unwrap_or(self.chars.len());
+                self.chars.splice(..cut, [ellipsis]);
+                self.elided = Some((0, cut));
+                self.reset_text();
+            }
+            TextWrap::ElideMiddle => {
+                let mut left_width = 0.0;
+                let mut left = 0;
+                while left < self.chars.len() && left_width + self.chars[left].width <= available / 2.0 {
+                    left_width += self.chars[left].width;
+                    left += 1;
+                }
+                let mut right_width = 0.0;
+                let mut right = self.chars.len();
+                while right > left && left_width + right_width + self.chars[right - 1].width <= available {
+                    right_width += self.chars[right - 1].width;
+                    right -= 1;
+                }
+                self.chars.splice(left..right, [ellipsis]);
+                self.elided = Some((left, right - left));
+                self.reset_text();
+            }
+            _ => self.elide_end(ellipsis, max_width),
+        }
+        true
+    }
 }
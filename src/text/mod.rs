@@ -3,12 +3,174 @@ pub mod buffer;
 pub mod render;
 pub mod rich;
 pub mod cchar;
+pub mod piece;
+
+use crate::style::color::Color;
+use std::ops::Range;
+
+///文本样式范围，range为行内字符索引
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextSpan {
+    pub range: Range<usize>,
+    pub color: Color,
+}
+
+impl TextSpan {
+    pub fn new(range: Range<usize>, color: Color) -> TextSpan {
+        TextSpan { range, color }
+    }
+}
+
+///文本装饰范围，range为整个文本中的字符索引
+#[derive(Clone, PartialEq, Debug)]
+pub struct DecorationSpan {
+    pub range: Range<usize>,
+    pub decoration: TextDecoration,
+}
+
+impl DecorationSpan {
+    pub fn new(range: Range<usize>, decoration: TextDecoration) -> DecorationSpan {
+        DecorationSpan { range, decoration }
+    }
+}
+
+///文本装饰，颜色为None时使用文本颜色，粗细为None时使用字体度量
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct TextDecoration {
+    ///下划线
+    pub underline: bool,
+    ///删除线
+    pub strikethrough: bool,
+    ///上划线
+    pub overline: bool,
+    ///下划线绘制为波浪线，用于提示拼写错误
+    pub wavy: bool,
+    pub color: Option<Color>,
+    pub thickness: Option<f32>,
+    ///高亮背景色
+    pub background: Option<Color>,
+}
+
+impl TextDecoration {
+    pub fn new() -> TextDecoration {
+        TextDecoration::default()
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    pub fn overline(mut self) -> Self {
+        self.overline = true;
+        self
+    }
+
+    pub fn wavy_underline(mut self) -> Self {
+        self.underline = true;
+        self.wavy = true;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn has_lines(&self) -> bool {
+        self.underline || self.strikethrough || self.overline
+    }
+
+    ///既没有装饰线也没有背景色
+    pub fn is_empty(&self) -> bool {
+        self.background.is_none() && !self.has_lines()
+    }
+
+    ///需要绘制的线，返回(相对基线的中心偏移,粗细,是否波浪线)，向下为正
+    pub(crate) fn lines(&self, metrics: &FontMetrics) -> Vec<(f32, f32, bool)> {
+        let mut res = vec![];
+        if self.underline {
+            let thickness = self.thickness.unwrap_or(metrics.underline_thickness);
+            res.push((metrics.underline_position + thickness / 2.0, thickness, self.wavy));
+        }
+        if self.strikethrough {
+            let thickness = self.thickness.unwrap_or(metrics.strikeout_thickness);
+            res.push((-metrics.strikeout_position + thickness / 2.0, thickness, false));
+        }
+        if self.overline {
+            let thickness = self.thickness.unwrap_or(metrics.underline_thickness);
+            res.push((-metrics.ascent + thickness / 2.0, thickness, false));
+        }
+        res
+    }
+
+    ///波浪线的折线顶点，振幅与波长随粗细变化
+    pub(crate) fn wave_points(x_min: f32, x_max: f32, y: f32, thickness: f32) -> Vec<(f32, f32)> {
+        let amplitude = thickness.max(1.0);
+        let step = amplitude * 2.0;
+        let mut res = vec![];
+        let mut x = x_min;
+        let mut up = true;
+        while x < x_max {
+            res.push((x, if up { y - amplitude } else { y + amplitude }));
+            x += step;
+            up = !up;
+        }
+        res.push((x_max, if up { y - amplitude } else { y + amplitude }));
+        res
+    }
+}
+
+///绘制装饰使用的字体度量，位置均相对基线，ascent及strikeout_position向上为正，underline_position向下为正
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct FontMetrics {
+    pub(crate) ascent: f32,
+    pub(crate) underline_position: f32,
+    pub(crate) underline_thickness: f32,
+    pub(crate) strikeout_position: f32,
+    pub(crate) strikeout_thickness: f32,
+}
+
+impl FontMetrics {
+    ///字体未提供装饰度量时按ascent和descent估算
+    pub(crate) fn estimate(ascent: f32, descent: f32) -> FontMetrics {
+        let thickness = ((ascent + descent) / 14.0).max(1.0);
+        FontMetrics {
+            ascent,
+            underline_position: (descent * 0.4).max(1.0),
+            underline_thickness: thickness,
+            strikeout_position: ascent * 0.3,
+            strikeout_thickness: thickness,
+        }
+    }
+}
 
 #[derive(PartialEq)]
 pub enum TextWrap {
     NoWrap,
     WrapAny,
     WrapWorld,
+    ///单行显示，超出宽度时末尾显示省略号
+    ElideEnd,
+    ///单行显示，超出宽度时中间显示省略号
+    ElideMiddle,
+    ///单行显示，超出宽度时开头显示省略号
+    ElideStart,
 }
 
 
@@ -18,7 +180,8 @@ impl TextWrap {
         match self {
             TextWrap::NoWrap => glyphon::Wrap::None,
             TextWrap::WrapAny => glyphon::Wrap::Glyph,
-            TextWrap::WrapWorld => glyphon::Wrap::Word
+            TextWrap::WrapWorld => glyphon::Wrap::Word,
+            TextWrap::ElideEnd | TextWrap::ElideMiddle | TextWrap::ElideStart => glyphon::Wrap::None,
         }
     }
 
@@ -28,4 +191,8 @@ impl TextWrap {
             _ => false
         }
     }
+
+    pub fn is_elide(&self) -> bool {
+        matches!(self, TextWrap::ElideEnd | TextWrap::ElideMiddle | TextWrap::ElideStart)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,220 @@
+use std::ops::Range;
+
+///原始文本按此字节数切分为片段，避免大文本定位时逐字符扫描
+const CHUNK_SIZE: usize = 4096;
+
+#[derive(Clone)]
+struct Piece {
+    added: bool,
+    start: usize, //字节起始
+    len: usize, //字节长度
+    chars: usize, //字符数量
+}
+
+///片段表，文本由只读的原始缓冲和只追加的新增缓冲中的片段组成，
+///插入删除只修改片段列表，不移动已有文本
+pub struct PieceTable {
+    original: String,
+    added: String,
+    pieces: Vec<Piece>,
+    len: usize,
+}
+
+impl PieceTable {
+    pub fn new(text: impl ToString) -> PieceTable {
+        let original = text.to_string();
+        let mut pieces = vec![];
+        let mut start = 0;
+        while start < original.len() {
+            let mut end = (start + CHUNK_SIZE).min(original.len());
+            while !original.is_char_boundary(end) { end += 1; }
+            let chars = original[start..end].chars().count();
+            pieces.push(Piece { added: false, start, len: end - start, chars });
+            start = end;
+        }
+        PieceTable {
+            len: pieces.iter().map(|x| x.chars).sum(),
+            original,
+            added: String::new(),
+            pieces,
+        }
+    }
+
+    ///字符数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    fn piece_text(&self, piece: &Piece) -> &str {
+        let buffer = if piece.added { &self.added } else { &self.original };
+        &buffer[piece.start..piece.start + piece.len]
+    }
+
+    ///在字符索引处切分片段，返回切分后右侧片段的位置
+    fn split(&mut self, index: usize) -> usize {
+        let mut start = 0;
+        for i in 0..self.pieces.len() {
+            let chars = self.pieces[i].chars;
+            if index == start { return i; }
+            if index < start + chars {
+                let piece = self.pieces[i].clone();
+                let text = self.piece_text(&piece);
+                let offset = text.char_indices().nth(index - start).map(|(x, _)| x).unwrap_or(text.len());
+                self.pieces[i] = Piece { len: offset, chars: index - start, ..piece.clone() };
+                self.pieces.insert(i + 1, Piece {
+                    added: piece.added,
+                    start: piece.start + offset,
+                    len: piece.len - offset,
+                    chars: chars - (index - start),
+                });
+                return i + 1;
+            }
+            start += chars;
+        }
+        self.pieces.len()
+    }
+
+    ///在字符索引处插入文本
+    pub fn insert(&mut self, index: usize, text: &str) {
+        if text.is_empty() { return; }
+        let index = index.min(self.len);
+        let chars = text.chars().count();
+        let position = self.split(index);
+        let start = self.added.len();
+        self.added.push_str(text);
+        self.len += chars;
+        //连续输入时直接扩展上一个新增片段
+        if position > 0 && let Some(previous) = self.pieces.get_mut(position - 1) && previous.added && previous.start + previous.len == start {
+            previous.len += text.len();
+            previous.chars += chars;
+            return;
+        }
+        self.pieces.insert(position, Piece { added: true, start, len: text.len(), chars });
+    }
+
+    ///删除字符索引范围内的文本
+    pub fn remove(&mut self, range: Range<usize>) {
+        let end = range.end.min(self.len);
+        if range.start >= end { return; }
+        let start = self.split(range.start);
+        let end = self.split(end);
+        let removed: usize = self.pieces.drain(start..end).map(|x| x.chars).sum();
+        self.len -= removed;
+    }
+
+    ///获取字符索引范围内的文本
+    pub fn slice(&self, range: Range<usize>) -> String {
+        let mut res = String::new();
+        let mut start = 0;
+        for piece in self.pieces.iter() {
+            let end = start + piece.chars;
+            if end > range.start && start < range.end {
+                let text = self.piece_text(piece);
+                let skip = range.start.saturating_sub(start);
+                let take = range.end.min(end) - start - skip;
+                res.extend(text.chars().skip(skip).take(take));
+            }
+            if end >= range.end { break; }
+            start = end;
+        }
+        res
+    }
+
+    ///从字符索引处开始向后遍历字符
+    pub fn chars_from(&self, index: usize) -> impl Iterator<Item = char> + '_ {
+        let mut start = 0;
+        self.pieces.iter().filter_map(move |piece| {
+            let skip = index.saturating_sub(start);
+            start += piece.chars;
+            (skip < piece.chars).then(|| self.piece_text(piece).chars().skip(skip))
+        }).flatten()
+    }
+
+    ///从字符索引处(不含)开始向前逆序遍历字符
+    pub fn chars_before(&self, index: usize) -> impl Iterator<Item = char> + '_ {
+        let mut end = self.len;
+        self.pieces.iter().rev().filter_map(move |piece| {
+            let skip = end.saturating_sub(index);
+            end -= piece.chars;
+            (skip < piece.chars).then(|| self.piece_text(piece).chars().rev().skip(skip))
+        }).flatten()
+    }
+
+    pub fn text(&self) -> String {
+        let mut res = String::with_capacity(self.pieces.iter().map(|x| x.len).sum());
+        for piece in self.pieces.iter() {
+            res.push_str(self.piece_text(piece));
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PieceTable;
+
+    ///包含多字节字符，使片段边界不在字节整数倍处
+    fn sample() -> String {
+        (0..3000).map(|x| if x % 3 == 0 { '中' } else { char::from(b'a' + (x % 26) as u8) }).collect()
+    }
+
+    fn assert_same(table: &PieceTable, model: &[char]) {
+        assert_eq!(table.len(), model.len());
+        assert_eq!(table.text(), model.iter().collect::<String>());
+    }
+
+    #[test]
+    fn new_splits_on_char_boundaries() {
+        let text = sample();
+        let table = PieceTable::new(&text);
+        assert!(table.pieces.len() > 1);
+        assert_eq!(table.text(), text);
+        assert_eq!(table.len(), text.chars().count());
+    }
+
+    #[test]
+    fn insert_remove_across_chunk_boundary() {
+        let text = sample();
+        let mut table = PieceTable::new(&text);
+        let mut model = text.chars().collect::<Vec<_>>();
+        let boundary = table.pieces[0].chars;
+        for index in [boundary - 1, boundary, boundary + 1] {
+            table.insert(index, "插入x");
+            model.splice(index..index, "插入x".chars());
+            assert_same(&table, &model);
+        }
+        //连续输入扩展同一新增片段
+        let count = table.pieces.len();
+        table.insert(boundary + 4, "y");
+        model.insert(boundary + 4, 'y');
+        assert_eq!(table.pieces.len(), count);
+        assert_same(&table, &model);
+        table.remove(boundary - 10..boundary + 10);
+        model.drain(boundary - 10..boundary + 10);
+        assert_same(&table, &model);
+        table.remove(model.len() - 5..model.len() + 100);
+        model.truncate(model.len() - 5);
+        assert_same(&table, &model);
+        table.insert(usize::MAX, "尾");
+        model.push('尾');
+        assert_same(&table, &model);
+        table.remove(3..3);
+        assert_same(&table, &model);
+    }
+
+    #[test]
+    fn slice_and_iterate_across_chunk_boundary() {
+        let text = sample();
+        let mut table = PieceTable::new(&text);
+        let boundary = table.pieces[0].chars;
+        table.insert(boundary, "新增");
+        let model = table.text().chars().collect::<Vec<_>>();
+        for range in [0..0, 0..5, boundary - 3..boundary + 5, boundary + 1..boundary + 2, model.len() - 2..model.len()] {
+            assert_eq!(table.slice(range.clone()), model[range].iter().collect::<String>());
+        }
+        for index in [0, boundary - 1, boundary, boundary + 2, model.len()] {
+            assert!(table.chars_from(index).eq(model[index..].iter().copied()));
+            assert!(table.chars_before(index).eq(model[..index].iter().rev().copied()));
+        }
+    }
+}
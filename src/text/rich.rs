@@ -1,5 +1,7 @@
 use crate::style::color::Color;
-use crate::text::TextWrap;
+use crate::text::{DecorationSpan, FontMetrics, TextDecoration, TextWrap};
+use std::ops::Range;
+use crate::Font;
 use std::fmt::Display;
 
 /// ### RichText的示例用法
@@ -13,10 +15,19 @@ use std::fmt::Display;
 ///         .color(Color::BLUE)
 ///         //设置换行类型
 ///         .wrap(TextWrap::NoWrap)
+///         //设置最大行数，超出部分以"…"结尾
+///         .max_lines(2)
 ///         //设置字号
 ///         .size(16.0)
 ///         //设置字体
-///         .family("微软雅黑");
+///         .family("微软雅黑")
+///         //设置回退字体，字体缺少字符时使用
+///         .fallback("Noto Color Emoji")
+///         //设置下划线及高亮背景
+///         .underline()
+///         .background(Color::YELLOW)
+///         //仅为部分字符设置装饰
+///         .decorate(0..2, TextDecoration::new().wavy_underline().with_color(Color::RED));
 ///     ui.label(text);
 ///     ui.label(RichText::new("Rich").color(Color::YELLOW));
 /// }
@@ -32,6 +43,15 @@ pub struct RichText {
     pub(crate) width: f32,
     pub(crate) wrap: TextWrap,
     pub(crate) family: Option<String>,
+    ///回退字体，CChar::font为1时对应fallbacks[0]
+    pub(crate) fallbacks: Vec<String>,
+    ///最大显示行数
+    pub(crate) max_lines: Option<usize>,
+    ///作用于整个文本的装饰
+    pub(crate) decoration: TextDecoration,
+    ///作用于部分字符的装饰
+    pub(crate) decorations: Vec<DecorationSpan>,
+    pub(crate) metrics: FontMetrics,
 }
 
 impl RichText {
@@ -44,6 +64,11 @@ impl RichText {
             width: 0.0,
             wrap: TextWrap::NoWrap,
             family: None,
+            fallbacks: vec![],
+            max_lines: None,
+            decoration: TextDecoration::new(),
+            decorations: vec![],
+            metrics: FontMetrics::default(),
         }
     }
 
@@ -52,6 +77,11 @@ impl RichText {
         self.wrap = wrap;
         self
     }
+    ///设置最大显示行数，超出的部分会被省略并以"…"结尾
+    pub fn max_lines(mut self, max_lines: usize) -> RichText {
+        self.max_lines = Some(max_lines);
+        self
+    }
     ///字体大小，如果没有提供就会使用全局字体大小,WindowAttribute::font
     pub fn size(mut self, size: f32) -> RichText {
         self.size = Some(size);
@@ -62,12 +92,90 @@ impl RichText {
         self.family = Some(family.to_string());
         self
     }
+    ///添加回退字体，优先于全局字体的回退链
+    pub fn fallback(mut self, family: impl ToString) -> RichText {
+        self.fallbacks.push(family.to_string());
+        self
+    }
+    ///设置文本装饰
+    pub fn decoration(mut self, decoration: TextDecoration) -> RichText {
+        self.decoration = decoration;
+        self
+    }
+    ///下划线
+    ///为range范围内的字符设置装饰，range为字符索引
+    pub fn decorate(mut self, range: Range<usize>, decoration: TextDecoration) -> RichText {
+        self.decorations.push(DecorationSpan::new(range, decoration));
+        self
+    }
+
+    pub fn underline(mut self) -> RichText {
+        self.decoration.underline = true;
+        self
+    }
+    ///波浪下划线，用于提示拼写错误
+    pub fn wavy_underline(mut self) -> RichText {
+        self.decoration.underline = true;
+        self.decoration.wavy = true;
+        self
+    }
+    ///删除线
+    pub fn strikethrough(mut self) -> RichText {
+        self.decoration.strikethrough = true;
+        self
+    }
+    ///上划线
+    pub fn overline(mut self) -> RichText {
+        self.decoration.overline = true;
+        self
+    }
+    ///装饰线颜色，默认使用文本颜色
+    pub fn decoration_color(mut self, color: Color) -> RichText {
+        self.decoration.color = Some(color);
+        self
+    }
+    ///装饰线粗细，默认使用字体度量
+    pub fn decoration_thickness(mut self, thickness: f32) -> RichText {
+        self.decoration.thickness = Some(thickness);
+        self
+    }
+    ///高亮背景色
+    pub fn background(mut self, color: Color) -> RichText {
+        self.decoration.background = Some(color);
+        self
+    }
     ///设置字体颜色
     pub fn color(mut self, color: Color) -> RichText {
         self.color = color;
         self
     }
 
+    ///在自身回退字体后追加全局字体的回退链
+    pub(crate) fn resolve_fallbacks(&mut self, font: &mut Font) {
+        for family in font.fallback_chain() {
+            if Some(&family) == self.family.as_ref() || self.fallbacks.contains(&family) { continue; }
+            self.fallbacks.push(family);
+        }
+    }
+
+    ///CChar::font对应的字体名称
+    pub(crate) fn family_by_index(&self, index: usize) -> &str {
+        match index {
+            0 => self.family.as_ref().unwrap(),
+            _ => &self.fallbacks[index - 1],
+        }
+    }
+
+    pub(crate) fn has_decoration(&self) -> bool {
+        !self.decoration.is_empty() || self.decorations.iter().any(|x| !x.decoration.is_empty())
+    }
+
+    ///所有装饰及其字符范围，整个文本的装饰在前
+    pub(crate) fn decoration_spans(&self) -> impl Iterator<Item = (Range<usize>, &TextDecoration)> {
+        let whole = (!self.decoration.is_empty()).then_some((0..usize::MAX, &self.decoration));
+        whole.into_iter().chain(self.decorations.iter().map(|x| (x.range.clone(), &x.decoration)))
+    }
+
     pub(crate) fn font_size(&self) -> f32 {
         self.size.unwrap()
     }
@@ -6,11 +6,14 @@ use crate::layout::vertical::VerticalLayout;
 use crate::layout::{Layout, LayoutItem, LayoutKind};
 use crate::map::Map;
 use crate::render::image::ImageSource;
+use crate::render::RenderParam;
+use crate::shape::Shape;
 use crate::size::padding::Padding;
 use crate::size::rect::Rect;
 use crate::text::rich::RichText;
 use crate::widgets::checkbox::CheckBox;
 use crate::widgets::space::Space;
+use crate::widgets::toast::{Toast, ToastKind, Toasts};
 use crate::widgets::{Widget, WidgetChange, WidgetKind};
 use crate::window::inner::InnerWindow;
 #[cfg(all(target_os = "linux", not(feature = "gpu")))]
@@ -18,6 +21,7 @@ use crate::window::x11::ffi::Cairo;
 use crate::window::{UserEvent, WindowId, WindowType};
 use crate::*;
 use std::fmt::{Debug, Display, Formatter};
+use std::mem;
 use std::ops::{AddAssign, Range, SubAssign};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -41,6 +45,8 @@ pub struct AppContext {
     pub(crate) previous_time: u128,
     pub(crate) redraw_thread: JoinHandle<()>,
     pub(crate) attr: WindowAttribute,
+    ///模态窗口下的遮罩
+    scrim: RenderParam,
 }
 
 impl AppContext {
@@ -58,6 +64,7 @@ impl AppContext {
             previous_time: 0,
             redraw_thread: spawn(|| {}),
             attr,
+            scrim: RenderParam::new(Shape::rectangle()).with_style(VisualStyle::same((Color::rgba(0, 0, 0, 80), 0.0, 0).into())),
         }
     }
 
@@ -111,15 +118,24 @@ impl AppContext {
     }
 
     pub fn update(&mut self, ut: UpdateType, app: &mut Box<dyn App>) {
+        let mouse_move = matches!(ut, UpdateType::MouseMove);
+        let hide_tooltip = matches!(ut, UpdateType::MousePress | UpdateType::MouseWheel);
         let size = self.context.window.size();
         let draw_rect = Rect::new().with_size(size.width, size.height);
+        //模态窗口打开时仅由最上层的模态窗口处理输入
+        let inner_windows = self.inner_windows.as_ref().unwrap();
+        let modal_window = inner_windows.iter().filter(|x| x.modal).last().map(|x| x.id);
+        let modal_child = self.context.modal_children.load(Ordering::SeqCst) != 0;
+        let blocked = (modal_child || modal_window.is_some()) && matches!(ut,
+            UpdateType::MouseMove | UpdateType::MousePress | UpdateType::MouseRelease | UpdateType::MouseWheel |
+            UpdateType::KeyPress(_) | UpdateType::KeyRelease(_) | UpdateType::IME(_));
         let mut ui = Ui {
             device: &self.device,
             context: &mut self.context,
             app: None,
             layout: self.layout.take(),
             popups: None,
-            update_type: ut,
+            update_type: if blocked { UpdateType::None } else { ut.clone() },
             can_offset: false,
             inner_windows: None,
             request_update: None,
@@ -130,10 +146,22 @@ impl AppContext {
             disabled: false,
         };
         app.update(&mut ui);
+        ui.update_type = ut;
         ui.app = Some(app);
-        let mut event_win = None;
+        let mut tooltips = mem::take(&mut ui.context.tooltips);
+        if mouse_move { tooltips.begin_move(); }
+        if hide_tooltip { tooltips.hide(&mut ui); }
+        ui.context.tooltips = tooltips;
+        let toast_handled = match ui.update_type {
+            UpdateType::MousePress => Toasts::with(&mut ui, |toasts, ui| toasts.pressed(ui)),
+            UpdateType::MouseRelease => Toasts::with(&mut ui, |toasts, ui| toasts.clicked(ui)),
+            _ => false,
+        };
+        if toast_handled { ui.update_type = UpdateType::None; }
         let inner_windows = self.inner_windows.as_ref().unwrap();
+        let mut event_win = if modal_child { None } else { modal_window };
         for i in 0..inner_windows.len() {
+            if modal_child || modal_window.is_some() { break; }
             let win = &inner_windows[inner_windows.len() - i - 1];
             if self.device.device_input.hovered_at(win.visual.rect()) || win.press_title {
                 event_win = Some(win.id);
@@ -145,11 +173,19 @@ impl AppContext {
             let inner_win = &mut self.inner_windows.as_mut().unwrap()[&wid];
             inner_win.update(&mut ui);
             if inner_win.top {
-                let win = self.inner_windows.as_mut().unwrap().remove(&wid).unwrap();
+                let mut win = self.inner_windows.as_mut().unwrap().remove(&wid).unwrap();
                 if win.request_close.load(Ordering::SeqCst) {
                     if let Some(win) = self.inner_windows.as_mut().unwrap().last_mut() {
                         win.top = true;
                     }
+                    ui.context.window.request_redraw();
+                    if let Some(mut callback) = win.on_close.take() {
+                        ui.inner_windows = self.inner_windows.take();
+                        let app = ui.app.take().unwrap();
+                        callback(app, win, &mut ui);
+                        ui.app.replace(app);
+                        self.inner_windows = ui.inner_windows.take();
+                    }
                 } else {
                     self.inner_windows.as_mut().unwrap().iter_mut().for_each(|x| x.top = false);
                     self.inner_windows.as_mut().unwrap().insert(win.id, win);
@@ -157,6 +193,7 @@ impl AppContext {
             }
         };
 
+        if blocked { ui.update_type = UpdateType::None; }
         ui.inner_windows = self.inner_windows.take();
         for popup in self.popups.as_mut().unwrap().iter_mut() {
             popup.update(&mut ui);
@@ -166,6 +203,11 @@ impl AppContext {
         self.layout.as_mut().unwrap().update(&mut ui);
         self.popups = ui.popups.take();
         self.inner_windows = ui.inner_windows.take();
+        if mouse_move {
+            let mut tooltips = mem::take(&mut ui.context.tooltips);
+            tooltips.end_move(&mut ui);
+            ui.context.tooltips = tooltips;
+        }
     }
 
     pub fn redraw(&mut self, app: &mut Box<dyn App>, paint: Option<PaintParam>) { //ps: Option<PAINTSTRUCT>, hdc: Option<HDC>
@@ -262,10 +304,25 @@ impl AppContext {
             ui.context.user_update = u;
             ui.context.window.request_update_event(UserEvent::ReqUpdate);
         }
-        self.inner_windows.as_mut().unwrap().sort_by_key(|x| x.value().top);
+        //模态窗口在最上层，遮罩绘制在模态窗口之下
+        self.inner_windows.as_mut().unwrap().sort_by_key(|x| (x.value().modal, x.value().top));
+        let mut scrim_drawn = false;
+        self.scrim.rect_mut().set_size(size.width, size.height);
         for inner_window in self.inner_windows.as_mut().unwrap().iter_mut() {
+            if inner_window.modal && !scrim_drawn {
+                scrim_drawn = true;
+                self.scrim.draw(&mut ui, false, false, false);
+            }
             inner_window.redraw(&mut ui);
         }
+        //模态子窗口打开时遮罩整个窗口
+        if ui.context.modal_children.load(Ordering::SeqCst) != 0 && !scrim_drawn {
+            self.scrim.draw(&mut ui, false, false, false);
+        }
+        Toasts::with(&mut ui, |toasts, ui| toasts.redraw(ui));
+        let mut tooltips = mem::take(&mut ui.context.tooltips);
+        tooltips.redraw(&mut ui);
+        ui.context.tooltips = tooltips;
         drop(ui);
         #[cfg(feature = "gpu")]
         self.device.queue.submit([encoder.finish()]);
@@ -373,6 +430,32 @@ impl<'a, 'p> Ui<'a, 'p> {
         layout.get_widget(&id.to_string())
     }
 
+    ///设置控件的悬停提示，id为控件的ID，示例见WidgetExt
+    pub fn set_tooltip(&mut self, id: impl ToString, text: impl Into<RichText>) {
+        self.context.tooltips.set(id.to_string(), text.into());
+    }
+
+    ///移除控件的悬停提示
+    pub fn remove_tooltip(&mut self, id: impl ToString) {
+        self.context.tooltips.remove(&id.to_string());
+    }
+
+    ///鼠标悬停多久后显示提示，单位为毫秒，默认500
+    pub fn set_tooltip_delay(&mut self, delay: u64) {
+        self.context.tooltips.set_delay(delay);
+    }
+
+    ///在窗口角落显示提示，duration为显示的毫秒数，为0时点击后关闭，示例见Toast
+    pub fn toast(&mut self, text: impl Into<RichText>, kind: ToastKind, duration: u64) -> &mut Toast {
+        self.context.window.request_redraw();
+        self.context.toasts.push(Toast::new(text, kind, duration))
+    }
+
+    ///提示显示的窗口角落，默认为Align::RightBottom
+    pub fn set_toast_align(&mut self, align: Align) {
+        self.context.toasts.set_align(align);
+    }
+
     ///请求更新，只执行update
     pub fn request_update(&mut self, ut: UpdateType) {
         let wid = self.context.window.id();
@@ -426,6 +509,18 @@ impl<'a, 'p> Ui<'a, 'p> {
         self.context.window.request_update_event(UserEvent::CreateChild);
     }
 
+    ///创建一个模态的外部独立窗口，关闭前当前窗口的输入被阻止。
+    ///winit后端不支持子窗口，改为创建模态的内部子窗口
+    pub fn create_modal_window<W: App>(&mut self, w: W) {
+        #[cfg(feature = "winit")]
+        self.create_inner_window(w).set_modal(true);
+        #[cfg(not(feature = "winit"))]
+        {
+            self.context.new_modal = true;
+            self.create_window(w);
+        }
+    }
+
     ///快速创建一个label
     pub fn label(&mut self, text: impl Into<RichText>) {
         let label = Label::new(text);
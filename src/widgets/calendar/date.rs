@@ -0,0 +1,281 @@
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+/// # 日期，按公历计算
+/// ```
+/// use xlui::*;
+///
+/// let date = Date::new(2024, 2, 28).unwrap();
+/// assert_eq!(date.add_days(1), Date::new(2024, 2, 29).unwrap());
+/// assert_eq!(date.add_months(12), Date::new(2025, 2, 28).unwrap());
+/// assert_eq!(date.format("%Y/%m/%d"), "2024/02/28");
+/// assert_eq!(Date::parse("2024-3-1", "%Y-%m-%d"), Date::new(2024, 3, 1));
+/// //周一为0
+/// assert_eq!(date.weekday(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    ///日期无效时返回None
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day == 0 || day > Date::days_in_month(year, month) { return None; }
+        Some(Date { year, month, day })
+    }
+
+    ///当前的本地日期
+    pub fn today() -> Date {
+        let secs = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |x| x.as_secs());
+        Date::from_days(local_secs(secs as i64).div_euclid(86400))
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    ///1970-01-01起的天数
+    pub fn to_days(&self) -> i64 {
+        let year = (if self.month <= 2 { self.year - 1 } else { self.year }) as i64;
+        let era = year.div_euclid(400);
+        let yoe = year - era * 400;
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    pub fn from_days(days: i64) -> Date {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let doe = days - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+
+    ///星期，周一为0，周日为6
+    pub fn weekday(&self) -> u32 {
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.to_days() + days)
+    }
+
+    ///加减月份，日超出该月天数时取该月最后一天
+    pub fn add_months(&self, months: i32) -> Date {
+        let total = self.year * 12 + self.month as i32 - 1 + months;
+        let year = total.div_euclid(12);
+        let month = total.rem_euclid(12) as u32 + 1;
+        Date { year, month, day: self.day.min(Date::days_in_month(year, month)) }
+    }
+
+    ///该月的第一天
+    pub fn first_of_month(&self) -> Date {
+        Date { day: 1, ..*self }
+    }
+
+    ///按格式输出，支持%Y、%m、%d
+    pub fn format(&self, format: &str) -> String {
+        format.replace("%Y", &format!("{:04}", self.year))
+            .replace("%m", &format!("{:02}", self.month))
+            .replace("%d", &format!("{:02}", self.day))
+    }
+
+    ///按格式解析，月和日可以省略前导0
+    pub fn parse(text: &str, format: &str) -> Option<Date> {
+        let values = parse_fields(text, format, &[('Y', 4), ('m', 2), ('d', 2)])?;
+        Date::new(values[0]? as i32, values[1]?, values[2]?)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format("%Y-%m-%d"))
+    }
+}
+
+/// # 时间，精确到秒
+/// ```
+/// use xlui::*;
+///
+/// let time = Time::new(9, 5, 0).unwrap();
+/// assert_eq!(time.format("%H:%M"), "09:05");
+/// assert_eq!(Time::parse("21:30:15", "%H:%M:%S"), Time::new(21, 30, 15));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
+
+impl Time {
+    ///时间无效时返回None
+    pub fn new(hour: u32, minute: u32, second: u32) -> Option<Time> {
+        if hour > 23 || minute > 59 || second > 59 { return None; }
+        Some(Time { hour, minute, second })
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u32 {
+        self.second
+    }
+
+    ///按格式输出，支持%H、%M、%S
+    pub fn format(&self, format: &str) -> String {
+        format.replace("%H", &format!("{:02}", self.hour))
+            .replace("%M", &format!("{:02}", self.minute))
+            .replace("%S", &format!("{:02}", self.second))
+    }
+
+    ///按格式解析，格式中没有的字段为0
+    pub fn parse(text: &str, format: &str) -> Option<Time> {
+        let values = parse_fields(text, format, &[('H', 2), ('M', 2), ('S', 2)])?;
+        Time::new(values[0]?, values[1].unwrap_or(0), values[2].unwrap_or(0))
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format("%H:%M:%S"))
+    }
+}
+
+///UTC秒数对应的本地时间秒数，即加上该时刻本地时区的偏移
+#[cfg(unix)]
+pub(crate) fn local_secs(secs: i64) -> i64 {
+    use std::mem::MaybeUninit;
+
+    //32位平台上time_t和c_long可能为i32
+    #[allow(clippy::unnecessary_cast)]
+    let time = secs as libc::time_t;
+    let mut tm = MaybeUninit::<libc::tm>::uninit();
+    let res = unsafe { libc::localtime_r(&time, tm.as_mut_ptr()) };
+    if res.is_null() { return secs; }
+    #[allow(clippy::unnecessary_cast)]
+    let offset = unsafe { tm.assume_init() }.tm_gmtoff as i64;
+    secs + offset
+}
+
+///UTC秒数对应的本地时间秒数，即加上该时刻本地时区的偏移
+#[cfg(windows)]
+pub(crate) fn local_secs(secs: i64) -> i64 {
+    use windows::Win32::Foundation::{FILETIME, SYSTEMTIME};
+    use windows::Win32::System::Time::{FileTimeToSystemTime, SystemTimeToTzSpecificLocalTime};
+
+    //FILETIME为1601-01-01起的100纳秒数
+    let ticks = (secs + 11644473600) as u64 * 10_000_000;
+    let file_time = FILETIME { dwLowDateTime: ticks as u32, dwHighDateTime: (ticks >> 32) as u32 };
+    let mut utc = SYSTEMTIME::default();
+    let mut local = SYSTEMTIME::default();
+    let res = unsafe { FileTimeToSystemTime(&file_time, &mut utc).and_then(|_| SystemTimeToTzSpecificLocalTime(None, &utc, &mut local)) };
+    if res.is_err() { return secs; }
+    let Some(date) = Date::new(local.wYear as i32, local.wMonth as u32, local.wDay as u32) else { return secs; };
+    date.to_days() * 86400 + local.wHour as i64 * 3600 + local.wMinute as i64 * 60 + local.wSecond as i64
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn local_secs(secs: i64) -> i64 {
+    secs
+}
+
+///按格式读取数字字段，fields为(字段,最大位数)，其它字符需与text一致
+fn parse_fields(text: &str, format: &str, fields: &[(char, usize)]) -> Option<Vec<Option<u32>>> {
+    let mut values = vec![None; fields.len()];
+    let mut text = text.trim().chars().peekable();
+    let mut format = format.chars();
+    while let Some(c) = format.next() {
+        if c != '%' {
+            if text.next()? != c { return None; }
+            continue;
+        }
+        let field = format.next()?;
+        let index = fields.iter().position(|x| x.0 == field)?;
+        let mut digits = String::new();
+        while digits.len() < fields[index].1 && let Some(d) = text.next_if(|x| x.is_ascii_digit()) {
+            digits.push(d);
+        }
+        values[index] = Some(digits.parse().ok()?);
+    }
+    if text.next().is_some() { return None; }
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, Time};
+
+    #[test]
+    fn from_days_round_trips() {
+        assert_eq!(Date::from_days(0), Date::new(1970, 1, 1).unwrap());
+        assert_eq!(Date::from_days(-1), Date::new(1969, 12, 31).unwrap());
+        assert_eq!(Date::from_days(11016), Date::new(2000, 2, 29).unwrap());
+        assert_eq!(Date::from_days(-719468), Date::new(0, 3, 1).unwrap());
+        //逐日遍历闰年及世纪年前后
+        let mut expected = Date::new(1899, 12, 1).unwrap();
+        for days in expected.to_days()..Date::new(2101, 3, 1).unwrap().to_days() {
+            let date = Date::from_days(days);
+            assert_eq!(date, expected);
+            assert_eq!(date.to_days(), days);
+            expected = if expected.day < Date::days_in_month(expected.year, expected.month) {
+                Date { day: expected.day + 1, ..expected }
+            } else {
+                expected.add_months(1).first_of_month()
+            };
+        }
+    }
+
+    #[test]
+    fn parse_with_format() {
+        assert_eq!(Date::parse("2024-02-29", "%Y-%m-%d"), Date::new(2024, 2, 29));
+        assert_eq!(Date::parse(" 2024/3/1 ", "%Y/%m/%d"), Date::new(2024, 3, 1));
+        assert_eq!(Date::parse("01.03.2024", "%d.%m.%Y"), Date::new(2024, 3, 1));
+        assert_eq!(Date::parse("2023-02-29", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse("2024-13-01", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse("2024-01-01x", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse("2024-01", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse("2024/01/01", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse("2024-001-01", "%Y-%m-%d"), None);
+        assert_eq!(Date::parse("2024-01-01", "%Y-%m"), None);
+        assert_eq!(Date::parse("2024-01-01", "%Y-%m-%q"), None);
+        assert_eq!(Time::parse("7:05", "%H:%M"), Time::new(7, 5, 0));
+        assert_eq!(Time::parse("24:00", "%H:%M"), None);
+    }
+}
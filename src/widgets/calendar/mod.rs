@@ -0,0 +1,424 @@
+pub mod date;
+pub mod picker;
+pub mod time;
+
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
+use crate::response::{Callback, DateCallB, Response};
+use crate::shape::Shape;
+use crate::size::Geometry;
+use crate::text::buffer::TextBuffer;
+use crate::ui::Ui;
+use crate::widgets::calendar::date::Date;
+use crate::widgets::{Widget, WidgetSize, WidgetState};
+use crate::*;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+const HEADER_HEIGHT: f32 = 28.0;
+const WEEKDAY_HEIGHT: f32 = 22.0;
+const WEEKDAYS: [&str; 7] = ["一", "二", "三", "四", "五", "六", "日"];
+
+pub(crate) type DateFilter = Rc<dyn Fn(&Date) -> bool>;
+
+///可选日期的范围及禁用的日期
+#[derive(Clone, Default)]
+pub(crate) struct DateLimits {
+    pub(crate) min: Option<Date>,
+    pub(crate) max: Option<Date>,
+    pub(crate) disabled: Option<DateFilter>,
+}
+
+impl DateLimits {
+    pub(crate) fn contains(&self, date: &Date) -> bool {
+        if self.min.is_some_and(|x| *date < x) || self.max.is_some_and(|x| *date > x) { return false; }
+        self.disabled.as_ref().is_none_or(|x| !x(date))
+    }
+
+    ///month所在的月份是否有可选的范围
+    fn month_visible(&self, month: &Date) -> bool {
+        let last = month.first_of_month().add_months(1).add_days(-1);
+        self.min.is_none_or(|x| last >= x) && self.max.is_none_or(|x| month.first_of_month() <= x)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CalendarHit {
+    Previous,
+    Next,
+    Day(usize),
+}
+
+/// ### Calendar的示例用法
+/// ```
+/// use xlui::*;
+///
+/// fn date_changed<A:App>(_:&mut A,_:&mut Ui,date:Date){
+///     println!("选择的日期:{}",date);
+/// }
+///
+/// fn draw<A:App>(ui:&mut Ui){
+///     let calendar=Calendar::new(Date::new(2025,10,1).unwrap())
+///         //可选的范围
+///         .with_min_date(Date::new(2025,1,1).unwrap())
+///         .with_max_date(Date::new(2026,12,31).unwrap())
+///         //周末不可选
+///         .with_disabled_dates(|date| date.weekday()>=5)
+///         .connect(date_changed::<A>);
+///     ui.add(calendar);
+/// }
+/// ```
+pub struct Calendar {
+    pub(crate) id: String,
+    visual: Visual,
+    geometry: Geometry,
+    state: WidgetState,
+    value: Date,
+    month: Date, //显示月份的第一天
+    today: Date,
+    limits: DateLimits,
+    callback: Option<DateCallB>,
+    contact: Option<Arc<RwLock<Date>>>,
+    title: TextBuffer,
+    previous: TextBuffer,
+    next: TextBuffer,
+    weekdays: Vec<TextBuffer>,
+    days: Vec<TextBuffer>,
+    hover_render: RenderParam,
+    select_render: RenderParam,
+    today_render: RenderParam,
+    hovered: Option<CalendarHit>,
+    changed: bool,
+}
+
+impl Calendar {
+    pub fn new(value: Date) -> Calendar {
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::WHITE,
+            border: Border::same(1.0).color(Color::rgb(210, 210, 210)),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        let cell_style = |fill: Color, border: Border| VisualStyle::same(WidgetStyle {
+            fill,
+            border,
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        let text = |text: &str| TextBuffer::new(RichText::new(text).size(13.0)).with_align(Align::Center);
+        Calendar {
+            id: gen_unique_id(),
+            visual: Visual::new().with_enable().with_style(style),
+            geometry: Geometry::new().with_context_size(230.0, 214.0),
+            state: WidgetState::default(),
+            value,
+            month: value.first_of_month(),
+            today: Date::today(),
+            limits: DateLimits::default(),
+            callback: None,
+            contact: None,
+            title: text(""),
+            previous: text("<"),
+            next: text(">"),
+            weekdays: WEEKDAYS.iter().map(|x| text(x)).collect(),
+            days: (0..42).map(|_| text("")).collect(),
+            hover_render: RenderParam::new(Shape::rectangle()).with_style(cell_style(Color::rgb(230, 236, 245), Border::same(0.0))),
+            select_render: RenderParam::new(Shape::rectangle()).with_style(cell_style(Color::rgb(64, 128, 230), Border::same(0.0))),
+            today_render: RenderParam::new(Shape::rectangle()).with_style(cell_style(Color::TRANSPARENT, Border::same(1.0).color(Color::rgb(64, 128, 230)))),
+            hovered: None,
+            changed: true,
+        }
+    }
+
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.geometry.set_context_size(width, height);
+        self
+    }
+
+    ///最早可选的日期
+    pub fn with_min_date(mut self, date: Date) -> Self {
+        self.limits.min = Some(date);
+        self
+    }
+
+    ///最晚可选的日期
+    pub fn with_max_date(mut self, date: Date) -> Self {
+        self.limits.max = Some(date);
+        self
+    }
+
+    ///f返回true的日期不可选
+    pub fn with_disabled_dates(mut self, f: impl Fn(&Date) -> bool + 'static) -> Self {
+        self.limits.disabled = Some(Rc::new(f));
+        self
+    }
+
+    ///高亮显示的今天，默认为Date::today()
+    pub fn with_today(mut self, today: Date) -> Self {
+        self.today = today;
+        self
+    }
+
+    pub(crate) fn with_limits(mut self, limits: DateLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    ///与其它控件共享选择的日期
+    pub(crate) fn contact(mut self, value: Arc<RwLock<Date>>) -> Self {
+        self.contact = Some(value);
+        self
+    }
+
+    pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, Date)) -> Self {
+        self.callback = Some(Callback::create_date(f));
+        self
+    }
+
+    pub fn set_callback<A: App>(&mut self, f: fn(&mut A, &mut Ui, Date)) {
+        self.callback = Some(Callback::create_date(f));
+    }
+
+    pub fn value(&self) -> Date {
+        self.value
+    }
+
+    ///设置选择的日期并显示所在的月份
+    pub fn set_value(&mut self, value: Date) {
+        self.value = value;
+        self.month = value.first_of_month();
+        self.changed = true;
+    }
+
+    ///当前显示的(年,月)
+    pub fn month(&self) -> (i32, u32) {
+        (self.month.year(), self.month.month())
+    }
+
+    ///显示指定的月份
+    pub fn show_month(&mut self, year: i32, month: u32) {
+        let Some(month) = Date::new(year, month, 1) else { return; };
+        self.month = month;
+        self.changed = true;
+    }
+
+    ///网格中第index格的日期，从显示月份第一天所在周的周一开始
+    fn date_at(&self, index: usize) -> Date {
+        self.month.add_days(index as i64 - self.month.weekday() as i64)
+    }
+
+    fn header_rect(&self) -> Rect {
+        let mut rect = self.geometry.context_rect();
+        rect.set_y_max(rect.dy().min + HEADER_HEIGHT);
+        rect
+    }
+
+    fn previous_rect(&self) -> Rect {
+        let mut rect = self.header_rect();
+        rect.set_x_max(rect.dx().min + HEADER_HEIGHT);
+        rect
+    }
+
+    fn next_rect(&self) -> Rect {
+        let mut rect = self.header_rect();
+        rect.set_x_min(rect.dx().max - HEADER_HEIGHT);
+        rect
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        let width = self.geometry.context_width() / 7.0;
+        let height = (self.geometry.context_height() - HEADER_HEIGHT - WEEKDAY_HEIGHT) / 6.0;
+        (width, height)
+    }
+
+    ///第column列的星期文本或第row行的日期所在的矩形，row为None时为星期行
+    fn cell_rect(&self, row: Option<usize>, column: usize) -> Rect {
+        let (width, height) = self.cell_size();
+        let mut rect = self.geometry.context_rect();
+        let x = rect.dx().min + width * column as f32;
+        let y = match row {
+            None => rect.dy().min + HEADER_HEIGHT,
+            Some(row) => rect.dy().min + HEADER_HEIGHT + WEEKDAY_HEIGHT + height * row as f32,
+        };
+        rect.set_x_min(x);
+        rect.set_x_max(x + width);
+        rect.set_y_min(y);
+        rect.set_y_max(y + if row.is_some() { height } else { WEEKDAY_HEIGHT });
+        rect
+    }
+
+    fn hit(&self, ui: &Ui) -> Option<CalendarHit> {
+        let input = &ui.device.device_input;
+        if input.hovered_at(&self.previous_rect()) { return Some(CalendarHit::Previous); }
+        if input.hovered_at(&self.next_rect()) { return Some(CalendarHit::Next); }
+        (0..42).find(|x| input.hovered_at(&self.cell_rect(Some(x / 7), x % 7))).map(CalendarHit::Day)
+    }
+
+    ///切换显示的月份，超出可选范围时忽略
+    fn shift_month(&mut self, months: i32) {
+        let month = self.month.add_months(months);
+        if !self.limits.month_visible(&month) { return; }
+        self.month = month;
+        self.changed = true;
+    }
+
+    fn select(&mut self, ui: &mut Ui, date: Date) {
+        if !self.limits.contains(&date) { return; }
+        if date.first_of_month() != self.month { self.month = date.first_of_month(); }
+        self.changed = true;
+        if date == self.value { return; }
+        self.value = date;
+        if let Some(ref contact) = self.contact { *contact.write().unwrap() = date; }
+        if let Some(ref mut callback) = self.callback {
+            let app = ui.app.take().unwrap();
+            callback(app, ui, date);
+            ui.app.replace(app);
+        }
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        let (width, height) = self.cell_size();
+        for (buffer, width) in [(&mut self.previous, HEADER_HEIGHT), (&mut self.next, HEADER_HEIGHT), (&mut self.title, self.geometry.context_width())] {
+            buffer.init(ui);
+            buffer.geometry.set_fix_size(width, HEADER_HEIGHT);
+        }
+        for weekday in self.weekdays.iter_mut() {
+            weekday.init(ui);
+            weekday.geometry.set_fix_size(width, WEEKDAY_HEIGHT);
+        }
+        for day in self.days.iter_mut() {
+            day.init(ui);
+            day.geometry.set_fix_size(width, height);
+        }
+        self.visual.rect_mut().set_size(self.geometry.padding_width(), self.geometry.padding_height());
+        self.changed = true;
+    }
+
+    fn update_buffer(&mut self, ui: &mut Ui) {
+        if let Some(ref contact) = self.contact {
+            let value = *contact.read().unwrap();
+            if value != self.value { self.set_value(value); }
+        }
+        if !self.changed { return; }
+        self.changed = false;
+        self.title.update_buffer_text(ui, &format!("{}年{}月", self.month.year(), self.month.month()));
+        for index in 0..self.days.len() {
+            let date = self.date_at(index);
+            let color = if date == self.value {
+                Color::WHITE
+            } else if date.first_of_month() != self.month || !self.limits.contains(&date) {
+                Color::rgb(170, 170, 170)
+            } else {
+                Color::BLACK
+            };
+            self.days[index].text.color = color;
+            self.days[index].update_buffer_text(ui, &date.day().to_string());
+        }
+        let color = |visible: bool| if visible { Color::BLACK } else { Color::rgb(200, 200, 200) };
+        self.previous.text.color = color(self.limits.month_visible(&self.month.add_months(-1)));
+        self.next.text.color = color(self.limits.month_visible(&self.month.add_months(1)));
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+        self.geometry.offset_to_rect(&ui.draw_rect);
+        self.update_buffer(ui);
+        self.visual.draw(ui, self.state.disabled, false, false, false);
+        let (previous, next) = (self.previous_rect(), self.next_rect());
+        match self.hovered {
+            Some(CalendarHit::Previous) => self.draw_cell(ui, &previous, 0),
+            Some(CalendarHit::Next) => self.draw_cell(ui, &next, 0),
+            Some(CalendarHit::Day(index)) if self.limits.contains(&self.date_at(index)) => {
+                self.draw_cell(ui, &self.cell_rect(Some(index / 7), index % 7), 0);
+            }
+            _ => {}
+        }
+        for (index, date) in [(1, self.value), (2, self.today)] {
+            let Some(cell) = self.index_of(&date) else { continue; };
+            self.draw_cell(ui, &self.cell_rect(Some(cell / 7), cell % 7), index);
+        }
+        self.previous.geometry.offset_to_rect(&previous);
+        self.previous.redraw(ui);
+        self.next.geometry.offset_to_rect(&next);
+        self.next.redraw(ui);
+        self.title.geometry.offset_to_rect(&self.header_rect());
+        self.title.redraw(ui);
+        for column in 0..self.weekdays.len() {
+            let rect = self.cell_rect(None, column);
+            self.weekdays[column].geometry.offset_to_rect(&rect);
+            self.weekdays[column].redraw(ui);
+        }
+        for index in 0..self.days.len() {
+            let rect = self.cell_rect(Some(index / 7), index % 7);
+            self.days[index].geometry.offset_to_rect(&rect);
+            self.days[index].redraw(ui);
+        }
+    }
+
+    ///date在网格中的位置，不在显示范围内时返回None
+    fn index_of(&self, date: &Date) -> Option<usize> {
+        let index = date.to_days() - self.date_at(0).to_days();
+        (0..42).contains(&index).then_some(index as usize)
+    }
+
+    ///绘制格子的背景，kind为0:悬停、1:选择、2:今天
+    fn draw_cell(&mut self, ui: &mut Ui, rect: &Rect, kind: usize) {
+        let render = match kind {
+            0 => &mut self.hover_render,
+            1 => &mut self.select_render,
+            _ => &mut self.today_render,
+        };
+        let mut rect = rect.clone();
+        rect.add_min_x(1.0);
+        rect.add_min_y(1.0);
+        render.rect_mut().set_size(rect.width() - 1.0, rect.height() - 1.0);
+        render.offset_to_rect(&rect);
+        render.draw(ui, false, false, false);
+    }
+}
+
+impl Widget for Calendar {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::Init | UpdateType::ReInit => self.init(ui),
+            UpdateType::MouseMove => {
+                let hit = self.hit(ui);
+                if hit != self.hovered {
+                    self.hovered = hit;
+                    ui.context.window.request_redraw();
+                }
+            }
+            UpdateType::MouseWheel if ui.device.device_input.hovered_at(self.visual.rect()) => {
+                self.shift_month(if ui.device.device_input.mouse.delta_y() > 0.0 { -1 } else { 1 });
+                ui.context.window.request_redraw();
+            }
+            UpdateType::MouseRelease if !self.state.disabled && ui.device.device_input.click_at(self.visual.rect()) => {
+                match self.hit(ui) {
+                    Some(CalendarHit::Previous) => self.shift_month(-1),
+                    Some(CalendarHit::Next) => self.shift_month(1),
+                    Some(CalendarHit::Day(index)) => self.select(ui, self.date_at(index)),
+                    None => {}
+                }
+                ui.update_type = UpdateType::None;
+                ui.context.window.request_redraw();
+            }
+            _ => {}
+        }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -0,0 +1,246 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::key::Key;
+use crate::layout::popup::Popup;
+use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
+use crate::response::{Callback, DateCallB, Response};
+use crate::shape::Shape;
+use crate::size::Geometry;
+use crate::ui::Ui;
+use crate::widgets::calendar::date::Date;
+use crate::widgets::calendar::{Calendar, DateLimits};
+use crate::widgets::textedit::TextEdit;
+use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
+use crate::*;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+/// ### DatePicker的示例用法
+/// ```
+/// use xlui::*;
+///
+/// fn date_changed<A:App>(_:&mut A,_:&mut Ui,date:Date){
+///     println!("选择的日期:{}",date);
+/// }
+///
+/// fn draw<A:App>(ui:&mut Ui){
+///     let picker=DatePicker::new(Date::new(2025,10,1).unwrap())
+///         //输入框中日期的格式
+///         .with_format("%Y/%m/%d")
+///         .with_min_date(Date::new(2025,1,1).unwrap())
+///         .connect(date_changed::<A>);
+///     ui.add(picker);
+/// }
+/// ```
+pub struct DatePicker {
+    pub(crate) id: String,
+    edit: TextEdit,
+    rect: Rect,
+    geometry: Geometry,
+    state: WidgetState,
+    value: Date,
+    format: String,
+    limits: DateLimits,
+    selected: Arc<RwLock<Date>>, //与弹窗中的Calendar共享
+    popup_id: String,
+    popup_rect: Rect,
+    button: Visual,
+    arrow_render: RenderParam,
+    callback: Option<DateCallB>,
+}
+
+impl DatePicker {
+    pub fn new(value: Date) -> DatePicker {
+        let mut button_style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(230, 230, 230),
+            border: Border::same(0.0),
+            radius: Radius::same(2),
+            shadow: Shadow::new(),
+        });
+        button_style.hovered.fill = Color::rgb(210, 210, 210);
+        button_style.pressed.fill = Color::rgb(200, 200, 200);
+        let mut arrow_render = RenderParam::new(Shape::triangle()).with_style(VisualStyle::same((Color::rgb(95, 95, 95), 0.0, 0).into()));
+        arrow_render.set_poses((0.0, 0.0).into(), (8.0, 0.0).into(), (4.0, 6.0).into());
+        let format = "%Y-%m-%d".to_string();
+        DatePicker {
+            id: gen_unique_id(),
+            edit: TextEdit::single_edit(value.format(&format)),
+            rect: Rect::new().with_size(140.0, 25.0),
+            geometry: Geometry::new().with_context_size(140.0, 25.0),
+            state: WidgetState::default(),
+            value,
+            format,
+            limits: DateLimits::default(),
+            selected: Arc::new(RwLock::new(value)),
+            popup_id: "".to_string(),
+            popup_rect: Rect::new().with_size(240.0, 224.0),
+            button: Visual::new().with_enable().with_style(button_style).with_size(20.0, 25.0),
+            arrow_render,
+            callback: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.rect.set_size(width, height);
+        self.geometry.set_context_size(width, height);
+        self.button.rect_mut().set_size(20.0, height);
+        self
+    }
+
+    ///日期的格式，支持%Y、%m、%d，默认为%Y-%m-%d
+    pub fn with_format(mut self, format: impl ToString) -> Self {
+        self.format = format.to_string();
+        self.edit = TextEdit::single_edit(self.value.format(&self.format));
+        self
+    }
+
+    pub fn with_min_date(mut self, date: Date) -> Self {
+        self.limits.min = Some(date);
+        self
+    }
+
+    pub fn with_max_date(mut self, date: Date) -> Self {
+        self.limits.max = Some(date);
+        self
+    }
+
+    ///f返回true的日期不可选，输入这些日期时恢复为原日期
+    pub fn with_disabled_dates(mut self, f: impl Fn(&Date) -> bool + 'static) -> Self {
+        self.limits.disabled = Some(Rc::new(f));
+        self
+    }
+
+    pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, Date)) -> Self {
+        self.callback = Some(Callback::create_date(f));
+        self
+    }
+
+    pub fn set_callback<A: App>(&mut self, f: fn(&mut A, &mut Ui, Date)) {
+        self.callback = Some(Callback::create_date(f));
+    }
+
+    pub fn value(&self) -> Date {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: Date) {
+        self.value = value;
+        *self.selected.write().unwrap() = value;
+        self.state.changed = true;
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        self.edit.geometry().set_fix_width(self.geometry.context_width() - 22.0);
+        self.edit.update(ui);
+        let popup = Popup::new(self.popup_rect.width(), self.popup_rect.height());
+        self.popup_id = popup.id.clone();
+        let mut calendar = Some(Calendar::new(self.value).with_size(self.popup_rect.width() - 12.0, self.popup_rect.height() - 12.0)
+            .with_limits(self.limits.clone()).contact(self.selected.clone()));
+        popup.show(ui, |ui| if let Some(calendar) = calendar.take() { ui.add(calendar); });
+    }
+
+    fn call(&mut self, ui: &mut Ui) {
+        if let Some(ref mut callback) = self.callback {
+            let app = ui.app.take().unwrap();
+            callback(app, ui, self.value);
+            ui.app.replace(app);
+        }
+    }
+
+    ///解析输入的文本，无效或不可选时恢复为原日期
+    fn update_from_edit(&mut self, ui: &mut Ui) {
+        let date = Date::parse(&self.edit.text(), &self.format).filter(|x| self.limits.contains(x));
+        if let Some(date) = date && date != self.value {
+            self.set_value(date);
+            self.call(ui);
+        }
+        self.state.changed = true;
+        ui.context.window.request_redraw();
+    }
+
+    fn update_buffer(&mut self, ui: &mut Ui) {
+        let selected = *self.selected.read().unwrap();
+        if selected != self.value {
+            //在弹窗中选择了日期
+            self.value = selected;
+            self.state.changed = true;
+            self.call(ui);
+            ui.popups.as_mut().unwrap()[&self.popup_id].request_state(false);
+        }
+        if self.state.changed { ui.widget_changed |= WidgetChange::Value; }
+        self.state.changed = false;
+        if ui.widget_changed.contains(WidgetChange::Position) {
+            self.rect.offset_to_rect(&ui.draw_rect);
+            self.popup_rect.offset_to_rect(&ui.draw_rect);
+            self.popup_rect.offset_y(&Offset::new().covered().with_y(self.rect.height() + 3.0));
+            ui.popups.as_mut().unwrap()[&self.popup_id].set_rect(self.popup_rect.clone());
+            let mut rect = self.rect.clone();
+            rect.set_x_min(rect.dx().max - self.button.rect().width());
+            self.button.rect_mut().offset_to_rect(&rect);
+            rect.add_min_x(6.0);
+            rect.add_min_y((rect.height() - 6.0) / 2.0);
+            self.arrow_render.offset_to_rect(&rect);
+        }
+        if ui.widget_changed.contains(WidgetChange::Value) {
+            self.edit.update_text(ui, self.value.format(&self.format));
+        }
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        self.update_buffer(ui);
+        if ui.widget_changed.contains(WidgetChange::Position) {
+            let mut edit_rect = self.rect.clone();
+            edit_rect.set_x_max(edit_rect.dx().max - 22.0);
+            ui.draw_rect = edit_rect;
+        }
+        self.edit.redraw(ui);
+        self.button.draw(ui, self.state.disabled, self.state.hovered, self.state.pressed, false);
+        self.arrow_render.draw(ui, self.state.disabled, false, false);
+    }
+}
+
+impl Widget for DatePicker {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::Init => self.init(ui),
+            UpdateType::ReInit => { self.edit.update(ui); }
+            UpdateType::MouseMove => {
+                let hovered = ui.device.device_input.hovered_at(self.button.rect());
+                if self.state.on_hovered(hovered) { ui.context.window.request_redraw(); }
+                self.edit.update(ui);
+            }
+            UpdateType::MousePress => {
+                let pressed = ui.device.device_input.pressed_at(self.button.rect());
+                if self.state.on_pressed(pressed) { ui.context.window.request_redraw(); }
+                let focused = self.edit.state().focused;
+                self.edit.update(ui);
+                if focused && !self.edit.state().focused { self.update_from_edit(ui); }
+            }
+            UpdateType::MouseRelease if self.state.on_clicked(ui.device.device_input.click_at(self.button.rect())) => {
+                ui.popups.as_mut().unwrap()[&self.popup_id].toggle();
+                ui.update_type = UpdateType::None;
+                ui.context.window.request_redraw();
+            }
+            UpdateType::KeyRelease(Key::Enter) if self.edit.state().focused => {
+                self.edit.state().focused = false;
+                self.update_from_edit(ui);
+            }
+            _ => { self.edit.update(ui); }
+        }
+        Response::new(&self.id, WidgetSize::same(self.rect.width(), self.rect.height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -0,0 +1,167 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::response::{Callback, Response, TimeCallB};
+use crate::size::Geometry;
+use crate::ui::Ui;
+use crate::widgets::calendar::date::Time;
+use crate::widgets::spinbox::SpinBox;
+use crate::widgets::{Widget, WidgetSize, WidgetState};
+use crate::*;
+
+const FIELD_WIDTH: f32 = 50.0;
+const SEPARATOR_WIDTH: f32 = 8.0;
+
+/// ### TimeEdit的示例用法
+/// ```
+/// use xlui::*;
+///
+/// fn time_changed<A:App>(_:&mut A,_:&mut Ui,time:Time){
+///     println!("时间改变了:{}",time);
+/// }
+///
+/// fn draw<A:App>(ui:&mut Ui){
+///     let edit=TimeEdit::new(Time::new(9,30,0).unwrap())
+///         //只编辑时和分
+///         .with_seconds(false)
+///         .connect(time_changed::<A>);
+///     ui.add(edit);
+/// }
+/// ```
+pub struct TimeEdit {
+    pub(crate) id: String,
+    layout: Option<LayoutKind>,
+    geometry: Geometry,
+    state: WidgetState,
+    value: Time,
+    seconds: bool,
+    field_ids: Vec<String>, //时、分、秒输入框的ID
+    callback: Option<TimeCallB>,
+}
+
+impl TimeEdit {
+    pub fn new(value: Time) -> TimeEdit {
+        TimeEdit {
+            id: gen_unique_id(),
+            layout: None,
+            geometry: Geometry::new(),
+            state: WidgetState::default(),
+            value,
+            seconds: true,
+            field_ids: (0..3).map(|_| gen_unique_id()).collect(),
+            callback: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    ///是否显示秒，默认显示
+    pub fn with_seconds(mut self, seconds: bool) -> Self {
+        self.seconds = seconds;
+        self
+    }
+
+    pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, Time)) -> Self {
+        self.callback = Some(Callback::create_time(f));
+        self
+    }
+
+    pub fn set_callback<A: App>(&mut self, f: fn(&mut A, &mut Ui, Time)) {
+        self.callback = Some(Callback::create_time(f));
+    }
+
+    pub fn value(&self) -> Time {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: Time) {
+        self.value = value;
+        self.state.changed = true;
+    }
+
+    fn field_count(&self) -> usize {
+        if self.seconds { 3 } else { 2 }
+    }
+
+    fn values(time: &Time) -> [u32; 3] {
+        [time.hour(), time.minute(), time.second()]
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        let count = self.field_count();
+        let width = FIELD_WIDTH * count as f32 + SEPARATOR_WIDTH * (count - 1) as f32;
+        self.geometry.set_context_size(width, 25.0);
+        let layout = HorizontalLayout::left_to_right().with_size(width, 25.0).with_space(0.0).with_padding(Padding::same(0.0));
+        let previous_layout = ui.layout.replace(LayoutKind::new(layout)).unwrap();
+        let values = TimeEdit::values(&self.value);
+        for (index, value) in values.into_iter().take(count).enumerate() {
+            if index != 0 { ui.add(Label::new(":").width(SEPARATOR_WIDTH).height(25.0).align(Align::Center)); }
+            let max = if index == 0 { 23 } else { 59 };
+            ui.add(SpinBox::new(value as i32, 1, 0..max).with_format(|v| format!("{:02}", v))
+                .with_size(FIELD_WIDTH, 25.0).id(&self.field_ids[index]));
+        }
+        self.layout = ui.layout.replace(previous_layout);
+    }
+
+    fn field(&mut self, index: usize) -> Option<&mut SpinBox<i32>> {
+        self.layout.as_mut()?.get_widget(&self.field_ids[index])
+    }
+
+    ///读取输入框的值，改变时调用回调
+    fn update_from_fields(&mut self, ui: &mut Ui) {
+        let mut values = TimeEdit::values(&self.value);
+        let count = self.field_count();
+        for (index, value) in values.iter_mut().take(count).enumerate() {
+            let max = if index == 0 { 23 } else { 59 };
+            let Some(field) = self.field(index) else { return; };
+            let clamped = field.value().clamp(0, max);
+            if clamped != field.value() { field.set_value(clamped); }
+            *value = clamped as u32;
+        }
+        let Some(time) = Time::new(values[0], values[1], values[2]) else { return; };
+        if time == self.value { return; }
+        self.value = time;
+        if let Some(ref mut callback) = self.callback && ui.app.is_some() {
+            let app = ui.app.take().unwrap();
+            callback(app, ui, time);
+            ui.app.replace(app);
+        }
+    }
+
+    ///set_value后同步到输入框
+    fn update_fields(&mut self) {
+        if !self.state.changed { return; }
+        self.state.changed = false;
+        let values = TimeEdit::values(&self.value);
+        for (index, value) in values.into_iter().take(self.field_count()).enumerate() {
+            if let Some(field) = self.field(index) { field.set_value(value as i32); }
+        }
+    }
+}
+
+impl Widget for TimeEdit {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Init => self.init(ui),
+            UpdateType::Draw => {
+                self.update_fields();
+                if let Some(layout) = self.layout.as_mut() { layout.update(ui); }
+            }
+            _ => {
+                if let Some(layout) = self.layout.as_mut() { layout.update(ui); }
+                self.update_from_fields(ui);
+            }
+        }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -151,6 +151,10 @@ impl CheckBox {
         self.visual.draw(ui, self.state.disabled, self.state.hovered, self.state.pressed, true);
     }
 
+    pub(crate) fn checked(&self) -> bool {
+        self.value
+    }
+
     pub fn style_mut(&mut self) -> &mut VisualStyle {
         self.visual.enable().style_mut()
     }
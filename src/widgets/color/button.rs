@@ -0,0 +1,167 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::layout::popup::Popup;
+use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
+use crate::response::{Callback, ColorCallB, Response};
+use crate::shape::Shape;
+use crate::size::Geometry;
+use crate::ui::Ui;
+use crate::widgets::color::ColorPicker;
+use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
+use crate::*;
+use std::sync::{Arc, RwLock};
+
+///显示当前颜色的按钮，点击后在弹窗中选择颜色，示例见ColorPicker
+pub struct ColorButton {
+    pub(crate) id: String,
+    visual: Visual,
+    geometry: Geometry,
+    state: WidgetState,
+    color: Color,
+    selected: Arc<RwLock<Color>>, //与弹窗中的ColorPicker共享
+    recent: Vec<Color>,
+    swatch_render: RenderParam,
+    popup_id: String,
+    popup_rect: Rect,
+    callback: Option<ColorCallB>,
+}
+
+impl ColorButton {
+    pub fn new(color: Color) -> ColorButton {
+        let mut style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(230, 230, 230),
+            border: Border::same(1.0).color(Color::rgb(190, 190, 190)),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        style.hovered.border = Border::same(1.0).color(Color::rgb(144, 209, 255));
+        style.pressed.fill = Color::rgb(210, 210, 210);
+        ColorButton {
+            id: gen_unique_id(),
+            visual: Visual::new().with_enable().with_style(style),
+            geometry: Geometry::new().with_context_size(40.0, 22.0),
+            state: WidgetState::default(),
+            color: color.clone(),
+            selected: Arc::new(RwLock::new(color.clone())),
+            recent: vec![],
+            swatch_render: RenderParam::new(Shape::rectangle()).with_style(VisualStyle::same((color, 0.0, 2).into())),
+            popup_id: "".to_string(),
+            popup_rect: Rect::new().with_size(208.0, 304.0),
+            callback: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.geometry.set_context_size(width, height);
+        self
+    }
+
+    ///弹窗中最近使用的颜色
+    pub fn with_recent_colors(mut self, colors: Vec<Color>) -> Self {
+        self.recent = colors;
+        self
+    }
+
+    pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, Color)) -> Self {
+        self.callback = Some(Callback::create_color(f));
+        self
+    }
+
+    pub fn set_callback<A: App>(&mut self, f: fn(&mut A, &mut Ui, Color)) {
+        self.callback = Some(Callback::create_color(f));
+    }
+
+    pub fn color(&self) -> Color {
+        self.color.clone()
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        *self.selected.write().unwrap() = color.clone();
+        self.color = color;
+        self.state.changed = true;
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        self.visual.rect_mut().set_size(self.geometry.padding_width(), self.geometry.padding_height());
+        self.swatch_render.rect_mut().set_size(self.geometry.context_width() - 8.0, self.geometry.context_height() - 8.0);
+        let popup = Popup::new(self.popup_rect.width(), self.popup_rect.height());
+        self.popup_id = popup.id.clone();
+        let mut picker = Some(ColorPicker::new(self.color.clone()).with_recent_colors(self.recent.clone()).contact(self.selected.clone()));
+        popup.show(ui, |ui| if let Some(picker) = picker.take() { ui.add(picker); });
+    }
+
+    fn update_buffer(&mut self, ui: &mut Ui) {
+        let selected = self.selected.read().unwrap().clone();
+        if selected != self.color {
+            //在弹窗中选择了颜色
+            self.color = selected;
+            self.state.changed = true;
+            if let Some(ref mut callback) = self.callback {
+                let app = ui.app.take().unwrap();
+                callback(app, ui, self.color.clone());
+                ui.app.replace(app);
+            }
+        }
+        if self.state.changed {
+            self.swatch_render.set_style(VisualStyle::same((self.color.clone(), 0.0, 2).into()));
+            ui.widget_changed |= WidgetChange::Value;
+        }
+        self.state.changed = false;
+        if ui.widget_changed.contains(WidgetChange::Position) {
+            self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+            let mut rect = self.visual.rect().clone();
+            rect.add_min_x(4.0);
+            rect.add_min_y(4.0);
+            self.swatch_render.offset_to_rect(&rect);
+            self.popup_rect.offset_to_rect(&ui.draw_rect);
+            self.popup_rect.offset_y(&Offset::new().covered().with_y(self.visual.rect().height() + 3.0));
+            ui.popups.as_mut().unwrap()[&self.popup_id].set_rect(self.popup_rect.clone());
+        }
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        self.update_buffer(ui);
+        self.visual.draw(ui, self.state.disabled, self.state.hovered, self.state.pressed, false);
+        self.swatch_render.draw(ui, self.state.disabled, false, false);
+    }
+}
+
+impl Widget for ColorButton {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::Init => self.init(ui),
+            UpdateType::MouseMove => {
+                let hovered = ui.device.device_input.hovered_at(self.visual.rect());
+                if self.state.on_hovered(hovered) { ui.context.window.request_redraw(); }
+            }
+            UpdateType::MousePress => {
+                let pressed = ui.device.device_input.pressed_at(self.visual.rect());
+                if self.state.on_pressed(pressed) { ui.context.window.request_redraw(); }
+            }
+            UpdateType::MouseRelease => {
+                let clicked = ui.device.device_input.click_at(self.visual.rect());
+                if self.state.on_clicked(clicked) {
+                    ui.popups.as_mut().unwrap()[&self.popup_id].toggle();
+                    ui.update_type = UpdateType::None;
+                    ui.context.window.request_redraw();
+                }
+            }
+            _ => {}
+        }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
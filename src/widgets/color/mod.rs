@@ -0,0 +1,503 @@
+pub mod button;
+
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::key::Key;
+use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
+use crate::response::{Callback, ColorCallB, Response};
+use crate::shape::Shape;
+use crate::size::Geometry;
+use crate::text::buffer::TextBuffer;
+use crate::ui::Ui;
+use crate::widgets::spinbox::SpinBox;
+use crate::widgets::textedit::TextEdit;
+use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
+use crate::*;
+use std::sync::{Arc, RwLock};
+
+const SQUARE: f32 = 160.0;
+const STRIP: f32 = 16.0;
+const SPACE: f32 = 8.0;
+const BAR: f32 = 12.0;
+const FIELD_HEIGHT: f32 = 25.0;
+const LABEL_HEIGHT: f32 = 14.0;
+const SWATCH: f32 = 16.0;
+const WIDTH: f32 = SQUARE + SPACE + STRIP;
+const HEIGHT: f32 = SQUARE + SPACE + BAR + SPACE + FIELD_HEIGHT + 4.0 + LABEL_HEIGHT + FIELD_HEIGHT + SPACE + SWATCH;
+const STEPS: usize = 24; //方块及透明度条的渐变分段
+const HUE_STEPS: usize = 36;
+const MAX_RECENT: usize = 10;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ColorDrag {
+    Square,
+    Hue,
+    Alpha,
+}
+
+fn rect_at(x: f32, y: f32, width: f32, height: f32) -> Rect {
+    let mut rect = Rect::new();
+    rect.set_x_min(x);
+    rect.set_x_max(x + width);
+    rect.set_y_min(y);
+    rect.set_y_max(y + height);
+    rect
+}
+
+fn fill_style(color: Color) -> VisualStyle {
+    VisualStyle::same((color, 0.0, 0).into())
+}
+
+fn marker_style(border: Color, radius: u8) -> VisualStyle {
+    VisualStyle::same(WidgetStyle {
+        fill: Color::TRANSPARENT,
+        border: Border::same(2.0).color(border),
+        radius: Radius::same(radius),
+        shadow: Shadow::new(),
+    })
+}
+
+/// ### ColorPicker的示例用法
+/// ```
+/// use xlui::*;
+///
+/// fn color_changed<A:App>(_:&mut A,_:&mut Ui,color:Color){
+///     println!("颜色改变了:{}",color.to_hex());
+/// }
+///
+/// fn draw<A:App>(ui:&mut Ui){
+///     let picker=ColorPicker::new(Color::rgb(64,128,230))
+///         //最近使用的颜色
+///         .with_recent_colors(vec![Color::RED,Color::ORANGE])
+///         .connect(color_changed::<A>);
+///     ui.add(picker);
+///     //点击后在弹窗中选择颜色
+///     ui.add(ColorButton::new(Color::BLACK).connect(color_changed::<A>));
+/// }
+/// ```
+pub struct ColorPicker {
+    pub(crate) id: String,
+    visual: Visual,
+    geometry: Geometry,
+    state: WidgetState,
+    hue: f32,
+    saturation: f32,
+    value: f32,
+    alpha: u8,
+    original: Color, //创建或打开时的颜色，与当前颜色对比显示
+    recent: Vec<Color>,
+    callback: Option<ColorCallB>,
+    contact: Option<Arc<RwLock<Color>>>,
+    base_render: RenderParam, //方块的纯色底色，叠加白色及黑色渐变
+    white_renders: Vec<RenderParam>,
+    black_renders: Vec<RenderParam>,
+    hue_renders: Vec<RenderParam>,
+    alpha_back: RenderParam,
+    alpha_renders: Vec<RenderParam>,
+    square_marker: RenderParam,
+    hue_marker: RenderParam,
+    alpha_marker: RenderParam,
+    original_render: RenderParam,
+    preview_render: RenderParam,
+    swatch_renders: Vec<RenderParam>,
+    hex_edit: TextEdit,
+    labels: Vec<TextBuffer>,
+    fields: Vec<SpinBox<i32>>, //R、G、B、A输入框
+    drag: Option<ColorDrag>,
+    hex_changed: bool,
+    changed: bool,
+}
+
+impl ColorPicker {
+    pub fn new(color: Color) -> ColorPicker {
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(245, 245, 245),
+            border: Border::same(0.0),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        //白色从左到右、黑色从上到下逐渐透明，叠加后为饱和度及明度的渐变
+        let gradient = |index: usize, color: &Color| {
+            let alpha = (1.0 - (index as f32 + 0.5) / STEPS as f32) * 255.0;
+            RenderParam::new(Shape::rectangle()).with_style(fill_style(Color::rgba(color.r, color.g, color.b, alpha as u8)))
+        };
+        let hue = |index: usize| {
+            let hue = (index as f32 + 0.5) / HUE_STEPS as f32 * 360.0;
+            RenderParam::new(Shape::rectangle()).with_style(fill_style(Color::from_hsva(hue, 1.0, 1.0, 255)))
+        };
+        let mut picker = ColorPicker {
+            id: gen_unique_id(),
+            visual: Visual::new().with_enable().with_style(style),
+            geometry: Geometry::new().with_context_size(WIDTH, HEIGHT).with_padding(Padding::same(6.0)),
+            state: WidgetState::default(),
+            hue: 0.0,
+            saturation: 0.0,
+            value: 0.0,
+            alpha: 255,
+            original: color.clone(),
+            recent: vec![],
+            callback: None,
+            contact: None,
+            base_render: RenderParam::new(Shape::rectangle()),
+            white_renders: (0..STEPS).map(|x| gradient(x, &Color::WHITE)).collect(),
+            black_renders: (0..STEPS).map(|x| gradient(STEPS - 1 - x, &Color::BLACK)).collect(),
+            hue_renders: (0..HUE_STEPS).map(hue).collect(),
+            alpha_back: RenderParam::new(Shape::rectangle()).with_style(fill_style(Color::rgb(204, 204, 204))),
+            alpha_renders: (0..STEPS).map(|_| RenderParam::new(Shape::rectangle())).collect(),
+            square_marker: RenderParam::new(Shape::circle()).with_style(marker_style(Color::WHITE, 5)).with_size(10.0, 10.0),
+            hue_marker: RenderParam::new(Shape::rectangle()).with_style(marker_style(Color::rgb(60, 60, 60), 1)).with_size(STRIP + 4.0, 6.0),
+            alpha_marker: RenderParam::new(Shape::rectangle()).with_style(marker_style(Color::rgb(60, 60, 60), 1)).with_size(6.0, BAR + 4.0),
+            original_render: RenderParam::new(Shape::rectangle()).with_style(fill_style(color.clone())).with_size(40.0, FIELD_HEIGHT),
+            preview_render: RenderParam::new(Shape::rectangle()).with_size(40.0, FIELD_HEIGHT),
+            swatch_renders: vec![],
+            hex_edit: TextEdit::single_edit(color.to_hex()).with_width(WIDTH - 92.0),
+            labels: ["R", "G", "B", "A"].iter().map(|x| TextBuffer::new(RichText::new(*x).size(11.0)).with_align(Align::Center)).collect(),
+            fields: (0..4).map(|_| SpinBox::new(0, 1, 0..255).with_format(|v| v.to_string()).with_size((WIDTH - 12.0) / 4.0, FIELD_HEIGHT)).collect(),
+            drag: None,
+            hex_changed: false,
+            changed: true,
+        };
+        picker.set_color(color);
+        picker
+    }
+
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    ///最近使用的颜色，最多保留10个
+    pub fn with_recent_colors(mut self, colors: Vec<Color>) -> Self {
+        self.recent = colors;
+        self.recent.truncate(MAX_RECENT);
+        self
+    }
+
+    pub(crate) fn contact(mut self, color: Arc<RwLock<Color>>) -> Self {
+        self.contact = Some(color);
+        self
+    }
+
+    pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, Color)) -> Self {
+        self.callback = Some(Callback::create_color(f));
+        self
+    }
+
+    pub fn set_callback<A: App>(&mut self, f: fn(&mut A, &mut Ui, Color)) {
+        self.callback = Some(Callback::create_color(f));
+    }
+
+    pub fn color(&self) -> Color {
+        Color::from_hsva(self.hue, self.saturation, self.value, self.alpha)
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        let (hue, saturation, value) = color.to_hsv();
+        //灰色没有色相，保留原色相
+        if saturation > 0.0 { self.hue = hue; }
+        if value > 0.0 { self.saturation = saturation; }
+        self.value = value;
+        self.alpha = color.a;
+        self.sync_fields(&color);
+    }
+
+    ///同步输入框的值
+    fn sync_fields(&mut self, color: &Color) {
+        let channels = [color.r, color.g, color.b, color.a];
+        for (field, channel) in self.fields.iter_mut().zip(channels) {
+            field.set_value(channel as i32);
+        }
+        self.hex_changed = true;
+        self.changed = true;
+    }
+
+    pub fn recent_colors(&self) -> &Vec<Color> {
+        &self.recent
+    }
+
+    ///记录最近使用的颜色，重复的颜色移到最前
+    fn push_recent(&mut self) {
+        let color = self.color();
+        self.recent.retain(|x| *x != color);
+        self.recent.insert(0, color);
+        self.recent.truncate(MAX_RECENT);
+        self.changed = true;
+    }
+
+    ///设置颜色并通知
+    fn emit(&mut self, ui: &mut Ui, color: Color) {
+        self.set_color(color);
+        self.notify(ui);
+    }
+
+    ///颜色改变后写入共享的颜色并调用回调
+    fn notify(&mut self, ui: &mut Ui) {
+        let color = self.color();
+        if let Some(ref contact) = self.contact { *contact.write().unwrap() = color.clone(); }
+        if let Some(ref mut callback) = self.callback {
+            let app = ui.app.take().unwrap();
+            callback(app, ui, color);
+            ui.app.replace(app);
+        }
+        ui.context.window.request_redraw();
+    }
+
+    fn square_rect(&self) -> Rect {
+        let rect = self.geometry.context_rect();
+        rect_at(rect.dx().min, rect.dy().min, SQUARE, SQUARE)
+    }
+
+    fn hue_rect(&self) -> Rect {
+        let rect = self.geometry.context_rect();
+        rect_at(rect.dx().min + SQUARE + SPACE, rect.dy().min, STRIP, SQUARE)
+    }
+
+    fn alpha_rect(&self) -> Rect {
+        let rect = self.geometry.context_rect();
+        rect_at(rect.dx().min, rect.dy().min + SQUARE + SPACE, WIDTH, BAR)
+    }
+
+    ///预览、16进制输入框、通道标签、通道输入框及最近颜色所在行的顶部
+    fn row_tops(&self) -> [f32; 4] {
+        let preview = self.alpha_rect().dy().max + SPACE;
+        let labels = preview + FIELD_HEIGHT + 4.0;
+        let fields = labels + LABEL_HEIGHT;
+        [preview, labels, fields, fields + FIELD_HEIGHT + SPACE]
+    }
+
+    fn field_rect(&self, index: usize) -> Rect {
+        let width = (WIDTH - 12.0) / 4.0;
+        rect_at(self.geometry.context_left() + (width + 4.0) * index as f32, self.row_tops()[2], width, FIELD_HEIGHT)
+    }
+
+    fn swatch_rect(&self, index: usize) -> Rect {
+        let space = (WIDTH - SWATCH * MAX_RECENT as f32) / (MAX_RECENT - 1) as f32;
+        rect_at(self.geometry.context_left() + (SWATCH + space) * index as f32, self.row_tops()[3], SWATCH, SWATCH)
+    }
+
+    ///按鼠标位置设置拖动的分量
+    fn drag_to(&mut self, ui: &mut Ui, drag: ColorDrag) {
+        let pos = ui.device.device_input.mouse.lastest.relative;
+        let ratio = |value: f32, min: f32, length: f32| ((value - min) / length).clamp(0.0, 1.0);
+        let (mut hue, mut saturation, mut value, mut alpha) = (self.hue, self.saturation, self.value, self.alpha);
+        match drag {
+            ColorDrag::Square => {
+                let rect = self.square_rect();
+                saturation = ratio(pos.x, rect.dx().min, rect.width());
+                value = 1.0 - ratio(pos.y, rect.dy().min, rect.height());
+            }
+            ColorDrag::Hue => {
+                let rect = self.hue_rect();
+                hue = ratio(pos.y, rect.dy().min, rect.height()) * 359.9;
+            }
+            ColorDrag::Alpha => {
+                let rect = self.alpha_rect();
+                alpha = (ratio(pos.x, rect.dx().min, rect.width()) * 255.0).round() as u8;
+            }
+        }
+        if (hue, saturation, value, alpha) == (self.hue, self.saturation, self.value, self.alpha) { return; }
+        (self.hue, self.saturation, self.value, self.alpha) = (hue, saturation, value, alpha);
+        self.sync_fields(&self.color());
+        self.notify(ui);
+    }
+
+    ///输入框的值改变时更新颜色
+    fn update_from_fields(&mut self, ui: &mut Ui) {
+        let channels = self.fields.iter().map(|x| x.value().clamp(0, 255) as u8).collect::<Vec<_>>();
+        let color = Color::rgba(channels[0], channels[1], channels[2], channels[3]);
+        let current = self.color();
+        if [current.r, current.g, current.b, current.a] == [color.r, color.g, color.b, color.a] { return; }
+        self.emit(ui, color);
+    }
+
+    ///解析16进制输入框，无效时恢复为当前颜色
+    fn update_from_hex(&mut self, ui: &mut Ui) {
+        match Color::from_hex(&self.hex_edit.text()) {
+            Some(color) if color != self.color() => self.emit(ui, color),
+            _ => self.hex_changed = true,
+        }
+        ui.context.window.request_redraw();
+    }
+
+    ///将事件传给输入框
+    fn update_children(&mut self, ui: &mut Ui) {
+        let focused = self.hex_edit.state().focused;
+        self.hex_edit.update(ui);
+        if let UpdateType::MousePress = ui.update_type && focused && !self.hex_edit.state().focused { self.update_from_hex(ui); }
+        for field in self.fields.iter_mut() { field.update(ui); }
+        self.update_from_fields(ui);
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        self.hex_edit.update(ui);
+        for label in self.labels.iter_mut() {
+            label.init(ui);
+            label.geometry.set_fix_size((WIDTH - 12.0) / 4.0, LABEL_HEIGHT);
+        }
+        for field in self.fields.iter_mut() { field.update(ui); }
+        self.visual.rect_mut().set_size(self.geometry.padding_width(), self.geometry.padding_height());
+    }
+
+    ///颜色改变后更新各渐变的颜色
+    fn update_styles(&mut self) {
+        if !self.changed { return; }
+        self.changed = false;
+        let color = self.color();
+        self.base_render.set_style(fill_style(Color::from_hsva(self.hue, 1.0, 1.0, 255)));
+        for (index, render) in self.alpha_renders.iter_mut().enumerate() {
+            let alpha = (index as f32 + 0.5) / STEPS as f32 * 255.0;
+            render.set_style(fill_style(Color::rgba(color.r, color.g, color.b, alpha as u8)));
+        }
+        self.preview_render.set_style(fill_style(color));
+        self.original_render.set_style(fill_style(self.original.clone()));
+        self.swatch_renders.resize_with(self.recent.len(), || RenderParam::new(Shape::rectangle()).with_size(SWATCH, SWATCH));
+        for (render, color) in self.swatch_renders.iter_mut().zip(self.recent.iter()) {
+            let mut style = fill_style(color.clone());
+            style.hovered.border = Border::same(1.0).color(Color::rgb(60, 60, 60));
+            render.set_style(style);
+        }
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        let contact = self.contact.as_ref().map(|x| x.read().unwrap().clone());
+        if let Some(color) = contact && color != self.color() {
+            //共享的颜色在其它控件中改变
+            self.original = color.clone();
+            self.set_color(color);
+        }
+        self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+        self.geometry.offset_to_rect(&ui.draw_rect);
+        self.update_styles();
+        self.visual.draw(ui, self.state.disabled, false, false, false);
+        //饱和度及明度方块
+        let square = self.square_rect();
+        let (width, height) = (square.width() / STEPS as f32, square.height() / STEPS as f32);
+        *self.base_render.rect_mut() = square.clone();
+        self.base_render.draw(ui, false, false, false);
+        for (index, render) in self.white_renders.iter_mut().enumerate() {
+            *render.rect_mut() = rect_at(square.dx().min + width * index as f32, square.dy().min, width + 0.5, square.height());
+            render.draw(ui, false, false, false);
+        }
+        for (index, render) in self.black_renders.iter_mut().enumerate() {
+            *render.rect_mut() = rect_at(square.dx().min, square.dy().min + height * index as f32, square.width(), height + 0.5);
+            render.draw(ui, false, false, false);
+        }
+        let x = square.dx().min + self.saturation * square.width() - 5.0;
+        let y = square.dy().min + (1.0 - self.value) * square.height() - 5.0;
+        self.square_marker.offset_to_rect(&rect_at(x, y, 10.0, 10.0));
+        self.square_marker.draw(ui, false, false, false);
+        //色相条
+        let hue = self.hue_rect();
+        let height = hue.height() / HUE_STEPS as f32;
+        for (index, render) in self.hue_renders.iter_mut().enumerate() {
+            *render.rect_mut() = rect_at(hue.dx().min, hue.dy().min + height * index as f32, hue.width(), height + 0.5);
+            render.draw(ui, false, false, false);
+        }
+        self.hue_marker.offset_to_rect(&rect_at(hue.dx().min - 2.0, hue.dy().min + self.hue / 360.0 * hue.height() - 3.0, 0.0, 0.0));
+        self.hue_marker.draw(ui, false, false, false);
+        //透明度条
+        let alpha = self.alpha_rect();
+        *self.alpha_back.rect_mut() = alpha.clone();
+        self.alpha_back.draw(ui, false, false, false);
+        let width = alpha.width() / STEPS as f32;
+        for (index, render) in self.alpha_renders.iter_mut().enumerate() {
+            *render.rect_mut() = rect_at(alpha.dx().min + width * index as f32, alpha.dy().min, width + 0.5, alpha.height());
+            render.draw(ui, false, false, false);
+        }
+        self.alpha_marker.offset_to_rect(&rect_at(alpha.dx().min + self.alpha as f32 / 255.0 * alpha.width() - 3.0, alpha.dy().min - 2.0, 0.0, 0.0));
+        self.alpha_marker.draw(ui, false, false, false);
+        //原颜色及当前颜色
+        let tops = self.row_tops();
+        let left = self.geometry.context_left();
+        self.original_render.offset_to_rect(&rect_at(left, tops[0], 0.0, 0.0));
+        self.original_render.draw(ui, false, false, false);
+        self.preview_render.offset_to_rect(&rect_at(left + 40.0, tops[0], 0.0, 0.0));
+        self.preview_render.draw(ui, false, false, false);
+        for index in 0..self.swatch_renders.len() {
+            let rect = self.swatch_rect(index);
+            let hovered = ui.device.device_input.hovered_at(&rect);
+            self.swatch_renders[index].offset_to_rect(&rect);
+            self.swatch_renders[index].draw(ui, false, hovered, false);
+        }
+        self.redraw_children(ui);
+    }
+
+    fn redraw_children(&mut self, ui: &mut Ui) {
+        let tops = self.row_tops();
+        let left = self.geometry.context_left();
+        let changed = ui.widget_changed;
+        if std::mem::take(&mut self.hex_changed) { self.hex_edit.update_text(ui, self.color().to_hex()); }
+        ui.widget_changed = changed | WidgetChange::Position;
+        ui.draw_rect = rect_at(left + 88.0, tops[0], WIDTH - 88.0, FIELD_HEIGHT);
+        self.hex_edit.update(ui);
+        for index in 0..self.fields.len() {
+            let rect = self.field_rect(index);
+            let mut label_rect = rect.clone();
+            label_rect.set_y_min(tops[1]);
+            self.labels[index].geometry.offset_to_rect(&label_rect);
+            self.labels[index].redraw(ui);
+            ui.widget_changed = changed | WidgetChange::Position;
+            ui.draw_rect = rect;
+            self.fields[index].update(ui);
+        }
+    }
+}
+
+impl Widget for ColorPicker {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::Init | UpdateType::ReInit => self.init(ui),
+            UpdateType::MousePress => {
+                let input = &ui.device.device_input;
+                self.drag = if input.pressed_at(&self.square_rect()) {
+                    Some(ColorDrag::Square)
+                } else if input.pressed_at(&self.hue_rect()) {
+                    Some(ColorDrag::Hue)
+                } else if input.pressed_at(&self.alpha_rect()) {
+                    Some(ColorDrag::Alpha)
+                } else {
+                    None
+                };
+                match self.drag {
+                    Some(drag) => self.drag_to(ui, drag),
+                    None => self.update_children(ui),
+                }
+            }
+            UpdateType::MouseMove => match self.drag {
+                Some(drag) => self.drag_to(ui, drag),
+                None => {
+                    if (0..self.recent.len()).any(|x| ui.device.device_input.hovered_at(&self.swatch_rect(x))) { ui.context.window.request_redraw(); }
+                    self.update_children(ui);
+                }
+            },
+            UpdateType::MouseRelease if self.drag.is_some() => {
+                self.drag = None;
+                self.push_recent();
+                ui.context.window.request_redraw();
+            }
+            UpdateType::MouseRelease => {
+                let swatch = (0..self.recent.len()).find(|x| ui.device.device_input.click_at(&self.swatch_rect(*x)));
+                match swatch {
+                    Some(index) => self.emit(ui, self.recent[index].clone()),
+                    None => self.update_children(ui),
+                }
+            }
+            UpdateType::KeyRelease(Key::Enter) if self.hex_edit.state().focused => {
+                self.hex_edit.state().focused = false;
+                self.update_from_hex(ui);
+                self.push_recent();
+            }
+            _ => self.update_children(ui),
+        }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -1,11 +1,12 @@
 use crate::key::Key;
 use crate::render::{RenderParam, VisualStyle, WidgetStyle};
-use crate::response::{Callback, Response};
+use crate::response::{Callback, InnerValuesCallB, Response};
 use crate::shape::Shape;
 use crate::size::Geometry;
 use crate::widgets::{WidgetChange, WidgetSize, WidgetState};
 use crate::*;
 use std::fmt::Display;
+use std::mem;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
@@ -36,6 +37,8 @@ pub struct CheckComboBox<T> {
     data: Vec<T>,
     popup_rect: Rect,
     callback: Option<Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, &T)>>,
+    inner_callback: Option<InnerValuesCallB>,
+    open_callback: Option<Box<dyn Fn()>>,
     allow_render: RenderParam,
 
     selected: Arc<RwLock<Vec<String>>>,
@@ -56,6 +59,8 @@ impl<T: Display + 'static> CheckComboBox<T> {
             data,
             popup_rect: Rect::new().with_size(100.0, 150.0),
             callback: None,
+            inner_callback: None,
+            open_callback: None,
             selected: Arc::new(RwLock::new(vec![])),
 
             changed: Arc::new(AtomicBool::new(false)),
@@ -79,13 +84,16 @@ impl<T: Display + 'static> CheckComboBox<T> {
         let value = item.to_string();
         let current = self.selected.clone();
         let state = self.changed.clone();
-        let item = CheckBox::new(false, &value).with_width(self.popup_rect.width() - 10.0);
+        let inner_callback = self.inner_callback.clone();
+        let checked = self.selected.read().unwrap().contains(&value);
+        let item = CheckBox::new(checked, &value).with_width(self.popup_rect.width() - 10.0);
         let mut item = item.connect_inner(move || {
             let mut current = current.write().unwrap();
             match current.iter().position(|x| x == &value) {
                 None => current.push(value.clone()),
                 Some(index) => { current.remove(index); }
             }
+            if let Some(ref callback) = inner_callback { callback(&current); }
             state.store(true, Ordering::SeqCst);
         });
         item.geometry_mut().an(Align::LeftCenter).pd(Padding::same(3.0));
@@ -106,11 +114,46 @@ impl<T: Display + 'static> CheckComboBox<T> {
         }
     }
 
+    ///替换全部选项并重建弹窗，仍存在的已选值保持选中，不存在的已选值被移除
+    pub(crate) fn set_data(&mut self, ui: &mut Ui, data: Vec<T>) {
+        self.data = data;
+        let values: Vec<String> = self.data.iter().map(|x| x.to_string()).collect();
+        let mut selected = self.selected.write().unwrap();
+        let count = selected.len();
+        selected.retain(|x| values.contains(x));
+        if selected.len() != count {
+            if let Some(ref callback) = self.inner_callback { callback(&selected); }
+            self.changed.store(true, Ordering::SeqCst);
+        }
+        drop(selected);
+        let popups = ui.popups.as_mut().unwrap();
+        let mut popup = Popup::new(self.popup_rect.width(), self.popup_rect.height());
+        popup.id = self.popup_id.clone();
+        popup.set_rect(popups[&self.popup_id].rect().clone());
+        popup.inherit_state(&mut popups[&self.popup_id]);
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        popup.show(ui, |ui| self.add_items(ui));
+        ui.update_type = previous_update;
+        ui.context.window.request_redraw();
+    }
+
     pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, &T)) -> Self {
         self.callback = Some(Callback::create_combobox(f));
         self
     }
 
+    ///选中项改变时回调，参数为当前全部选中项
+    pub(crate) fn connect_inner(mut self, callback: impl Fn(&[String]) + 'static) -> Self {
+        self.inner_callback = Some(Arc::new(callback));
+        self
+    }
+
+    ///弹窗打开时回调，可在其中通过set_data更新选项
+    pub(crate) fn connect_open(mut self, callback: impl Fn() + 'static) -> Self {
+        self.open_callback = Some(Box::new(callback));
+        self
+    }
+
     fn init(&mut self, ui: &mut Ui) {
         //下拉框布局
         let popup = Popup::new(self.popup_rect.width(), self.popup_rect.height());
@@ -161,6 +204,7 @@ impl<T: Display + 'static> Widget for CheckComboBox<T> {
                 let clicked = ui.device.device_input.mouse.clicked.load(Ordering::SeqCst);
                 self.edit.update(ui);
                 if clicked && self.edit.buffer().geometry.padding_rect().has_position(ui.device.device_input.mouse.lastest.relative) {
+                    if let Some(ref callback) = self.open_callback { callback(); }
                     let popup = &mut ui.popups.as_mut().unwrap()[&self.popup_id];
                     popup.request_state(true);
                     ui.update_type = UpdateType::None;
@@ -16,7 +16,7 @@ use crate::text::buffer::TextBuffer;
 use crate::ui::Ui;
 use crate::widgets::select::SelectItem;
 use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
-use crate::{Align, Offset, Shadow};
+use crate::{Align, Offset, RichText, Shadow, TextWrap};
 use std::fmt::Display;
 use std::sync::{Arc, RwLock};
 
@@ -67,7 +67,7 @@ impl<T: Display + 'static> ComboBox<T> {
         fill_style.inactive.border = Border::same(0.0);
         fill_style.pressed.fill = Color::rgb(210, 210, 210);
         let allow_style = VisualStyle::same((Color::BLACK, 0.0, 0).into());
-        let buffer = TextBuffer::new("").with_align(Align::LeftCenter)
+        let buffer = TextBuffer::new(RichText::new("").wrap(TextWrap::ElideEnd)).with_align(Align::LeftCenter)
             .padding(Padding::same(2.0)).fix_width(100.0).fix_height(20.0);
         let mut allow_render = RenderParam::new(Shape::triangle()).with_style(allow_style);
         allow_render.set_poses((0.0, 0.0).into(), (10.0, 0.0).into(), (5.0, 8.0).into());
@@ -126,6 +126,10 @@ impl<T: Display + 'static> ComboBox<T> {
         popup.show(ui, |ui| self.add_items(ui));
     }
 
+    pub(crate) fn popup_id(&self) -> &String {
+        &self.popup_id
+    }
+
     pub fn parent(&self) -> Arc<RwLock<Option<String>>> {
         self.selected.clone()
     }
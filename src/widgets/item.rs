@@ -1,19 +1,22 @@
 use crate::frame::context::UpdateType;
 use crate::layout::LayoutKind;
-use crate::render::{Visual, VisualStyle};
-use crate::response::Response;
+use crate::render::{RenderParam, Visual, VisualStyle};
+use crate::response::{AppNotifies, AppNotify, Response};
 use crate::size::Geometry;
 use crate::ui::Ui;
 use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
-use crate::Color;
-use std::sync::{Arc, RwLock};
+use crate::shape::Shape;
+use crate::{Color, Rect};
 
 pub struct ItemWidget {
     id: String,
     visual: Visual,
     layout: Option<LayoutKind>,
     data_str: String,
-    current: Arc<RwLock<Option<String>>>,
+    selected: bool,
+    notifies: Vec<AppNotifies>,
+    drop_line: RenderParam,
+    drop_at: Option<bool>, //拖动排序时的插入线，false在顶部，true在底部
     callback: Option<Box<dyn Fn(&String, &mut Ui)>>,
     geometry: Geometry,
     state: WidgetState,
@@ -30,7 +33,10 @@ impl ItemWidget {
             visual: Visual::new().with_enable().with_style(style),
             layout: Some(layout),
             data_str,
-            current: Arc::new(RwLock::new(None)),
+            selected: false,
+            notifies: vec![],
+            drop_line: RenderParam::new(Shape::rectangle()).with_style(VisualStyle::same((Color::rgb(30, 120, 230), 0.0, 0).into())),
+            drop_at: None,
             callback: None,
             geometry: Geometry::new(),
             state: WidgetState::default(),
@@ -56,11 +62,24 @@ impl ItemWidget {
         self
     }
 
-    pub(crate) fn parent(mut self, parent: Arc<RwLock<Option<String>>>) -> Self {
-        self.current = parent;
+    ///布局更新时调用选择改变、排序等回调
+    pub(crate) fn with_notify(mut self, notify: &AppNotifies) -> Self {
+        self.notifies.push(notify.clone());
         self
     }
 
+    pub(crate) fn rect(&self) -> &Rect {
+        self.visual.rect()
+    }
+
+    pub(crate) fn set_drop_line(&mut self, drop_at: Option<bool>) {
+        self.drop_at = drop_at;
+    }
+
+    pub(crate) fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+    }
+
     fn update_buffer(&mut self, ui: &mut Ui) {
         // let current = self.current.read().unwrap();
         // if current.as_ref() != Some(&self.data_str) {
@@ -101,16 +120,23 @@ impl ItemWidget {
 
     fn redraw(&mut self, ui: &mut Ui) {
         self.update_buffer(ui);
-        let current = self.current.read().unwrap();
-        let selected = current.as_ref() == Some(&self.data_str);
-        self.visual.draw(ui, self.state.disabled, self.state.hovered || selected, selected, false);
+        self.visual.draw(ui, self.state.disabled, self.state.hovered || self.selected, self.selected, false);
         self.layout.as_mut().unwrap().update(ui);
+        if let Some(bottom) = self.drop_at {
+            let mut rect = self.visual.rect().clone();
+            let y = if bottom { rect.dy().max } else { rect.dy().min - 2.0 };
+            rect.set_y_min(y);
+            rect.set_y_max(y + 2.0);
+            *self.drop_line.rect_mut() = rect;
+            self.drop_line.draw(ui, false, false, false);
+        }
     }
 }
 
 impl Widget for ItemWidget {
     fn update(&mut self, ui: &mut Ui) -> Response<'_> {
         // self.layout.as_mut().unwrap().update(ui);注意这里不能直接调widgets的update
+        self.notifies.iter().for_each(|notify| AppNotify::emit(notify, ui));
         match ui.update_type {
             UpdateType::Draw => self.redraw(ui),
             // #[cfg(feature = "gpu")]
@@ -1,5 +1,6 @@
 use crate::align::Align;
 use crate::frame::context::UpdateType;
+use crate::key::Key;
 use crate::render::Visual;
 use crate::response::Response;
 use crate::size::Geometry;
@@ -7,7 +8,12 @@ use crate::text::buffer::TextBuffer;
 use crate::text::rich::RichText;
 use crate::text::TextWrap;
 use crate::ui::Ui;
+use crate::widgets::textedit::buffer::CharBuffer;
+use crate::widgets::textedit::cursor::EditCursor;
+use crate::widgets::textedit::select::EditSelection;
 use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
+#[cfg(not(feature = "winit"))]
+use crate::window::ClipboardData;
 /// ### Label的示例用法
 /// ```
 /// use xlui::*;
@@ -22,17 +28,31 @@ use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
 ///         //设置控件宽度
 ///         .width(100.0)
 ///         //设置控件高度
-///         .height(100.0);
+///         .height(100.0)
+///         //超出宽度时中间省略
+///         .wrap(TextWrap::ElideMiddle);
+///     //可选择、复制的Label
+///     ui.add(Label::new("可复制的文本").selectable());
 ///     //获取控件ID
 ///     let _id=label.id();
 ///     ui.add(label);
 /// }
+///
+/// fn update(ui:&mut Ui,id:&str){
+///     //布局测量后才能判断文本是否被省略，被省略时可以获取完整文本，用于提示
+///     let label:&mut Label=ui.get_widget(id).unwrap();
+///     if label.is_elided() { println!("{}", label.text()); }
+/// }
 /// ```
 pub struct Label {
     id: String,
-    buffer: TextBuffer,
+    char_layout: CharBuffer,
     state: WidgetState,
     visual: Visual,
+    selectable: bool,
+    cursor_render: EditCursor,
+    select_render: EditSelection,
+    press_time: u128,
 }
 
 impl Label {
@@ -40,47 +60,86 @@ impl Label {
         let buffer = TextBuffer::new(text);
         Label {
             id: crate::gen_unique_id(),
-            buffer,
+            char_layout: CharBuffer::from_buffer(buffer),
             state: WidgetState::default(),
             visual: Visual::new(),
+            selectable: false,
+            cursor_render: EditCursor::new(),
+            select_render: EditSelection::new(),
+            press_time: 0,
         }
     }
+
+    ///文本可选择、复制(不可编辑)
+    pub fn selectable(mut self) -> Self {
+        self.selectable = true;
+        self
+    }
+
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+        if !selectable { self.select_render.reset(&self.cursor_render); }
+    }
+
+    ///当前选择的文本
+    pub fn selected_text(&self) -> String {
+        self.char_layout.select_text(&self.select_render, &self.cursor_render)
+    }
     ///仅作用于draw
     pub fn wrap(mut self, wrap: TextWrap) -> Self {
-        self.buffer.set_wrap(wrap);
+        self.char_layout.buffer.set_wrap(wrap);
         self
     }
 
     ///仅作用于draw
     pub fn align(mut self, align: Align) -> Self {
-        self.buffer.geometry.set_align(align);
+        self.char_layout.buffer.geometry.set_align(align);
+        self
+    }
+
+    ///最大显示行数，超出的部分以"…"结尾
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.char_layout.buffer.set_max_lines(max_lines);
         self
     }
 
     ///设置文本
     pub fn set_text(&mut self, text: impl ToString) {
-        self.buffer.set_text(text.to_string());
+        let text = text.to_string();
+        self.char_layout.set_source(&text);
+        self.char_layout.buffer.set_text(text);
     }
 
     ///仅作用于draw
     pub fn width(mut self, w: f32) -> Self {
-        self.buffer.geometry.set_fix_width(w);
+        self.char_layout.buffer.geometry.set_fix_width(w);
         self
     }
 
     pub fn max_width(mut self, w: f32) -> Self {
-        self.buffer.geometry.set_max_width(w);
+        self.char_layout.buffer.geometry.set_max_width(w);
         self
     }
     ///仅作用于draw
     pub fn height(mut self, h: f32) -> Self {
-        self.buffer.geometry.set_fix_height(h);
-        // self.buffer.set_height(h);
+        self.char_layout.buffer.geometry.set_fix_height(h);
+        // self.char_layout.buffer.set_height(h);
         self
     }
 
+    ///完整文本，省略显示时也返回完整文本
     pub fn text(&self) -> &String {
-        &self.buffer.text.text
+        &self.char_layout.buffer.text.text
+    }
+
+    ///文本是否被省略显示
+    pub fn is_elided(&self) -> bool {
+        self.char_layout.buffer.is_elided()
+    }
+
+    ///实际显示的文本
+    pub fn display_text(&self) -> String {
+        self.char_layout.buffer.display_text()
     }
 
     pub fn id(&self) -> &str {
@@ -93,29 +152,113 @@ impl Label {
     }
 
     fn init(&mut self, ui: &mut Ui) {
-        self.buffer.init(ui);
+        self.char_layout.buffer.init(ui);
+        self.init_select();
+    }
+
+    fn init_select(&mut self) {
+        if !self.selectable { return; }
+        self.cursor_render.init(&self.char_layout, true);
+        self.select_render.init(self.char_layout.buffer.lines.len(), self.char_layout.buffer.text.height);
+        self.select_render.reset(&self.cursor_render);
+    }
+
+    fn update_select_position(&mut self) {
+        let mut cursor_rect = self.char_layout.buffer.geometry.context_rect();
+        cursor_rect.set_width(2.0);
+        cursor_rect.set_height(self.char_layout.buffer.text.height);
+        self.cursor_render.update_position(cursor_rect, &self.char_layout);
+        self.select_render.update_position(self.char_layout.buffer.geometry.context_rect());
+    }
+
+    fn select_word(&mut self) {
+        let range = self.char_layout.word_range(&self.cursor_render);
+        let vert = self.cursor_render.vert;
+        self.cursor_render.set_cursor(range.end, vert, &self.char_layout);
+        self.select_render.select_by_ime(range.start, vert, &self.char_layout, &self.cursor_render);
+    }
+
+    fn select_all(&mut self) {
+        let vert = self.char_layout.buffer.lines.len() - 1;
+        let horiz = self.char_layout.buffer.lines[vert].len();
+        self.cursor_render.set_cursor(horiz, vert, &self.char_layout);
+        self.select_render.select_by_ime(0, 0, &self.char_layout, &self.cursor_render);
+    }
+
+    fn select_event(&mut self, ui: &mut Ui) {
+        match ui.update_type {
+            UpdateType::MouseMove if self.state.hovered_moving() => {
+                self.select_render.move_select(ui, &mut self.cursor_render, &mut self.char_layout);
+                self.char_layout.buffer.clip_x = self.char_layout.offset.x;
+                ui.context.window.request_redraw();
+            }
+            UpdateType::MousePress => {
+                let pressed = ui.device.device_input.pressed_at(&self.char_layout.buffer.geometry.context_rect());
+                if !pressed {
+                    if self.state.on_release() { ui.context.window.request_redraw(); }
+                    return;
+                }
+                self.state.on_pressed(true);
+                let pos = ui.device.device_input.mouse.lastest.relative;
+                self.cursor_render.update_by_pos(pos, &mut self.char_layout);
+                let press_time = crate::time_ms();
+                if press_time - self.press_time < 400 {
+                    self.select_word();
+                } else {
+                    self.select_render.set_by_cursor(&self.cursor_render);
+                }
+                self.press_time = press_time;
+                ui.context.window.request_redraw();
+            }
+            UpdateType::MouseRelease => self.state.pressed = false,
+            //各后端的组合键只发送按下或释放其中之一
+            UpdateType::KeyPress(ref key) | UpdateType::KeyRelease(ref key) => {
+                if !self.state.focused { return; }
+                match key {
+                    Key::CtrlA => {
+                        self.select_all();
+                        ui.context.window.request_redraw();
+                    }
+                    //winit后端暂无剪贴板
+                    #[cfg(not(feature = "winit"))]
+                    Key::CtrlC => {
+                        let text = self.selected_text();
+                        ui.context.window.set_clipboard(ClipboardData::Text(text));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
     }
 
     fn update_before_draw(&mut self, ui: &mut Ui) {
         if let Some(v) = ui.context.updates.remove(&self.id) {
-            v.update_str(&mut self.buffer.text.text);
-            self.buffer.change = true;
+            v.update_str(&mut self.char_layout.buffer.text.text);
+            let text = self.char_layout.buffer.text.text.clone();
+            self.char_layout.set_source(&text);
+            self.char_layout.buffer.change = true;
         }
-        if self.buffer.change {
+        if self.char_layout.buffer.change {
             ui.widget_changed |= WidgetChange::Value;
         }
         if ui.widget_changed.contains(WidgetChange::Position) {
-            self.buffer.geometry.offset_to_rect(&ui.draw_rect);
+            self.char_layout.buffer.geometry.offset_to_rect(&ui.draw_rect);
         }
 
         if ui.widget_changed.contains(WidgetChange::Value) {
-            self.buffer.update_buffer(ui);
+            self.char_layout.buffer.update_buffer(ui);
+            self.init_select();
+        }
+        if self.selectable && !ui.widget_changed.unchanged() {
+            self.update_select_position();
         }
-        self.buffer.change = false;
+        self.char_layout.buffer.change = false;
     }
     fn redraw(&mut self, ui: &mut Ui) {
         self.update_before_draw(ui);
-        self.buffer.redraw(ui);
+        if self.selectable { self.select_render.render(ui, self.char_layout.buffer.lines.len()); }
+        self.char_layout.buffer.redraw(ui);
     }
 }
 
@@ -125,17 +268,18 @@ impl Widget for Label {
         self.visual.draw(ui, self.state.disabled, self.state.hovered, self.state.pressed, false);
         match &ui.update_type {
             UpdateType::Init => self.init(ui),
-            UpdateType::ReInit => self.buffer.init(ui),
+            UpdateType::ReInit => self.char_layout.buffer.init(ui),
             UpdateType::Draw => self.redraw(ui),
-            _ => self.state.handle_event(ui, &self.buffer.geometry, self.visual.disable())
+            _ if self.selectable => self.select_event(ui),
+            _ => self.state.handle_event(ui, &self.char_layout.buffer.geometry, self.visual.disable())
         }
         self.visual.draw(ui, self.state.disabled, self.state.hovered, self.state.pressed, true);
-        println!("{} {} {}", self.buffer.text.text, self.buffer.geometry.margin_width(), self.buffer.geometry.margin_height());
-        Response::new(&self.id, WidgetSize::same(self.buffer.geometry.margin_width(), self.buffer.geometry.margin_height()))
+        println!("{} {} {}", self.char_layout.buffer.text.text, self.char_layout.buffer.geometry.margin_width(), self.char_layout.buffer.geometry.margin_height());
+        Response::new(&self.id, WidgetSize::same(self.char_layout.buffer.geometry.margin_width(), self.char_layout.buffer.geometry.margin_height()))
     }
 
     fn geometry(&mut self) -> &mut Geometry {
-        &mut self.buffer.geometry
+        &mut self.char_layout.buffer.geometry
     }
 
     fn state(&mut self) -> &mut WidgetState {
@@ -0,0 +1,124 @@
+use crate::frame::context::UpdateType;
+use crate::render::{Visual, VisualStyle, WidgetStyle};
+use crate::response::Response;
+use crate::size::Geometry;
+use crate::ui::Ui;
+use crate::widgets::listview::{ListAction, ListActions};
+use crate::widgets::{Widget, WidgetSize, WidgetState};
+use crate::*;
+
+///列表右侧的索引栏，点击或拖动到索引时跳转到对应的分组
+pub(crate) struct ListIndexBar {
+    id: String,
+    visual: Visual,
+    layout: Option<LayoutKind>,
+    geometry: Geometry,
+    state: WidgetState,
+    indices: Vec<(String, usize)>, //索引的文本及对应的分组
+    actions: ListActions,
+    pressed: bool,
+    current: Option<usize>,
+}
+
+impl ListIndexBar {
+    pub fn new(width: f32, height: f32, actions: &ListActions) -> ListIndexBar {
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(0, 0, 0, 10),
+            border: Border::same(0.0),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        ListIndexBar {
+            id: gen_unique_id(),
+            visual: Visual::new().with_enable().with_style(style).with_size(width, height),
+            layout: None,
+            geometry: Geometry::new().with_context_size(width, height),
+            state: WidgetState::default(),
+            indices: vec![],
+            actions: actions.clone(),
+            pressed: false,
+            current: None,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn indices(&self) -> &Vec<(String, usize)> {
+        &self.indices
+    }
+
+    ///索引的文本，各索引平分索引栏的高度
+    pub fn index_layout(ui: &mut Ui, width: f32, height: f32, indices: &[(String, usize)]) -> LayoutKind {
+        let slot = height / indices.len().max(1) as f32;
+        let layout = VerticalLayout::top_to_bottom().with_size(width, height).with_space(0.0).with_padding(Padding::same(0.0));
+        let previous_layout = ui.layout.replace(LayoutKind::new(layout)).unwrap();
+        let previous_update = std::mem::replace(&mut ui.update_type, UpdateType::Init);
+        for (text, _) in indices.iter() {
+            ui.add(Label::new(RichText::new(text.as_str()).size(11.0)).width(width).height(slot).align(Align::Center));
+        }
+        ui.update_type = previous_update;
+        ui.layout.replace(previous_layout).unwrap()
+    }
+
+    ///设置索引的文本及对应的分组，layout由index_layout创建
+    pub fn set_indices(&mut self, indices: Vec<(String, usize)>, layout: LayoutKind) {
+        self.indices = indices;
+        self.layout = Some(layout);
+        self.current = None;
+    }
+
+    ///按鼠标位置跳转，同一索引只跳转一次
+    fn jump(&mut self, ui: &mut Ui) {
+        if self.indices.is_empty() { return; }
+        let rect = self.visual.rect();
+        let slot = rect.height() / self.indices.len() as f32;
+        let index = ((ui.device.device_input.mouse.y() - rect.dy().min) / slot).floor().max(0.0) as usize;
+        let index = index.min(self.indices.len() - 1);
+        if self.current == Some(index) { return; }
+        self.current = Some(index);
+        self.actions.write().unwrap().push(ListAction::Jump(self.indices[index].1));
+        ui.context.window.request_redraw();
+    }
+}
+
+impl Widget for ListIndexBar {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            #[cfg(feature = "gpu")]
+            UpdateType::ReInit => self.visual.re_init(),
+            UpdateType::Draw => {
+                self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+                self.visual.draw(ui, false, self.pressed, self.pressed, false);
+            }
+            UpdateType::MousePress if ui.device.device_input.pressed_at(self.visual.rect()) => {
+                self.pressed = true;
+                self.current = None;
+                self.jump(ui);
+            }
+            UpdateType::MouseMove if self.pressed => {
+                if ui.device.device_input.mouse.pressed() {
+                    self.jump(ui);
+                } else {
+                    self.pressed = false;
+                }
+            }
+            UpdateType::MouseRelease if self.pressed => {
+                self.pressed = false;
+                ui.context.window.request_redraw();
+            }
+            _ => {}
+        }
+        if let Some(layout) = self.layout.as_mut() { layout.update(ui); }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -0,0 +1,989 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::key::{Key, KeyModifiers};
+use crate::layout::recycle::RecycleLayout;
+use crate::layout::{Layout, LayoutItem, LayoutKind};
+use crate::render::{VisualStyle, WidgetStyle};
+use crate::response::{AppNotifies, AppNotify, Callback};
+use crate::size::border::Border;
+use crate::size::radius::Radius;
+use crate::style::color::Color;
+use crate::ui::Ui;
+use crate::widgets::item::ItemWidget;
+use crate::widgets::listview::index::ListIndexBar;
+use crate::widgets::listview::section::SectionHeader;
+use crate::widgets::listview::selection::{ListSelection, ListSelectionMode};
+use crate::widgets::model::{ListModel, ModelChange, ModelUpdate, ViewRows};
+use crate::widgets::WidgetKind;
+use crate::{HorizontalLayout, Label, Padding, ScrollWidget, Shadow};
+use std::cell::RefCell;
+use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+pub mod selection;
+mod section;
+mod index;
+
+pub enum ListUpdate<T> {
+    Push(T),
+    Remove,
+    Move,
+    Reset,
+}
+
+pub(crate) enum ListAction {
+    Select(usize, KeyModifiers),
+    Jump(usize),
+}
+
+pub(crate) type ListActions = Arc<RwLock<Vec<ListAction>>>;
+
+type ListItemText<T> = Box<dyn Fn(&T) -> String>;
+
+type ListSectionUi = Box<dyn Fn(&mut Ui, &str)>;
+
+///拖动排序的状态
+struct ListDrag {
+    from: usize,
+    to: usize, //插入位置，0..=data.len()
+    press_y: f32,
+    grab: f32, //按下位置距项顶部的距离
+    dragging: bool, //超过拖动阈值后开始拖动
+    scroll_time: u128, //上次自动滚动的时间
+}
+
+/// # ListView的使用示例
+/// ```
+/// use xlui::*;
+///
+/// struct XlUi{
+///     list_view: ListView<i32>
+/// }
+///
+/// impl XlUi{
+///     pub fn new()->XlUi{
+///         //这里的data可以是任意类型
+///         let data=vec![1,2,3,4];
+///         let mut list_view=ListView::new(data)
+///             //设置控件大小
+///             .with_size(100.0,100.0);
+///         //设置Item的Widget
+///         list_view.set_item_widget(|ui,datum|{
+///             ui.label(format!("Item-{}",datum))
+///         });
+///         //扩展选择：Ctrl+点击切换选中，Shift+点击或Shift+方向键选择范围
+///         list_view.set_selection_mode(ListSelectionMode::Extended);
+///         list_view.set_selected(&[1, 2]);
+///         assert_eq!(list_view.selected_indices(), vec![1, 2]);
+///         //获得焦点后输入字符，按项的文本查找并跳转
+///         list_view.set_item_text(|datum| format!("Item-{}", datum));
+///         //拖动项调整顺序，拖到边缘时自动滚动
+///         list_view.set_reorderable(true);
+///         //按代码调整顺序，与拖动相同，第0项移动到第2项
+///         list_view.move_item(0, 2);
+///         //使用模型作为数据时，其它线程可通过模型追加、修改数据，只更新受影响的可见项
+///         let model = VecModel::new(vec![1, 2, 3, 4]);
+///         list_view.set_model(model.clone());
+///         model.push(5);
+///         XlUi{
+///             list_view
+///         }
+///     }
+///
+///
+///     fn item_changed(&mut self,ui:&mut Ui){
+///         //获取当前已选择的Item
+///         if let Some(datum) = self.list_view.current() {
+///             println!("list: {:?}", self.list_view.current());
+///         }
+///         //添加一条Item
+///         self.list_view.push(0);
+///         //删除一条Item
+///         self.list_view.remove(0);
+///         //获取当前已选择Item的索引
+///         self.list_view.current_index();
+///         //获取全部已选择Item的索引
+///         self.list_view.selected_indices();
+///     }
+/// }
+///
+/// impl App for XlUi{
+///     fn draw(&mut self, ui: &mut Ui) {
+///         //设置选择改变回调函数，点击或方向键、PageUp/PageDown、Home/End移动时调用
+///         self.list_view.set_selection_callback(Self::item_changed);
+///         //拖动排序后调用，参数为原索引及新索引
+///         self.list_view.set_reorder_callback(|_: &mut Self, _, from, to| println!("move {} to {}", from, to));
+///         //监听滚动，并更新Item数据显示
+///         self.list_view.on_scrolling(|datum,layout|{
+///             let item:&mut Label=layout.get_widget(&"list_item".to_string()).unwrap();
+///             item.set_text(datum);
+///         });
+///         self.list_view.show(ui);
+///     }
+///
+///     fn update(&mut self, ui: &mut Ui) {
+///         //这里需要调update，否则push、remove不起作用
+///         self.list_view.update(ui);
+///     }
+///
+///
+/// }
+/// ```
+
+pub struct ListView<T> {
+    lid: String,
+    rows: ViewRows<T, dyn ListModel<T>>,
+    selection: ListSelection,
+    actions: ListActions,
+    notify: AppNotifies, //选择改变的通知，由可见项调用
+    reorderable: bool,
+    drag: Option<ListDrag>,
+    moves: Rc<RefCell<Vec<(usize, usize)>>>, //待通知的排序
+    reorder_notify: AppNotifies,
+    onscroll: Box<dyn Fn(&T, &mut LayoutKind)>,
+    dyn_item_widget: Box<dyn Fn(&mut Ui, &T)>,
+    updates: Vec<ListUpdate<T>>,
+    width: f32,
+    height: f32,
+    previous_display: Range<usize>,
+    item_height: f32,
+    hovered: Option<usize>,
+    changed: bool,
+    focused: bool, //点击项后接收方向键及输入查找
+    item_text: Option<ListItemText<T>>,
+    search: (String, u128), //输入查找的文本及最后输入时间
+    section_key: Option<ListItemText<T>>,
+    sections: Vec<(usize, String)>, //分组的起始索引及标题
+    section_widget: ListSectionUi,
+    section_height: f32,
+    section_version: usize, //分组或分组头控件改变时递增，分组头据此判断是否需重新绑定
+    index_bar: bool,
+    bar_id: String,
+}
+
+impl<T: 'static> ListView<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        ListView {
+            lid: "".to_string(),
+            rows: ViewRows::new(data),
+            selection: ListSelection::new(),
+            actions: Arc::new(RwLock::new(vec![])),
+            notify: Rc::new(RefCell::new(AppNotify::default())),
+            reorderable: false,
+            drag: None,
+            moves: Rc::new(RefCell::new(vec![])),
+            reorder_notify: Rc::new(RefCell::new(AppNotify::default())),
+            onscroll: Box::new(|_, _| {}),
+            dyn_item_widget: Box::new(|ui, _| { ui.add(Label::new("ListItem").with_id("list_item")); }),
+            updates: vec![],
+            width: 100.0,
+            height: 150.0,
+            previous_display: 0..0,
+            item_height: 38.0,
+            hovered: None,
+            changed: false,
+            focused: false,
+            item_text: None,
+            search: (String::new(), 0),
+            section_key: None,
+            sections: vec![],
+            section_widget: Box::new(|ui, title| ui.label(title)),
+            section_height: 26.0,
+            section_version: 0,
+            index_bar: false,
+            bar_id: "".to_string(),
+        }
+    }
+
+
+    pub fn with_size(mut self, w: f32, h: f32) -> Self {
+        self.width = w;
+        self.height = h;
+        self
+    }
+
+    pub fn with_item_height(mut self, h: f32) -> Self {
+        self.item_height = h;
+        self
+    }
+
+    pub fn with_section_height(mut self, h: f32) -> Self {
+        self.section_height = h;
+        self
+    }
+
+    ///按分组键将相邻的项分组，每组前显示分组头，滚动时当前分组头固定在顶部。
+    ///数据需按分组键排列，分组后不支持拖动排序
+    pub fn set_section_key(&mut self, key: impl Fn(&T) -> String + 'static) {
+        self.section_key = Some(Box::new(key));
+        self.sections = self.compute_sections();
+        self.section_version += 1;
+        self.updates.push(ListUpdate::Reset);
+    }
+
+    ///设置分组头的控件，参数为分组标题
+    pub fn set_section_widget(&mut self, section_widget: impl Fn(&mut Ui, &str) + 'static) {
+        self.section_widget = Box::new(section_widget);
+        self.section_version += 1;
+        self.previous_display = 0..0;
+    }
+
+    ///在列表右侧显示索引栏，索引为分组标题的首字符，点击或拖动时跳转到对应的分组，需在show之前设置
+    pub fn set_index_bar(&mut self, index_bar: bool) {
+        self.index_bar = index_bar;
+    }
+
+    ///各分组的标题
+    pub fn sections(&self) -> Vec<&str> {
+        self.sections.iter().map(|(_, title)| title.as_str()).collect()
+    }
+
+    ///第index项所在的分组
+    pub fn section_of(&self, index: usize) -> Option<usize> {
+        self.sections.partition_point(|(start, _)| *start <= index).checked_sub(1)
+    }
+
+    ///滚动使第section个分组显示在顶部
+    pub fn scroll_to_section(&mut self, section: usize) {
+        self.actions.write().unwrap().push(ListAction::Jump(section));
+    }
+
+    pub fn with_model(mut self, model: impl ListModel<T> + 'static) -> Self {
+        self.set_model(model);
+        self
+    }
+
+    ///使用模型作为数据，视图不保存数据，显示时只读取可见项，模型的变化在update中处理，只更新受影响的可见项。
+    ///设置模型后push、remove、move_item及拖动排序通过模型修改数据
+    pub fn set_model(&mut self, model: impl ListModel<T> + 'static) {
+        self.rows.set_model(Box::new(model));
+        self.selection.clear();
+        self.updates.push(ListUpdate::Reset);
+    }
+
+    pub fn set_item_widget(&mut self, item_widget: impl Fn(&mut Ui, &T) + 'static) {
+        self.dyn_item_widget = Box::new(item_widget);
+    }
+
+
+    fn item_widget(&self, ui: &mut Ui, datum: &T, index: usize) -> LayoutItem {
+        let previous_update = ui.update_type.clone();
+        ui.update_type = UpdateType::Init;
+        let mut style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(153, 193, 241, 220),
+            border: Border::same(0.0),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        style.inactive.fill = Color::TRANSPARENT;
+        style.inactive.border=Border::same(1.0).color(Color::rgb(190, 190, 190));
+
+        // let style = ClickStyle {
+        //     fill: FillStyle {
+        //         inactive: Color::TRANSPARENT,
+        //         hovered: Color::rgba(153, 193, 241, 220),
+        //         clicked: Color::rgba(153, 193, 241, 220),
+        //     },
+        //     border: BorderStyle {
+        //         inactive: Border::same(1.0).radius(Radius::same(3)).color(Color::rgb(190, 190, 190)),
+        //         hovered: Border::same(0.0).radius(Radius::same(3)),
+        //         clicked: Border::same(0.0).radius(Radius::same(3)),
+        //     },
+        // };
+        let actions = self.actions.clone();
+        let item_layout = HorizontalLayout::left_to_right().with_size(self.width - 18.0, self.item_height)
+            .with_padding(Padding::same(2.0));
+        let mut item = ItemWidget::new(LayoutKind::new(item_layout), index.to_string()).with_style(style)
+            .with_notify(&self.notify).with_notify(&self.reorder_notify).connect(move |item_id, ui| {
+            let Ok(index) = item_id.parse() else { return; };
+            let mouse = &ui.device.device_input.mouse;
+            let modifiers = KeyModifiers { shift: mouse.shift_pressed(), ctrl: mouse.ctrl_pressed() };
+            actions.write().unwrap().push(ListAction::Select(index, modifiers));
+        });
+        item.show(ui, |ui| (self.dyn_item_widget)(ui, &datum));
+        let item = WidgetKind::new(ui, item);
+        ui.update_type = previous_update;
+        LayoutItem::Widget(item)
+    }
+
+    ///当前项的索引，即最后点击或键盘移动到的项
+    pub fn current_index(&self) -> Option<usize> {
+        self.selection.current()
+    }
+
+    ///当前项的数据，设置模型时返回None，应通过current_index从模型读取
+    pub fn current(&self) -> Option<&T> {
+        let index = self.selection.current()?;
+        self.rows.data().get(index)
+    }
+
+    ///设置选择模式，切换时清空选择
+    pub fn set_selection_mode(&mut self, mode: ListSelectionMode) {
+        self.selection.set_mode(mode);
+        self.changed = true;
+    }
+
+    pub fn selection_mode(&self) -> ListSelectionMode {
+        self.selection.mode()
+    }
+
+    ///已选中项的索引，按升序排列
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selection.indices().iter().cloned().collect()
+    }
+
+    ///选中给定索引的项，最后一个为当前项，单选模式下只选中最后一个
+    pub fn set_selected(&mut self, indices: &[usize]) {
+        let indices = indices.iter().cloned().filter(|x| *x < self.rows.len()).collect::<Vec<_>>();
+        self.selection.set(&indices);
+        self.changed = true;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.changed = true;
+    }
+
+    ///允许拖动项调整顺序
+    pub fn set_reorderable(&mut self, reorderable: bool) {
+        self.reorderable = reorderable;
+    }
+
+    ///拖动排序后调用，参数为原索引及新索引
+    pub fn set_reorder_callback<A: App>(&mut self, f: impl Fn(&mut A, &mut Ui, usize, usize) + 'static) {
+        let callback = Callback::create_list_reorder(f);
+        let moves = self.moves.clone();
+        self.reorder_notify.borrow_mut().set_callback(Arc::new(move |app, ui| {
+            let moves = mem::take(&mut *moves.borrow_mut());
+            moves.into_iter().for_each(|(from, to)| callback(app, ui, from, to));
+        }));
+    }
+
+    ///将第from项移动到第to项，同时调整选择，返回是否移动。
+    ///设置模型时通过ListModel::move_row移动，模型不支持时返回false
+    pub fn move_item(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.rows.len() || to >= self.rows.len() || from == to { return false; }
+        if let Some(model) = self.rows.model() {
+            //可见项在处理模型的变化时更新
+            if !model.move_row(from, to) { return false; }
+            self.selection.move_item(from, to);
+            return true;
+        }
+        let datum = self.rows.data_mut().remove(from);
+        self.rows.data_mut().insert(to, datum);
+        self.selection.move_item(from, to);
+        self.updates.push(ListUpdate::Move);
+        true
+    }
+
+    ///设置项的文本，用于输入查找
+    pub fn set_item_text(&mut self, item_text: impl Fn(&T) -> String + 'static) {
+        self.item_text = Some(Box::new(item_text));
+    }
+
+    fn _remove(&mut self, ui: &mut Ui) {
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        recycle.remove_item();
+        recycle.items_mut().iter_mut().for_each(|x| {
+            let item: &mut ItemWidget = x.widget_mut().unwrap();
+            item.restore_status(false, 0.to_string());
+        });
+        let h = recycle.size().rh;
+        area.reset_context_height(h);
+        self.previous_display = 0..0;
+        self.hovered.take();
+        self.changed = true;
+        if let UpdateType::Draw = ui.update_type {} else { ui.context.window.request_redraw(); }
+
+        // let mut layout = ui.layout.take().expect("应在App::update中调用");
+        // let area = layout.get_layout(&self.lid).expect("找不到ListView");
+        // area.remove_widget(ui, &wid);
+        // if let LayoutKind::ScrollArea(area) = area {
+        //     area.reset_context_height();
+        // }
+        // ui.layout = Some(layout);
+    }
+
+    ///删除第index项，设置模型时通过ListModel::remove_row删除，索引越界或模型不支持时panic
+    pub fn remove(&mut self, index: usize) -> T {
+        self.try_remove(index).expect("索引越界或模型不支持删除")
+    }
+
+    ///同remove，索引越界或模型不支持时返回None
+    pub fn try_remove(&mut self, index: usize) -> Option<T> {
+        if let Some(model) = self.rows.model() { return model.remove_row(index); }
+        if index >= self.rows.len() { return None; }
+        let datum = self.rows.data_mut().remove(index);
+        self.selection.remove(index);
+        self.notify.borrow_mut().notify();
+        self.updates.push(ListUpdate::Remove);
+        // let (wid, t) = self.items.remove_map_by_index(index);
+        // let mut current = self.current.write().unwrap();
+        // if current.as_ref() == Some(&wid) { *current = None; }
+        // self.updates.push(ListUpdate::Remove(wid));
+        Some(datum)
+    }
+
+    fn _push(&mut self, datum: T, ui: &mut Ui) {
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        if recycle.items().len() < recycle.draw_count() {
+            let item = self.item_widget(ui, &datum, self.rows.len());
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            recycle.add_item(item);
+            let h = recycle.size().rh;
+            area.reset_context_height(h);
+        } else {
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            recycle.add_item_empty();
+            let h = recycle.size().rh;
+            area.reset_context_height(h);
+        }
+        self.rows.data_mut().push(datum);
+        if let UpdateType::Draw = ui.update_type {} else { ui.context.window.request_redraw(); }
+        // let mut layout = ui.layout.take().expect("应在App::update中调用");
+        // let area = layout.get_layout(&self.lid).expect("找不到ListView");
+        // if let LayoutKind::ScrollArea(area) = area {
+        //     ui.layout = Some(LayoutKind::Vertical(area.layout.take().unwrap()));
+        //     ui.update_type = UpdateType::Init;
+        //     let wid = self.item_widget(ui, &datum);
+        //     if let LayoutKind::Vertical(layout) = ui.layout.take().unwrap() {
+        //         area.layout = Some(layout);
+        //     }
+        //     ui.update_type = UpdateType::None;
+        //     area.reset_context_height();
+        //     self.items.insert(wid, datum);
+        // }
+        // ui.layout = Some(layout);
+    }
+
+    ///添加到末尾，设置模型时通过ListModel::insert_row添加，模型不支持时panic
+    pub fn push(&mut self, datum: T) {
+        assert!(self.try_push(datum), "模型不支持插入");
+    }
+
+    ///同push，返回是否已添加，模型不支持时返回false
+    pub fn try_push(&mut self, datum: T) -> bool {
+        if let Some(model) = self.rows.model() { return model.insert_row(model.row_count(), datum); }
+        self.updates.push(ListUpdate::Push(datum));
+        true
+    }
+
+    ///点击或键盘改变选择时调用，回调中可通过selected_indices获取选择
+    pub fn set_selection_callback<A: App>(&mut self, f: impl Fn(&mut A, &mut Ui) + 'static) {
+        self.notify.borrow_mut().set_callback(Arc::from(Callback::create_list(f)));
+    }
+
+    ///同set_selection_callback
+    pub fn set_callback<A: App>(&mut self, f: impl Fn(&mut A, &mut Ui) + 'static) {
+        self.set_selection_callback(f);
+    }
+
+    pub fn on_scrolling(&mut self, func: impl Fn(&T, &mut LayoutKind) + 'static) {
+        self.onscroll = Box::new(func);
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        if self.section_key.is_some() { self.updates.push(ListUpdate::Reset); }
+        if !self.index_bar { return self.show_list(ui); }
+        ui.horizontal(|ui| {
+            self.show_list(ui);
+            let bar = ListIndexBar::new(16.0, self.height, &self.actions);
+            self.bar_id = bar.id().to_string();
+            ui.add(bar);
+        });
+    }
+
+    fn show_list(&mut self, ui: &mut Ui) {
+        let layout = RecycleLayout::new().with_item_height(self.item_height).with_header_height(self.section_height);
+        let mut area = ScrollWidget::vertical().with_layout(layout).with_size(self.width, self.height);
+        self.lid = area.id.clone();
+        let fill_style = VisualStyle::same(WidgetStyle{
+            fill: Color::TRANSPARENT,
+            border: Border::same(1.0).color(Color::rgba(144, 209, 255, 255)),
+            radius: Radius::same(2),
+            shadow: Shadow::new(),
+        });
+        // fill_style.fill.inactive = Color::TRANSPARENT;
+        // fill_style.fill.hovered = Color::TRANSPARENT;
+        // fill_style.fill.clicked = Color::TRANSPARENT;
+        // fill_style.border.inactive = Border::same(1.0).color(Color::rgba(144, 209, 255, 255)).radius(Radius::same(2));
+        // fill_style.border.hovered = Border::same(1.0).color(Color::rgba(144, 209, 255, 255)).radius(Radius::same(2));
+        // fill_style.border.clicked = Border::same(1.0).color(Color::rgba(144, 209, 255, 255)).radius(Radius::same(2));
+        area.set_style(fill_style);
+        area.show(ui, |ui| {
+            let recycle: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
+            let draw_count = recycle.draw_count();
+            let len = self.rows.len();
+            self.rows.with_rows(0..len.min(draw_count + 1), |rows| for (i, datum) in rows.iter().enumerate() {
+                let item = self.item_widget(ui, datum, i);
+                ui.layout().add_item(item);
+            });
+            let recycle: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
+            for _ in recycle.total_count()..len { recycle.add_item_empty(); }
+        });
+    }
+
+    fn update_view(&mut self, ui: &mut Ui) {
+        let updates = mem::take(&mut self.updates);
+        let regroup = !updates.is_empty() && self.section_key.is_some() && !self.lid.is_empty();
+        for update in updates {
+            match update {
+                ListUpdate::Push(datum) => self._push(datum, ui),
+                ListUpdate::Remove => self._remove(ui),
+                ListUpdate::Move => {
+                    self.previous_display = 0..0;
+                    self.changed = true;
+                    if let UpdateType::Draw = ui.update_type {} else { ui.context.window.request_redraw(); }
+                }
+                ListUpdate::Reset if self.lid.is_empty() => {}
+                ListUpdate::Reset => {
+                    self.reset_items(ui);
+                    self.previous_display = 0..0;
+                    self.changed = true;
+                }
+            }
+        }
+        if regroup { self.reset_items(ui); }
+    }
+
+    ///补足可见项及分组头的控件，并按数据数及分组数重设滚动范围
+    fn reset_items(&mut self, ui: &mut Ui) {
+        let sections = self.compute_sections();
+        if sections != self.sections {
+            self.sections = sections;
+            self.section_version += 1;
+            self.previous_display = 0..0;
+            self.changed = true;
+        }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let count = recycle.items().len();
+        let missing = self.rows.len().min(recycle.draw_count()).saturating_sub(count);
+        self.rows.with_rows(count..count + missing, |rows| for (index, datum) in rows.iter().enumerate() {
+            let item = self.item_widget(ui, datum, count + index);
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            recycle.add_item(item);
+        });
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let count = recycle.header_items_mut().len();
+        let missing = self.sections.len().min(recycle.draw_count()).saturating_sub(count);
+        let sticky = !self.sections.is_empty() && recycle.sticky_mut().is_none();
+        for _ in 0..missing {
+            let header = self.section_header(ui);
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            recycle.add_header_item(header);
+        }
+        if sticky {
+            let header = self.section_header(ui);
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            recycle.set_sticky(Some(header));
+        }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        recycle.set_headers(self.sections.iter().enumerate().map(|(index, (start, _))| start + index).collect());
+        recycle.set_total_count(self.rows.len() + self.sections.len());
+        let h = recycle.size().rh;
+        area.reset_context_height(h);
+        self.update_index_bar(ui);
+        if let UpdateType::Draw = ui.update_type {} else { ui.context.window.request_redraw(); }
+    }
+
+    ///按分组键将相邻的项分组
+    fn compute_sections(&self) -> Vec<(usize, String)> {
+        let Some(ref key) = self.section_key else { return vec![]; };
+        let mut sections: Vec<(usize, String)> = vec![];
+        self.rows.with_rows(0..self.rows.len(), |rows| for (index, datum) in rows.iter().enumerate() {
+            let title = key(datum);
+            if sections.last().is_some_and(|(_, last)| *last == title) { continue; }
+            sections.push((index, title));
+        });
+        sections
+    }
+
+    ///第index项在布局中的位置，分组头也占用位置
+    fn position(&self, index: usize) -> usize {
+        index + self.sections.partition_point(|(start, _)| *start <= index)
+    }
+
+    fn section_header(&self, ui: &mut Ui) -> LayoutItem {
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        let header = WidgetKind::new(ui, SectionHeader::new(self.width - 18.0, self.section_height));
+        ui.update_type = previous_update;
+        LayoutItem::Widget(header)
+    }
+
+    ///绑定显示范围内的分组头及顶部固定的分组头，已绑定相同分组的跳过
+    fn bind_sections(&mut self, ui: &mut Ui) {
+        if self.sections.is_empty() { return; }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let mut layout = area.layout.take().unwrap();
+        let recycle: &mut RecycleLayout = layout.as_mut_().unwrap();
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        let first = recycle.first_header();
+        for (index, item) in recycle.header_items_mut().iter_mut().enumerate() {
+            let Some((_, title)) = self.sections.get(first + index) else { break; };
+            let header: &mut SectionHeader = item.widget_mut().unwrap();
+            header.bind(ui, (first + index, self.section_version), |ui| (self.section_widget)(ui, title));
+        }
+        if let Some(section) = recycle.sticky_header() && let Some(item) = recycle.sticky_mut() {
+            let header: &mut SectionHeader = item.widget_mut().unwrap();
+            header.bind(ui, (section, self.section_version), |ui| (self.section_widget)(ui, &self.sections[section].1));
+        }
+        ui.update_type = previous_update;
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.layout = Some(layout);
+    }
+
+    ///按分组标题的首字符更新索引栏
+    fn update_index_bar(&mut self, ui: &mut Ui) {
+        if self.bar_id.is_empty() { return; }
+        let mut indices: Vec<(String, usize)> = vec![];
+        for (section, (_, title)) in self.sections.iter().enumerate() {
+            let text = title.chars().next().map(|c| c.to_uppercase().to_string()).unwrap_or_default();
+            if indices.last().is_some_and(|(last, _)| *last == text) { continue; }
+            indices.push((text, section));
+        }
+        let bar: &mut ListIndexBar = ui.layout().get_widget(&self.bar_id).unwrap();
+        if bar.indices() == &indices { return; }
+        let layout = ListIndexBar::index_layout(ui, 16.0, self.height, &indices);
+        let bar: &mut ListIndexBar = ui.layout().get_widget(&self.bar_id).unwrap();
+        bar.set_indices(indices, layout);
+    }
+
+    ///滚动使第section个分组头显示在顶部
+    fn jump_to_section(&mut self, ui: &mut Ui, section: usize) {
+        let Some((start, _)) = self.sections.get(section) else { return; };
+        let position = start + section;
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.scroll_item_to_top(position);
+        ui.context.window.request_redraw();
+    }
+
+    ///重新绑定rows中已显示的项
+    fn rebind_rows(&mut self, ui: &mut Ui, rows: Range<usize>) {
+        if self.previous_display.is_empty() { return; }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let start = recycle.first_item();
+        let items = recycle.items_mut();
+        let bound = start..start + items.len();
+        let rows = rows.start.max(bound.start)..rows.end.min(bound.end);
+        if rows.is_empty() { return; }
+        self.rows.with_rows(rows.clone(), |data| for (item, datum) in items.iter_mut().skip(rows.start - start).zip(data) {
+            let item: &mut ItemWidget = item.widget_mut().unwrap();
+            (self.onscroll)(datum, item.layout());
+        });
+        if let UpdateType::Draw = ui.update_type {} else { ui.context.window.request_redraw(); }
+    }
+
+    ///处理模型的变化，插入或删除影响可见项时重新绑定全部可见项，否则只重新绑定改变的可见项
+    fn update_model(&mut self, ui: &mut Ui) {
+        if self.lid.is_empty() { return; }
+        let count = self.rows.len();
+        let Some(update) = self.rows.update_model(&ui.context.window) else { return; };
+        let rows = match update {
+            ModelUpdate::Changed(changes, applied) => {
+                for change in changes.iter() {
+                    match change {
+                        ModelChange::Inserted(range) => self.selection.insert(range.start, range.len()),
+                        ModelChange::Removed(range) => {
+                            let removed = self.selection.remove_range(range.clone());
+                            if removed { self.notify.borrow_mut().notify(); }
+                        }
+                        _ => {}
+                    }
+                    let moved = matches!(change, ModelChange::Inserted(_) | ModelChange::Removed(_));
+                    if moved && change.affects(&self.previous_display) { self.previous_display = 0..0; }
+                }
+                applied.reads().to_vec()
+            }
+            ModelUpdate::Reset => {
+                self.selection.clear();
+                self.notify.borrow_mut().notify();
+                self.previous_display = 0..0;
+                vec![]
+            }
+        };
+        if self.rows.len() != count || self.section_key.is_some() { self.reset_items(ui); }
+        for rows in rows {
+            self.rebind_rows(ui, rows);
+        }
+        self.hovered = None;
+        self.changed = true;
+    }
+
+    fn update_actions(&mut self, ui: &mut Ui) {
+        let actions = mem::take(&mut *self.actions.write().unwrap());
+        for action in actions {
+            match action {
+                ListAction::Jump(section) if !self.lid.is_empty() => self.jump_to_section(ui, section),
+                ListAction::Jump(_) => {}
+                ListAction::Select(index, modifiers) => {
+                    self.focused = true;
+                    if index < self.rows.len() && self.selection.click(index, modifiers) {
+                        self.notify.borrow_mut().notify();
+                        self.changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    ///按输入的字符查找项，1秒内连续输入时累加查找文本
+    fn search(&mut self, c: char) -> Option<usize> {
+        let item_text = self.item_text.as_ref()?;
+        let now = crate::time_ms();
+        if now - self.search.1 > 1000 { self.search.0.clear(); }
+        self.search.0.extend(c.to_lowercase());
+        self.search.1 = now;
+        let text = self.search.0.as_str();
+        //单个字符时从下一项开始查找，重复输入同一字符可依次跳转
+        let start = match self.selection.current() {
+            Some(current) if text.chars().count() == 1 => current + 1,
+            Some(current) => current,
+            None => 0,
+        };
+        let mut found = None;
+        self.rows.with_rows(0..self.rows.len(), |rows| {
+            let len = rows.len();
+            found = (0..len).map(|i| (start + i) % len).find(|i| item_text(rows[*i]).to_lowercase().starts_with(text));
+        });
+        found
+    }
+
+    ///方向键、PageUp/PageDown、Home/End移动当前项，空格切换选中，字符键按文本查找
+    fn navigate(&mut self, ui: &mut Ui, key: &Key, mut modifiers: KeyModifiers) {
+        if self.rows.is_empty() { return; }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let page = recycle.page_count();
+        let last = self.rows.len() - 1;
+        let current = self.selection.current();
+        let index = match key {
+            Key::UpArrow => current.map_or(0, |x| x.saturating_sub(1)),
+            Key::DownArrow => current.map_or(0, |x| (x + 1).min(last)),
+            Key::PageUp => current.map_or(0, |x| x.saturating_sub(page)),
+            Key::PageDown => current.map_or(0, |x| (x + page).min(last)),
+            Key::Home => 0,
+            Key::End => last,
+            Key::Space => {
+                if self.selection.toggle_current() {
+                    self.notify.borrow_mut().notify();
+                    self.changed = true;
+                }
+                return;
+            }
+            Key::Char(c) => match self.search(*c) {
+                Some(index) => {
+                    //输入大写字母时按下的Shift不用于选择范围
+                    modifiers.shift = false;
+                    index
+                }
+                None => return,
+            },
+            _ => return,
+        };
+        if !self.selection.move_to(index, modifiers) { return; }
+        self.notify.borrow_mut().notify();
+        self.changed = true;
+        let position = self.position(index);
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.scroll_to_item(position);
+        ui.context.window.request_redraw();
+    }
+
+    ///拖动时的影像，与项的显示相同
+    fn ghost_widget(&self, ui: &mut Ui, index: usize) -> LayoutItem {
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(153, 193, 241, 160),
+            border: Border::same(1.0).color(Color::rgb(30, 120, 230)),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        let item_layout = HorizontalLayout::left_to_right().with_size(self.width - 18.0, self.item_height)
+            .with_padding(Padding::same(2.0));
+        let mut item = ItemWidget::new(LayoutKind::new(item_layout), index.to_string()).with_style(style);
+        self.rows.with_row(index, |datum| item.show(ui, |ui| (self.dyn_item_widget)(ui, datum)));
+        let item = WidgetKind::new(ui, item);
+        ui.update_type = previous_update;
+        LayoutItem::Widget(item)
+    }
+
+    ///按鼠标位置更新插入位置及影像位置
+    fn drag_to(&mut self, ui: &mut Ui) {
+        let Some(ref mut drag) = self.drag else { return; };
+        let y = ui.device.device_input.mouse.y();
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let top = area.context_rect().dy().min;
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        recycle.set_floating_y(y - drag.grab);
+        let to = recycle.insert_index(y - top).min(self.rows.len());
+        if to != drag.to {
+            drag.to = to;
+            self.changed = true;
+        }
+        ui.context.window.request_redraw();
+    }
+
+    ///拖动到列表边缘时自动滚动
+    fn auto_scroll(&mut self, ui: &mut Ui) {
+        let Some(ref mut drag) = self.drag else { return; };
+        let now = crate::time_ms();
+        if now - drag.scroll_time < 50 {
+            ui.context.window.request_redraw();
+            return;
+        }
+        let y = ui.device.device_input.mouse.y();
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let rect = area.context_rect();
+        let (top, bottom) = (rect.dy().min, rect.dy().max);
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let display = recycle.display_range().clone();
+        let target = if y < top + 20.0 && display.start > 0 {
+            display.start - 1
+        } else if y > bottom - 20.0 && display.end < self.rows.len() {
+            display.end
+        } else {
+            return;
+        };
+        drag.scroll_time = now;
+        area.scroll_to_item(target);
+        self.drag_to(ui);
+    }
+
+    ///按下项后拖动超过阈值时开始排序，拖动期间不再分发鼠标事件
+    fn update_drag(&mut self, ui: &mut Ui) {
+        match ui.update_type {
+            UpdateType::MousePress => {
+                self.drag = None;
+                let input = &ui.device.device_input;
+                let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+                let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+                let start = recycle.display_range().start;
+                let count = self.rows.len().saturating_sub(start);
+                for (index, item) in recycle.items().iter().take(count).enumerate() {
+                    let item: &ItemWidget = item.widget().unwrap();
+                    if !input.pressed_at(item.rect()) { continue; }
+                    let y = input.mouse.y();
+                    let from = start + index;
+                    self.drag = Some(ListDrag { from, to: from, press_y: y, grab: y - item.rect().dy().min, dragging: false, scroll_time: 0 });
+                    break;
+                }
+            }
+            UpdateType::MouseMove => {
+                let Some(ref mut drag) = self.drag else { return; };
+                if !ui.device.device_input.mouse.pressed() {
+                    self.drag = None;
+                    return;
+                }
+                if !drag.dragging {
+                    if (ui.device.device_input.mouse.y() - drag.press_y).abs() < 5.0 { return; }
+                    drag.dragging = true;
+                    let from = drag.from;
+                    let ghost = self.ghost_widget(ui, from);
+                    let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+                    let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+                    recycle.set_floating(Some(ghost));
+                }
+                //超过拖动阈值后不再将移动事件传给子项
+                ui.update_type = UpdateType::None;
+                self.drag_to(ui);
+            }
+            UpdateType::MouseRelease => {
+                let Some(drag) = self.drag.take() else { return; };
+                if !drag.dragging { return; }
+                ui.update_type = UpdateType::None;
+                let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+                let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+                recycle.set_floating(None);
+                let to = if drag.to > drag.from { drag.to - 1 } else { drag.to };
+                if to != drag.from && self.move_item(drag.from, to) {
+                    self.moves.borrow_mut().push((drag.from, to));
+                    self.reorder_notify.borrow_mut().notify();
+                }
+                self.changed = true;
+                ui.context.window.request_redraw();
+            }
+            UpdateType::Draw if self.drag.as_ref().is_some_and(|drag| drag.dragging) => self.auto_scroll(ui),
+            _ => {}
+        }
+    }
+
+    ///拖动时第index项的插入线，插入位置在可见范围之后时画在最后一个可见项底部
+    fn drop_line(&self, index: usize, last: bool) -> Option<bool> {
+        let drag = self.drag.as_ref().filter(|drag| drag.dragging)?;
+        if drag.to == index { return Some(false); }
+        if last && drag.to > index { return Some(true); }
+        None
+    }
+
+    pub fn update(&mut self, ui: &mut Ui) {
+        if self.reorderable && !self.lid.is_empty() && self.sections.is_empty() { self.update_drag(ui); }
+        match ui.update_type {
+            UpdateType::MousePress => self.focused = false,
+            UpdateType::KeyRelease(ref key) if self.focused && !self.lid.is_empty() => {
+                let key = key.clone();
+                let modifiers = ui.device.device_input.key_modifiers();
+                self.navigate(ui, &key, modifiers);
+            }
+            _ => {}
+        }
+        self.update_actions(ui);
+        self.update_model(ui);
+        self.update_view(ui);
+        match ui.update_type {
+            UpdateType::Draw => {
+                let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+                let recycle_layout: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+                let display = recycle_layout.display_range().clone();
+                //显示范围内第一个项的数据索引，分组头不占用数据索引
+                let first = recycle_layout.first_item();
+                let scrolled = display != self.previous_display;
+                if scrolled {
+                    let mut start = self.previous_display.start - recycle_layout.headers().partition_point(|x| *x < self.previous_display.start);
+                    recycle_layout.items_mut().iter_mut().for_each(|x| {
+                        let item: &mut ItemWidget = x.widget_mut().unwrap();
+                        if item.state().hovered { self.hovered = Some(start); }
+                        start += 1;
+                    });
+                    let items = recycle_layout.items_mut();
+                    self.rows.with_rows(first..first + items.len(), |rows| for (index, (item, datum)) in items.iter_mut().zip(rows).enumerate() {
+                        let item: &mut ItemWidget = item.widget_mut().unwrap();
+                        let start = first + index;
+                        item.restore_status(self.hovered == Some(start), start.to_string());
+                        (self.onscroll)(datum, item.layout());
+                    });
+                    self.previous_display = display.clone();
+                } else if !self.changed {
+                    return;
+                }
+                let count = recycle_layout.items().len().min(self.rows.len().saturating_sub(first));
+                for (index, item) in recycle_layout.items_mut().iter_mut().take(count).enumerate() {
+                    let item: &mut ItemWidget = item.widget_mut().unwrap();
+                    item.set_selected(self.selection.is_selected(first + index));
+                    item.set_drop_line(self.drop_line(first + index, index + 1 == count));
+                }
+                if scrolled { self.bind_sections(ui); }
+                self.changed = false;
+            }
+            _ => if self.changed { ui.context.window.request_redraw(); }
+        }
+        // for update in mem::take(&mut self.updates) {
+        //     match update {
+        //         ListUpdate::Push(datum) => self._push(datum, ui),
+        //         ListUpdate::Remove(wid) => self._remove(wid, ui)
+        //     }
+        // }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use crate::frame::context::UpdateType;
+use crate::render::{Visual, VisualStyle, WidgetStyle};
+use crate::response::Response;
+use crate::size::Geometry;
+use crate::ui::Ui;
+use crate::widgets::{Widget, WidgetSize, WidgetState};
+use crate::*;
+
+///列表的分组头，绑定分组的标题
+pub(crate) struct SectionHeader {
+    id: String,
+    visual: Visual,
+    layout: Option<LayoutKind>,
+    bound: Option<(usize, usize)>, //已绑定的分组及版本
+    geometry: Geometry,
+    state: WidgetState,
+}
+
+impl SectionHeader {
+    pub fn new(width: f32, height: f32) -> SectionHeader {
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(238, 240, 243),
+            border: Border::same(0.0),
+            radius: Radius::same(0),
+            shadow: Shadow::new(),
+        });
+        SectionHeader {
+            id: gen_unique_id(),
+            visual: Visual::new().with_enable().with_style(style).with_size(width, height),
+            layout: None,
+            bound: None,
+            geometry: Geometry::new().with_context_size(width, height),
+            state: WidgetState::default(),
+        }
+    }
+
+    ///绑定分组，bound为分组及版本，与已绑定的不同时才重新创建分组头的内容，content绘制分组标题
+    pub fn bind(&mut self, ui: &mut Ui, bound: (usize, usize), content: impl FnOnce(&mut Ui)) {
+        if self.bound == Some(bound) { return; }
+        self.bound = Some(bound);
+        let layout = HorizontalLayout::left_to_right().with_size(self.geometry.context_width(), self.geometry.context_height())
+            .with_padding(Padding::same(2.0).left(8.0));
+        let previous_layout = ui.layout.replace(LayoutKind::new(layout)).unwrap();
+        content(ui);
+        self.layout = ui.layout.replace(previous_layout);
+    }
+}
+
+impl Widget for SectionHeader {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            #[cfg(feature = "gpu")]
+            UpdateType::ReInit => self.visual.re_init(),
+            UpdateType::Draw => {
+                self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+                self.visual.draw(ui, false, false, false, false);
+            }
+            _ => {}
+        }
+        if let Some(layout) = self.layout.as_mut() { layout.update(ui); }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -0,0 +1,168 @@
+use crate::key::KeyModifiers;
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+///列表的选择模式
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ListSelectionMode {
+    ///单选
+    #[default]
+    Single,
+    ///多选，点击切换选中
+    Multi,
+    ///扩展选择，Ctrl+点击切换选中，Shift+点击或Shift+方向键选择范围
+    Extended,
+}
+
+pub(crate) struct ListSelection {
+    mode: ListSelectionMode,
+    indices: BTreeSet<usize>,
+    current: Option<usize>,
+    anchor: Option<usize>, //Shift选择范围的起点
+}
+
+impl ListSelection {
+    pub fn new() -> Self {
+        ListSelection {
+            mode: ListSelectionMode::Single,
+            indices: BTreeSet::new(),
+            current: None,
+            anchor: None,
+        }
+    }
+
+    pub fn mode(&self) -> ListSelectionMode {
+        self.mode
+    }
+
+    ///切换模式时清空选择
+    pub fn set_mode(&mut self, mode: ListSelectionMode) {
+        self.mode = mode;
+        self.clear();
+    }
+
+    pub fn indices(&self) -> &BTreeSet<usize> {
+        &self.indices
+    }
+
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn clear(&mut self) {
+        self.indices.clear();
+        self.current = None;
+        self.anchor = None;
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.indices.contains(&index)
+    }
+
+    ///选中给定的索引，单选模式下只保留最后一个
+    pub fn set(&mut self, indices: &[usize]) {
+        self.indices.clear();
+        match self.mode {
+            ListSelectionMode::Single => self.indices.extend(indices.last().cloned()),
+            _ => self.indices.extend(indices.iter().cloned()),
+        }
+        self.current = indices.last().cloned();
+        self.anchor = self.current;
+    }
+
+    ///点击第index项，选择改变时返回true
+    pub fn click(&mut self, index: usize, modifiers: KeyModifiers) -> bool {
+        let previous = (self.indices.clone(), self.current);
+        match self.mode {
+            ListSelectionMode::Multi => {
+                if !self.indices.remove(&index) { self.indices.insert(index); }
+                self.anchor = Some(index);
+            }
+            ListSelectionMode::Extended if modifiers.shift => self.select_range(index, modifiers.ctrl),
+            ListSelectionMode::Extended if modifiers.ctrl => {
+                if !self.indices.remove(&index) { self.indices.insert(index); }
+                self.anchor = Some(index);
+            }
+            _ => {
+                self.indices.clear();
+                self.indices.insert(index);
+                self.anchor = Some(index);
+            }
+        }
+        self.current = Some(index);
+        previous != (self.indices.clone(), self.current)
+    }
+
+    ///选择从起点到index的范围，keep为true时保留已有的选择
+    fn select_range(&mut self, index: usize, keep: bool) {
+        let anchor = self.anchor.unwrap_or(index);
+        if !keep { self.indices.clear(); }
+        self.indices.extend(anchor.min(index)..=anchor.max(index));
+    }
+
+    ///键盘移动到第index项，多选模式下只移动当前项，扩展模式下按住Shift选择范围
+    pub fn move_to(&mut self, index: usize, modifiers: KeyModifiers) -> bool {
+        if self.current == Some(index) { return false; }
+        match self.mode {
+            ListSelectionMode::Multi => self.anchor = Some(index),
+            ListSelectionMode::Extended if modifiers.shift => {
+                self.anchor = self.anchor.or(self.current);
+                self.select_range(index, false);
+            }
+            _ => {
+                self.indices.clear();
+                self.indices.insert(index);
+                self.anchor = Some(index);
+            }
+        }
+        self.current = Some(index);
+        true
+    }
+
+    ///空格切换当前项的选中，单选模式下忽略
+    pub fn toggle_current(&mut self) -> bool {
+        let Some(current) = self.current else { return false; };
+        if self.mode == ListSelectionMode::Single { return false; }
+        if !self.indices.remove(&current) { self.indices.insert(current); }
+        true
+    }
+
+    ///第from项移动到第to项后调整索引
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let map = |x: usize| if x == from {
+            to
+        } else if from < to && from < x && x <= to {
+            x - 1
+        } else if to < from && to <= x && x < from {
+            x + 1
+        } else {
+            x
+        };
+        self.indices = self.indices.iter().map(|x| map(*x)).collect();
+        self.current = self.current.map(map);
+        self.anchor = self.anchor.map(map);
+    }
+
+    ///删除第index项后调整后续索引
+    pub fn remove(&mut self, index: usize) {
+        self.remove_range(index..index + 1);
+    }
+
+    ///删除range中的项后调整后续索引，删除了选中项或当前项时返回true
+    pub fn remove_range(&mut self, range: Range<usize>) -> bool {
+        let removed = self.indices.range(range.clone()).next().is_some() || self.current.is_some_and(|x| range.contains(&x));
+        let shift = |x: usize| if x >= range.end { x - range.len() } else { x };
+        self.indices = self.indices.iter().filter(|x| !range.contains(x)).map(|x| shift(*x)).collect();
+        self.current = self.current.filter(|x| !range.contains(x)).map(shift);
+        self.anchor = self.anchor.filter(|x| !range.contains(x)).map(shift);
+        removed
+    }
+
+    ///在index处插入count项后调整后续索引
+    pub fn insert(&mut self, index: usize, count: usize) {
+        let shift = |x: usize| if x >= index { x + count } else { x };
+        self.indices = self.indices.iter().map(|x| shift(*x)).collect();
+        self.current = self.current.map(shift);
+        self.anchor = self.anchor.map(shift);
+    }
+}
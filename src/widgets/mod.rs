@@ -5,6 +5,9 @@ use std::any::{Any, TypeId};
 use std::ops::{BitAnd, BitOr, BitOrAssign, Deref, DerefMut};
 use crate::size::Geometry;
 use crate::UpdateType;
+use crate::size::rect::Rect;
+use crate::text::rich::RichText;
+use crate::widgets::tooltip::TooltipOwner;
 
 pub mod label;
 pub mod button;
@@ -25,6 +28,13 @@ pub mod triangle;
 pub mod circle;
 pub mod table;
 pub mod combo;
+pub mod tree;
+pub mod model;
+pub mod calendar;
+pub mod color;
+pub mod tooltip;
+pub mod toast;
+pub mod toolbar;
 
 pub mod tab;
 
@@ -97,22 +107,37 @@ pub struct WidgetKind {
     width: f32,
     height: f32,
     change: WidgetChange,
+    ///上次绘制时的区域，用于悬停提示
+    rect: Rect,
+    tooltip: Option<TooltipOwner>,
 }
 
 impl WidgetKind {
     pub fn new(ui: &mut Ui, mut widget: impl Widget) -> Self {
+        let tooltip = widget.state().tooltip.take();
         let resp = widget.update(ui);
+        let tooltip = tooltip.map(|tooltip| ui.context.tooltips.set_owned(resp.id.to_string(), tooltip));
         WidgetKind {
             id: resp.id.to_string(),
             width: resp.size.dw,
             height: resp.size.dh,
             widget: Box::new(widget),
             change: WidgetChange::None,
+            rect: Rect::new(),
+            tooltip,
         }
     }
 
     pub fn update(&mut self, ui: &mut Ui) -> Response<'_> {
         ui.widget_changed = WidgetChange::Position;
+        if let UpdateType::Draw = ui.update_type {
+            self.rect.set_size(self.width, self.height);
+            self.rect.offset_to_rect(&ui.draw_rect);
+        }
+        if let UpdateType::MouseMove = ui.update_type && ui.context.tooltips.contains(&self.id) &&
+            (ui.device.device_input.hovered_at(&self.rect) || self.widget.state().hovered) {
+            ui.context.tooltips.hovered(&self.id);
+        }
         let resp = self.widget.update(ui);
         if resp.size.dw != self.width || resp.size.dh != self.height {
             self.id = resp.id.to_string();
@@ -188,6 +213,8 @@ pub struct WidgetState {
     pressed: bool,
     changed: bool,
     pub(crate) disabled: bool,
+    ///通过WidgetExt::tooltip设置，添加控件时移到Ui中
+    pub(crate) tooltip: Option<RichText>,
     // selected: bool,
 }
 
@@ -0,0 +1,620 @@
+use crate::widgets::table::TableExt;
+use crate::window::WindowType;
+use std::ops::Range;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+///模型数据的变化，范围为变化后(删除时为变化前)的行索引
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModelChange {
+    ///在range.start处插入range.len()行
+    Inserted(Range<usize>),
+    ///删除range中的行
+    Removed(Range<usize>),
+    ///range中的行数据改变
+    Changed(Range<usize>),
+    ///全部数据改变，视图重新读取所有行
+    Reset,
+}
+
+impl ModelChange {
+    ///变化是否影响display范围内已显示的项
+    pub(crate) fn affects(&self, display: &Range<usize>) -> bool {
+        match self {
+            ModelChange::Inserted(range) | ModelChange::Removed(range) => range.start < display.end,
+            ModelChange::Changed(range) => range.start < display.end && display.start < range.end,
+            ModelChange::Reset => true,
+        }
+    }
+}
+
+///模型的变化通知，可克隆到后台线程中发送，发送后唤醒视图所在的窗口
+#[derive(Clone, Default)]
+pub struct ModelNotifier {
+    changes: Arc<Mutex<Vec<ModelChange>>>,
+    window: Arc<RwLock<Option<Arc<WindowType>>>>,
+}
+
+impl ModelNotifier {
+    pub fn new() -> Self {
+        ModelNotifier::default()
+    }
+
+    ///锁定变化队列，在其它线程中修改数据时应在锁定期间修改并记录变化，
+    ///视图处理变化时也会锁定，保证读取到的数据与变化一致
+    pub fn lock(&self) -> ModelChanges<'_> {
+        ModelChanges {
+            changes: self.changes.lock().unwrap(),
+            window: &self.window,
+            notified: false,
+        }
+    }
+
+    pub fn inserted(&self, rows: Range<usize>) {
+        self.lock().inserted(rows);
+    }
+
+    pub fn removed(&self, rows: Range<usize>) {
+        self.lock().removed(rows);
+    }
+
+    pub fn changed(&self, rows: Range<usize>) {
+        self.lock().changed(rows);
+    }
+
+    pub fn reset(&self) {
+        self.lock().reset();
+    }
+
+    ///视图更新时设置，变化时唤醒该窗口
+    pub(crate) fn set_window(&self, window: &Arc<WindowType>) {
+        if self.window.read().unwrap().as_ref().is_some_and(|x| Arc::ptr_eq(x, window)) { return; }
+        *self.window.write().unwrap() = Some(window.clone());
+    }
+}
+
+///锁定的变化队列，释放时唤醒视图所在的窗口
+pub struct ModelChanges<'a> {
+    changes: MutexGuard<'a, Vec<ModelChange>>,
+    window: &'a RwLock<Option<Arc<WindowType>>>,
+    notified: bool,
+}
+
+impl ModelChanges<'_> {
+    pub fn inserted(&mut self, rows: Range<usize>) {
+        if rows.is_empty() { return; }
+        self.push(ModelChange::Inserted(rows));
+    }
+
+    pub fn removed(&mut self, rows: Range<usize>) {
+        if rows.is_empty() { return; }
+        self.push(ModelChange::Removed(rows));
+    }
+
+    pub fn changed(&mut self, rows: Range<usize>) {
+        if rows.is_empty() { return; }
+        self.push(ModelChange::Changed(rows));
+    }
+
+    pub fn reset(&mut self) {
+        self.push(ModelChange::Reset);
+    }
+
+    ///记录变化，视图在下一次App::update时处理
+    pub fn push(&mut self, change: ModelChange) {
+        self.changes.push(change);
+        self.notified = true;
+    }
+
+    ///取出自上次调用以来的变化
+    pub(crate) fn take(&mut self) -> Vec<ModelChange> {
+        mem::take(&mut *self.changes)
+    }
+}
+
+impl Drop for ModelChanges<'_> {
+    fn drop(&mut self) {
+        if !self.notified { return; }
+        if let Some(ref window) = *self.window.read().unwrap() { window.request_update(); }
+    }
+}
+
+///列表数据模型，视图通过notifier获取变化，显示时只读取可见的行
+pub trait ListModel<T> {
+    ///行数
+    fn row_count(&self) -> usize;
+
+    ///读取range中的行，f的参数为各行数据，超出行数的部分不读取。
+    ///f在读取期间调用一次，调用期间数据不应改变，VecModel在读锁内调用f，f中不能修改该模型
+    fn with_rows(&self, range: Range<usize>, f: &mut dyn FnMut(&[&T]));
+
+    ///变化通知，数据改变后需调用对应的方法
+    fn notifier(&self) -> &ModelNotifier;
+
+    ///视图添加数据时调用，在row处插入，默认不支持并返回false
+    fn insert_row(&self, row: usize, datum: T) -> bool {
+        let _ = (row, datum);
+        false
+    }
+
+    ///视图删除数据时调用，默认不支持并返回None
+    fn remove_row(&self, row: usize) -> Option<T> {
+        let _ = row;
+        None
+    }
+
+    ///视图拖动排序时调用，将第from行移动到第to行，应通知两者之间的行为changed，
+    ///默认不支持并返回false
+    fn move_row(&self, from: usize, to: usize) -> bool {
+        let _ = (from, to);
+        false
+    }
+
+    ///视图编辑数据时调用，f返回是否修改，默认不支持并返回false。VecModel在写锁内调用f
+    fn update_row(&self, row: usize, f: &mut dyn FnMut(&mut T) -> bool) -> bool {
+        let _ = (row, f);
+        false
+    }
+}
+
+///表格数据模型，各列的数据由TableExt提供
+pub trait TableModel<T: TableExt>: ListModel<T> {
+    ///表格编辑提交后调用，通过update_row写入第column列
+    fn set_col(&self, row: usize, column: usize, value: &str) -> bool {
+        self.update_row(row, &mut |datum| datum.set_col(column, value))
+    }
+}
+
+impl<T: TableExt, M: ListModel<T>> TableModel<T> for M {}
+
+/// # 基于Vec的模型，可克隆后在其它线程中修改数据
+/// ```
+/// use xlui::*;
+///
+/// let model = VecModel::new(vec![1, 2, 3]);
+/// let handle = model.clone();
+/// std::thread::spawn(move || {
+///     handle.extend(4..100);
+///     handle.set(0, 10);
+/// }).join().unwrap();
+/// assert_eq!(model.len(), 99);
+/// assert_eq!(model.get(0), Some(10));
+/// //作为ListView或TableView的数据
+/// let mut list_view = ListView::new(vec![]);
+/// list_view.set_model(model);
+/// ```
+pub struct VecModel<T> {
+    data: Arc<RwLock<Vec<T>>>,
+    notifier: ModelNotifier,
+}
+
+impl<T> Clone for VecModel<T> {
+    fn clone(&self) -> Self {
+        VecModel {
+            data: self.data.clone(),
+            notifier: self.notifier.clone(),
+        }
+    }
+}
+
+impl<T> VecModel<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        VecModel {
+            data: Arc::new(RwLock::new(data)),
+            notifier: ModelNotifier::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.read().unwrap().is_empty()
+    }
+
+    pub fn push(&self, datum: T) {
+        let mut changes = self.notifier.lock();
+        let mut data = self.data.write().unwrap();
+        data.push(datum);
+        changes.inserted(data.len() - 1..data.len());
+    }
+
+    pub fn extend(&self, items: impl IntoIterator<Item=T>) {
+        let mut changes = self.notifier.lock();
+        let mut data = self.data.write().unwrap();
+        let start = data.len();
+        data.extend(items);
+        changes.inserted(start..data.len());
+    }
+
+    pub fn insert(&self, index: usize, datum: T) {
+        let mut changes = self.notifier.lock();
+        self.data.write().unwrap().insert(index, datum);
+        changes.inserted(index..index + 1);
+    }
+
+    pub fn remove(&self, index: usize) -> T {
+        let mut changes = self.notifier.lock();
+        let datum = self.data.write().unwrap().remove(index);
+        changes.removed(index..index + 1);
+        datum
+    }
+
+    pub fn set(&self, index: usize, datum: T) {
+        let mut changes = self.notifier.lock();
+        self.data.write().unwrap()[index] = datum;
+        changes.changed(index..index + 1);
+    }
+
+    ///修改第index行。f在锁定变化队列及数据期间调用，f中不能再访问该模型(如len、get)，否则死锁，
+    ///需要模型中的其它值时应先在外部读取，或计算出新值后调用set
+    pub fn update(&self, index: usize, f: impl FnOnce(&mut T)) {
+        let mut changes = self.notifier.lock();
+        f(&mut self.data.write().unwrap()[index]);
+        changes.changed(index..index + 1);
+    }
+
+    pub fn clear(&self) {
+        let mut changes = self.notifier.lock();
+        let mut data = self.data.write().unwrap();
+        let len = data.len();
+        data.clear();
+        changes.removed(0..len);
+    }
+
+    ///替换全部数据
+    pub fn reset(&self, data: Vec<T>) {
+        let mut changes = self.notifier.lock();
+        *self.data.write().unwrap() = data;
+        changes.reset();
+    }
+}
+
+impl<T: Clone> VecModel<T> {
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.data.read().unwrap().get(index).cloned()
+    }
+}
+
+impl<T> ListModel<T> for VecModel<T> {
+    fn row_count(&self) -> usize {
+        self.len()
+    }
+
+    fn with_rows(&self, range: Range<usize>, f: &mut dyn FnMut(&[&T])) {
+        let data = self.data.read().unwrap();
+        let end = range.end.min(data.len());
+        let rows: Vec<&T> = data[range.start.min(end)..end].iter().collect();
+        f(&rows);
+    }
+
+    fn notifier(&self) -> &ModelNotifier {
+        &self.notifier
+    }
+
+    fn insert_row(&self, row: usize, datum: T) -> bool {
+        if row > self.len() { return false; }
+        self.insert(row, datum);
+        true
+    }
+
+    fn remove_row(&self, row: usize) -> Option<T> {
+        if row >= self.len() { return None; }
+        Some(self.remove(row))
+    }
+
+    fn move_row(&self, from: usize, to: usize) -> bool {
+        let mut changes = self.notifier.lock();
+        let mut data = self.data.write().unwrap();
+        if from >= data.len() || to >= data.len() { return false; }
+        let datum = data.remove(from);
+        data.insert(to, datum);
+        changes.changed(from.min(to)..from.max(to) + 1);
+        true
+    }
+
+    fn update_row(&self, row: usize, f: &mut dyn FnMut(&mut T) -> bool) -> bool {
+        let mut changes = self.notifier.lock();
+        let mut data = self.data.write().unwrap();
+        let Some(datum) = data.get_mut(row) else { return false; };
+        if !f(datum) { return false; }
+        changes.changed(row..row + 1);
+        true
+    }
+}
+
+///一批变化后的行，Old为保留的原行，New为需从模型读取的行数
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Old(Range<usize>),
+    New(usize),
+}
+
+impl Segment {
+    fn len(&self) -> usize {
+        match self {
+            Segment::Old(range) => range.len(),
+            Segment::New(count) => *count,
+        }
+    }
+}
+
+///按变化后的行索引切分的片段，相邻的同类片段会合并
+struct Segments(Vec<Segment>);
+
+impl Segments {
+    fn new(len: usize) -> Self {
+        Segments(if len == 0 { vec![] } else { vec![Segment::Old(0..len)] })
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|x| x.len()).sum()
+    }
+
+    ///各片段变化后的起始索引
+    fn starts(&self) -> Vec<usize> {
+        self.0.iter().scan(0, |start, segment| {
+            let current = *start;
+            *start += segment.len();
+            Some(current)
+        }).collect()
+    }
+
+    ///在行索引index处切分，返回切分后右侧片段的位置，超出总行数时返回None
+    fn split(&mut self, index: usize) -> Option<usize> {
+        let mut start = 0;
+        for i in 0..self.0.len() {
+            let len = self.0[i].len();
+            if index == start { return Some(i); }
+            if index < start + len {
+                let offset = index - start;
+                let right = match self.0[i] {
+                    Segment::Old(ref mut range) => {
+                        let right = Segment::Old(range.start + offset..range.end);
+                        range.end = range.start + offset;
+                        right
+                    }
+                    Segment::New(ref mut count) => {
+                        *count = offset;
+                        Segment::New(len - offset)
+                    }
+                };
+                self.0.insert(i + 1, right);
+                return Some(i + 1);
+            }
+            start += len;
+        }
+        (index == start).then_some(self.0.len())
+    }
+
+    ///用segment替换range中的行
+    fn replace(&mut self, range: Range<usize>, segment: Option<Segment>) -> Option<()> {
+        let start = self.split(range.start)?;
+        let end = self.split(range.end)?;
+        self.0.splice(start..end, segment);
+        self.merge();
+        Some(())
+    }
+
+    fn merge(&mut self) {
+        let mut merged: Vec<Segment> = Vec::with_capacity(self.0.len());
+        for segment in self.0.drain(..) {
+            match (merged.last_mut(), segment) {
+                (_, segment) if segment.len() == 0 => {}
+                (Some(Segment::New(count)), Segment::New(other)) => *count += other,
+                (Some(Segment::Old(range)), Segment::Old(other)) if range.end == other.start => range.end = other.end,
+                (_, segment) => merged.push(segment),
+            }
+        }
+        self.0 = merged;
+    }
+}
+
+///一批变化的行索引映射，用于把原行索引映射到变化后的索引
+pub(crate) struct AppliedChanges {
+    olds: Vec<(Range<usize>, usize)>, //保留的原行范围及其变化后的起始索引
+    reads: Vec<Range<usize>>,
+    len: usize,
+}
+
+impl AppliedChanges {
+    ///需从模型重新读取的行范围，为变化后的索引
+    pub fn reads(&self) -> &Vec<Range<usize>> {
+        &self.reads
+    }
+
+    ///变化后的行数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///原行索引在变化后的索引，行被删除或改变时返回None
+    pub fn map(&self, index: usize) -> Option<usize> {
+        let position = self.olds.partition_point(|(range, _)| range.end <= index);
+        let (range, start) = self.olds.get(position)?;
+        range.contains(&index).then(|| start + index - range.start)
+    }
+}
+
+///计算len行数据经过一批变化后的索引映射，插入及改变的行在最终位置重新读取，
+///变化超出行数或包含Reset时返回None
+pub(crate) fn map_changes(len: usize, changes: &[ModelChange]) -> Option<AppliedChanges> {
+    let mut segments = Segments::new(len);
+    for change in changes {
+        match change {
+            ModelChange::Inserted(range) => segments.replace(range.start..range.start, Some(Segment::New(range.len())))?,
+            ModelChange::Removed(range) => segments.replace(range.clone(), None)?,
+            ModelChange::Changed(range) => segments.replace(range.clone(), Some(Segment::New(range.len())))?,
+            ModelChange::Reset => return None,
+        }
+    }
+    let mut applied = AppliedChanges { olds: vec![], reads: vec![], len: segments.len() };
+    for (segment, start) in segments.0.iter().zip(segments.starts()) {
+        match segment {
+            Segment::Old(range) => applied.olds.push((range.clone(), start)),
+            Segment::New(count) => applied.reads.push(start..start + count),
+        }
+    }
+    Some(applied)
+}
+
+///模型变化的处理结果
+pub(crate) enum ModelUpdate {
+    ///变化及其索引映射
+    Changed(Vec<ModelChange>, AppliedChanges),
+    ///变化与模型不一致或全部数据改变，视图需重新读取
+    Reset,
+}
+
+///视图的数据，未设置模型时保存在视图中，设置模型后只记录已处理的行数，显示时从模型读取
+pub(crate) struct ViewRows<T, M: ?Sized> {
+    data: Vec<T>,
+    model: Option<Box<M>>,
+    count: usize,
+}
+
+impl<T, M: ListModel<T> + ?Sized> ViewRows<T, M> {
+    pub fn new(data: Vec<T>) -> Self {
+        ViewRows { data, model: None, count: 0 }
+    }
+
+    ///行数，设置模型时为已处理变化后的行数
+    pub fn len(&self) -> usize {
+        if self.model.is_some() { self.count } else { self.data.len() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn model(&self) -> Option<&M> {
+        self.model.as_deref()
+    }
+
+    ///视图保存的数据，设置模型时为空
+    pub fn data(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut Vec<T> {
+        &mut self.data
+    }
+
+    ///替换数据并移除模型
+    pub fn set_data(&mut self, data: Vec<T>) {
+        self.data = data;
+        self.model = None;
+    }
+
+    ///使用模型作为数据，丢弃模型中未处理的变化
+    pub fn set_model(&mut self, model: Box<M>) {
+        let mut locked = model.notifier().lock();
+        locked.take();
+        self.count = model.row_count();
+        drop(locked);
+        self.data.clear();
+        self.model = Some(model);
+    }
+
+    ///读取range中的行，超出行数的部分不读取
+    pub fn with_rows(&self, range: Range<usize>, mut f: impl FnMut(&[&T])) {
+        match self.model {
+            Some(ref model) => model.with_rows(range, &mut f),
+            None => {
+                let end = range.end.min(self.data.len());
+                let rows: Vec<&T> = self.data[range.start.min(end)..end].iter().collect();
+                f(&rows);
+            }
+        }
+    }
+
+    ///读取第row行，行不存在时不调用f
+    pub fn with_row(&self, row: usize, mut f: impl FnMut(&T)) {
+        self.with_rows(row..row + 1, |rows| if let Some(datum) = rows.first() { f(datum) });
+    }
+
+    ///取出模型的变化并更新行数，未设置模型或没有变化时返回None
+    pub fn update_model(&mut self, window: &Arc<WindowType>) -> Option<ModelUpdate> {
+        let model = self.model.as_ref()?;
+        model.notifier().set_window(window);
+        let mut locked = model.notifier().lock();
+        let changes = locked.take();
+        if changes.is_empty() { return None; }
+        //变化与数据在锁定期间一致
+        let count = model.row_count();
+        drop(locked);
+        let applied = map_changes(self.count, &changes).filter(|applied| applied.len() == count);
+        self.count = count;
+        Some(match applied {
+            Some(applied) => ModelUpdate::Changed(changes, applied),
+            None => ModelUpdate::Reset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{map_changes, ModelChange, Segment, Segments};
+
+    ///逐行模拟变化，Some为原行索引，None为需重新读取的行
+    fn simulate(len: usize, changes: &[ModelChange]) -> Vec<Option<usize>> {
+        let mut rows: Vec<Option<usize>> = (0..len).map(Some).collect();
+        for change in changes {
+            match change {
+                ModelChange::Inserted(range) => { rows.splice(range.start..range.start, range.clone().map(|_| None)); }
+                ModelChange::Removed(range) => { rows.drain(range.clone()); }
+                ModelChange::Changed(range) => rows[range.clone()].iter_mut().for_each(|x| *x = None),
+                ModelChange::Reset => unreachable!(),
+            }
+        }
+        rows
+    }
+
+    fn assert_mapped(len: usize, changes: &[ModelChange]) {
+        let rows = simulate(len, changes);
+        let applied = map_changes(len, changes).unwrap();
+        assert_eq!(applied.len(), rows.len());
+        for old in 0..len {
+            assert_eq!(applied.map(old), rows.iter().position(|x| *x == Some(old)), "{:?}", changes);
+        }
+        let reads: Vec<usize> = applied.reads().iter().flat_map(|x| x.clone()).collect();
+        let expected: Vec<usize> = rows.iter().enumerate().filter(|(_, x)| x.is_none()).map(|(i, _)| i).collect();
+        assert_eq!(reads, expected);
+    }
+
+    #[test]
+    fn map_changes_follows_rows() {
+        assert_mapped(10, &[]);
+        assert_mapped(10, &[ModelChange::Inserted(0..2)]);
+        assert_mapped(10, &[ModelChange::Removed(3..5), ModelChange::Inserted(3..4)]);
+        assert_mapped(10, &[ModelChange::Changed(2..4), ModelChange::Removed(1..3), ModelChange::Inserted(8..11)]);
+        assert_mapped(5, &[ModelChange::Removed(0..5), ModelChange::Inserted(0..3), ModelChange::Changed(1..2)]);
+        assert_mapped(0, &[ModelChange::Inserted(0..4), ModelChange::Removed(1..2)]);
+        //插入后删除插入的行，不需要读取
+        assert_mapped(6, &[ModelChange::Inserted(2..4), ModelChange::Removed(2..4), ModelChange::Changed(5..6)]);
+    }
+
+    #[test]
+    fn map_changes_rejects_out_of_range_and_reset() {
+        assert!(map_changes(3, &[ModelChange::Removed(2..4)]).is_none());
+        assert!(map_changes(3, &[ModelChange::Inserted(4..5)]).is_none());
+        assert!(map_changes(3, &[ModelChange::Changed(0..1), ModelChange::Reset]).is_none());
+        assert!(map_changes(3, &[ModelChange::Inserted(3..4), ModelChange::Changed(3..4)]).is_some());
+    }
+
+    #[test]
+    fn segments_merge_adjacent() {
+        let mut segments = Segments::new(6);
+        segments.replace(2..2, Some(Segment::New(1))).unwrap();
+        segments.replace(3..3, Some(Segment::New(2))).unwrap();
+        segments.replace(2..5, None).unwrap();
+        assert!(matches!(segments.0.as_slice(), [Segment::Old(x)] if *x == (0..6)));
+        segments.replace(1..3, Some(Segment::New(2))).unwrap();
+        segments.replace(3..4, Some(Segment::New(1))).unwrap();
+        assert_eq!(segments.0.len(), 3);
+        assert_eq!(segments.len(), 6);
+        assert_eq!(segments.starts(), vec![0, 1, 4]);
+        assert!(segments.replace(5..7, None).is_none());
+    }
+}
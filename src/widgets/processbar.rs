@@ -74,6 +74,13 @@ impl ProcessBar {
         self
     }
 
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.visual.rect_mut().set_size(width, height);
+        self.process_render.rect_mut().set_size(width, height);
+        self.geometry.set_context_size(width, height);
+        self
+    }
+
     pub fn with_range(mut self, r: Range<f32>) -> Self {
         self.range = r;
         self
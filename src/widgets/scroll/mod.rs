@@ -13,7 +13,7 @@ use crate::style::color::Color;
 use crate::ui::Ui;
 use crate::widgets::scroll::bar::ScrollBar;
 use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
-use crate::{Offset, Shadow, VerticalLayout};
+use crate::{Offset, RecycleLayout, Rect, Shadow, VerticalLayout};
 use std::mem;
 
 pub struct ScrollWidget {
@@ -134,6 +134,30 @@ impl ScrollWidget {
         self.bar_offset(0.0, 0.0);
     }
 
+    pub fn reset_context_width(&mut self, w: f32) {
+        self.h_bar.set_context_width(w);
+        self.bar_offset(0.0, 0.0);
+    }
+
+    ///内容区域(不含边距)
+    pub(crate) fn context_rect(&self) -> Rect {
+        self.geometry.context_rect()
+    }
+
+    ///滚动RecycleLayout使第index项可见
+    pub(crate) fn scroll_to_item(&mut self, index: usize) {
+        let Some(recycle) = self.layout.as_mut().and_then(|layout| layout.as_mut_::<RecycleLayout>()) else { return; };
+        let oy = recycle.scroll_delta(index);
+        if oy != 0.0 { self.bar_offset(0.0, oy); }
+    }
+
+    ///滚动RecycleLayout使第index项显示在顶部
+    pub(crate) fn scroll_item_to_top(&mut self, index: usize) {
+        let Some(recycle) = self.layout.as_mut().and_then(|layout| layout.as_mut_::<RecycleLayout>()) else { return; };
+        let oy = recycle.top_delta(index);
+        if oy != 0.0 { self.bar_offset(0.0, oy); }
+    }
+
     fn bar_offset(&mut self, ox: f32, oy: f32) {
         println!("offset {} {}", ox, oy);
         let roy = self.v_bar.set_vbar_value_by_offset(-oy);
@@ -223,6 +247,11 @@ impl Widget for ScrollWidget {
                 rect.add_max_x(-self.v_bar.geometry().context_width() - self.geometry.padding().right);
                 rect.add_max_y(-self.h_bar.geometry().context_height() - self.geometry.padding().bottom);
                 if ui.device.device_input.pressed_at(&rect) {
+                    //子控件处理拖动时(如调整表格列宽)不滚动
+                    self.layout.as_mut().unwrap().update(ui);
+                    if let UpdateType::None = ui.update_type {
+                        return Response::new(&self.id, WidgetSize::same(self.visual.rect().width(), self.visual.rect().height()));
+                    }
                     let oy = ui.device.device_input.mouse.offset_y();
                     let ox = ui.device.device_input.mouse.offset_x();
                     self.bar_offset(ox, oy);
@@ -271,7 +300,8 @@ impl Widget for ScrollWidget {
                     ui.context.window.request_redraw();
                 }
             }
-            _ => {}
+            //键盘、输入法等事件交给子控件处理
+            _ => { self.layout.as_mut().unwrap().update(ui); }
         }
         Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
     }
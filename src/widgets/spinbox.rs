@@ -47,6 +47,7 @@ pub struct SpinBox<T> {
     value: T,
     gap: T,
     range: Range<T>,
+    format: fn(T) -> String,
     callback: Option<Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, T)>>,
     up_render: RenderParam,
     down_render: RenderParam,
@@ -77,6 +78,7 @@ impl<T: PartialOrd + AddAssign + SubAssign + ToString + Copy + Display + NumCast
             value: v,
             gap: g,
             range: r,
+            format: |v| format!("{:.*}", 2, v),
             callback: None,
             up_render: RenderParam::new(Shape::triangle()).with_style(allow_style.clone()),
             down_render: RenderParam::new(Shape::triangle()).with_style(allow_style),
@@ -92,6 +94,30 @@ impl<T: PartialOrd + AddAssign + SubAssign + ToString + Copy + Display + NumCast
         self
     }
 
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.rect.set_size(width, height);
+        self.geometry.set_context_size(width, height);
+        self
+    }
+
+    ///输入框中显示值的格式，默认保留两位小数
+    pub fn with_format(mut self, format: fn(T) -> String) -> Self {
+        self.format = format;
+        self.edit = TextEdit::single_edit((format)(self.value));
+        self
+    }
+
+    ///创建时使输入框获得焦点
+    pub(crate) fn with_focused(mut self) -> Self {
+        self.edit.state().focused = true;
+        self
+    }
+
+    ///输入框中的文本，按下回车前输入的值尚未写入value
+    pub(crate) fn text(&self) -> String {
+        self.edit.text()
+    }
+
     pub(crate) fn reset_size(&mut self) {
         self.edit.geometry().set_fix_width(self.geometry.context_width() - 18.0);
     }
@@ -105,6 +131,10 @@ impl<T: PartialOrd + AddAssign + SubAssign + ToString + Copy + Display + NumCast
         self.callback = Some(Callback::create_spinbox(f));
     }
 
+    pub fn value(&self) -> T {
+        self.value
+    }
+
     pub fn set_value(&mut self, value: T) {
         self.state.changed = true;
         self.value = value;
@@ -219,7 +249,7 @@ impl<T: PartialOrd + AddAssign + SubAssign + ToString + Copy + Display + NumCast
             self.down_render.offset_to_rect(&rect);
         }
         if ui.widget_changed.contains(WidgetChange::Value) {
-            self.edit.update_text(ui, format!("{:.*}", 2, self.value));
+            self.edit.update_text(ui, (self.format)(self.value));
         }
     }
 
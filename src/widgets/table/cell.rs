@@ -1,16 +1,29 @@
 use crate::frame::context::UpdateType;
+use crate::key::Key;
 use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
-use crate::response::Response;
+use crate::response::{Response, TableEditCallB};
 use crate::shape::Shape;
 use crate::size::Geometry;
 use crate::style::color::Color;
 use crate::ui::Ui;
-use crate::widgets::table::column::TableColumn;
+use crate::widgets::table::column::{SortOrder, TableCellKind, TableColumn, TableEditor};
 use crate::widgets::table::header::{TableHeader, TableUi};
 use crate::widgets::table::row::TableRowData;
+use crate::widgets::table::value::CellValue;
+use crate::widgets::table::{TableAction, TableActions};
 use crate::widgets::{WidgetChange, WidgetSize, WidgetState};
 use crate::*;
 
+///单元格编辑器的状态
+struct TableCellEditor {
+    id: String, //编辑控件的ID
+    kind: TableEditor,
+    source: usize, //数据索引
+    popup_id: Option<String>,
+    callback: Option<TableEditCallB>,
+    actions: TableActions,
+}
+
 pub struct TableCell {
     pub(crate) id: String,
     visual: Visual,
@@ -18,16 +31,29 @@ pub struct TableCell {
     layout: Option<LayoutKind>,
     geometry: Geometry,
     state: WidgetState,
+    column: usize,
+    selected: bool,
+    order: Option<SortOrder>,
+    sort_up: RenderParam,
+    sort_down: RenderParam,
+    actions: Option<TableActions>,
+    sortable: bool,
+    resizing: Option<(f32, f32)>, //拖动调整列宽时的初始宽度、鼠标位置
+    dragging: Option<f32>, //拖动表头时鼠标按下的位置
+    resized_time: u128,
+    editor: Option<TableCellEditor>,
+    filter_id: Option<String>, //过滤行下拉框的ID
 }
 
 impl TableCell {
     pub fn new(width: f32, height: f32) -> TableCell {
-        let cell_style = VisualStyle::same(WidgetStyle {
+        let mut cell_style = VisualStyle::same(WidgetStyle {
             fill: Color::rgb(235, 235, 235),
             border: Border::same(0.0).color(Color::BLUE),
             radius: Radius::same(0),
             shadow: Shadow::new(),
         });
+        cell_style.pressed.fill = Color::rgba(153, 193, 241, 220);
         // cell_style.fill = FillStyle::same(Color::rgb(235, 235, 235));
         // cell_style.border = BorderStyle::same(Border::same(0.0).color(Color::BLUE).radius(Radius::same(0)));
         let layout = HorizontalLayout::left_to_right().with_size(width, height)
@@ -42,50 +68,271 @@ impl TableCell {
         // cell_line_style.border = BorderStyle::same(Border::same(0.0).radius(Radius::same(0)));
         // let fill_param = RectParam::new().with_rect(Rect::new().with_size(width, height)).with_style(cell_style);
         // let cell_param = RectParam::new().with_rect(Rect::new().with_size(1.0, height)).with_style(cell_line_style);
+        let sort_style = VisualStyle::same((Color::rgb(110, 110, 110), 0.0, 0).into());
+        let mut sort_up = RenderParam::new(Shape::triangle()).with_style(sort_style.clone());
+        sort_up.set_poses((4.0, 0.0).into(), (0.0, 6.0).into(), (8.0, 6.0).into());
+        let mut sort_down = RenderParam::new(Shape::triangle()).with_style(sort_style);
+        sort_down.set_poses((0.0, 0.0).into(), (8.0, 0.0).into(), (4.0, 6.0).into());
         TableCell {
             id: gen_unique_id(),
-            visual: Visual::new().with_style(cell_style),
+            visual: Visual::new().with_enable().with_style(cell_style).with_size(width, height),
             cell_line: RenderParam::new(Shape::rectangle()).with_style(cell_line_style),
             layout: Some(LayoutKind::new(layout)),
             geometry: Geometry::new().with_context_size(width, height),
             state: WidgetState::default(),
+            column: 0,
+            selected: false,
+            order: None,
+            sort_up,
+            sort_down,
+            actions: None,
+            sortable: false,
+            resizing: None,
+            dragging: None,
+            resized_time: 0,
+            editor: None,
+            filter_id: None,
         }
     }
 
-    pub fn show_header<T: TableExt>(&mut self, ui: &mut Ui, tui: &TableUi<T>, column: &TableColumn) {
+    pub(crate) fn with_column(mut self, column: usize) -> Self {
+        self.column = column;
+        self
+    }
+
+    pub(crate) fn column(&self) -> usize {
+        self.column
+    }
+
+    pub(crate) fn rect(&self) -> &Rect {
+        self.visual.rect()
+    }
+
+    pub(crate) fn show_header<T: TableExt>(&mut self, ui: &mut Ui, tui: &TableUi<T>, column: &TableColumn, actions: &TableActions) {
         let current_layout = self.layout.take().unwrap();
         let previous_layout = ui.layout.replace(current_layout).unwrap();
         tui.show_header(ui, column);
         self.layout = ui.layout.replace(previous_layout);
+        self.sortable = column.sort();
+        self.actions = Some(actions.clone());
         // #[cfg(feature = "gpu")]
         // self.fill_render.init(ui, false, false);
         // #[cfg(feature = "gpu")]
         // self.cell_line.init(ui, false, false);
     }
 
-    pub fn show_body<T: TableExt>(&mut self, ui: &mut Ui, header: &TableHeader<T>, row_datum: &TableRowData<T>) {
+    ///过滤行单元格：文本框按包含或相等过滤，下拉框按值过滤
+    ///下拉框的可选值在首次打开时由TableView填充
+    pub(crate) fn show_filter(&mut self, ui: &mut Ui, column: usize, actions: &TableActions) {
         let current_layout = self.layout.take().unwrap();
         let previous_layout = ui.layout.replace(current_layout).unwrap();
-        let tui = &header.uis[row_datum.column_index()];
-        tui.show_body(ui, row_datum);
+        let width = (self.geometry.context_width() - 10.0) / 2.0;
+        let height = self.geometry.context_height();
+        let text_actions = actions.clone();
+        let mut edit = TextEdit::single_edit("").connect_inner(move |text| {
+            text_actions.write().unwrap().push(TableAction::FilterText(column, text.to_string()));
+        });
+        edit.buffer().geometry.set_fix_size(width, height);
+        ui.add(edit);
+        let value_actions = actions.clone();
+        let open_actions = actions.clone();
+        let combo = CheckComboBox::<String>::new(vec![]).with_size(width, height).connect_inner(move |values| {
+            value_actions.write().unwrap().push(TableAction::FilterValues(column, values.to_vec()));
+        }).connect_open(move || open_actions.write().unwrap().push(TableAction::FilterOpen(column)));
+        self.filter_id = Some(combo.id.clone());
+        ui.add(combo);
         self.layout = ui.layout.replace(previous_layout);
-        if (row_datum.column_index() % 2 == 0 && row_datum.row_index() % 2 != 0) || (row_datum.column_index() % 2 != 0 && row_datum.row_index() % 2 == 0) {
-            self.visual.style_mut().inactive.fill = Color::rgb(245, 245, 245)
+    }
+
+    ///替换过滤下拉框的可选值
+    pub(crate) fn set_filter_values(&mut self, ui: &mut Ui, values: Vec<String>) {
+        let Some(ref id) = self.filter_id else { return; };
+        let Some(layout) = self.layout.as_mut() else { return; };
+        if let Some(combo) = layout.get_widget::<CheckComboBox<String>>(id) { combo.set_data(ui, values); }
+    }
+
+    pub fn show_body<T: TableExt>(&mut self, ui: &mut Ui, header: &TableHeader<T>, row_datum: &TableRowData<'_, T>) {
+        let current_layout = self.layout.take().unwrap();
+        let previous_layout = ui.layout.replace(current_layout).unwrap();
+        let column = row_datum.column_index();
+        match header.columns[column].kind() {
+            TableCellKind::Text | TableCellKind::Custom => header.uis[column].show_body(ui, row_datum),
+            kind => self.show_value(ui, kind, row_datum.data().value(column)),
         }
+        self.layout = ui.layout.replace(previous_layout);
+        self.visual.style_mut().inactive.fill = if (row_datum.column_index() % 2 == 0 && row_datum.row_index() % 2 != 0) || (row_datum.column_index() % 2 != 0 && row_datum.row_index() % 2 == 0) {
+            Color::rgb(245, 245, 245)
+        } else {
+            Color::rgb(235, 235, 235)
+        };
         // #[cfg(feature = "gpu")]
         // self.cell_line.init(ui, false, false);
         // #[cfg(feature = "gpu")]
         // self.fill_render.init(ui, false, false);
     }
+    ///按列的类型绘制值，值与类型不符时按文本显示
+    fn show_value(&self, ui: &mut Ui, kind: &TableCellKind, value: CellValue) {
+        let width = self.content_width();
+        let height = self.geometry.context_height();
+        match kind {
+            TableCellKind::Number { decimals } => match value.as_f64() {
+                Some(number) => { ui.add(Label::new(format!("{:.*}", decimals, number)).width(width).wrap(TextWrap::ElideEnd).align(Align::RightCenter)); }
+                None => TableCell::text_label(ui, value.to_string(), width),
+            },
+            TableCellKind::Bool => match value.as_bool() {
+                Some(checked) => {
+                    let mut check = CheckBox::new(checked, "");
+                    check.state().disabled = true;
+                    ui.add(check);
+                }
+                None => TableCell::text_label(ui, value.to_string(), width),
+            },
+            TableCellKind::Image => match value {
+                CellValue::Image(source) => { ui.add(Image::new(source).with_size(height - 4.0, height - 4.0)); }
+                value => TableCell::text_label(ui, value.to_string(), width),
+            },
+            TableCellKind::Progress => match value.as_f64() {
+                Some(progress) => ui.vertical(|ui| {
+                    ui.add_space((height - 8.0) / 2.0);
+                    ui.add(ProcessBar::new(progress as f32).with_size(width, 8.0));
+                }),
+                None => TableCell::text_label(ui, value.to_string(), width),
+            },
+            TableCellKind::Text | TableCellKind::Custom => TableCell::text_label(ui, value.to_string(), width),
+        }
+    }
+
+    ///单元格内容的可用宽度
+    pub(crate) fn content_width(&self) -> f32 {
+        self.geometry.context_width() - 10.0
+    }
+
+    ///超出宽度时省略末尾的文本
+    pub(crate) fn text_label(ui: &mut Ui, text: String, width: f32) {
+        ui.add(Label::new(text).width(width).wrap(TextWrap::ElideEnd));
+    }
+
+    ///重新绑定数据前清空单元格内容，同时关闭编辑器
+    pub(crate) fn reset(&mut self, ui: &mut Ui) {
+        if let Some(editor) = self.editor.take() && let Some(popup_id) = editor.popup_id && let Some(popups) = ui.popups.as_mut() {
+            popups.remove(&popup_id);
+        }
+        let layout = HorizontalLayout::left_to_right().with_size(self.geometry.context_width(), self.geometry.context_height())
+            .with_padding(Padding::same(0.0).left(5.0));
+        self.layout = Some(LayoutKind::new(layout));
+    }
+
+    ///按编辑器类型在单元格中显示编辑控件，source为数据索引
+    pub(crate) fn open_editor(&mut self, ui: &mut Ui, kind: &TableEditor, value: String, source: usize, callback: Option<TableEditCallB>, actions: &TableActions) {
+        self.reset(ui);
+        let current_layout = self.layout.take().unwrap();
+        let previous_layout = ui.layout.replace(current_layout).unwrap();
+        let width = self.geometry.context_width() - 10.0;
+        let height = self.geometry.context_height();
+        let id = gen_unique_id();
+        let mut popup_id = None;
+        match kind {
+            TableEditor::Text => {
+                let mut edit = TextEdit::single_edit(value).with_id(&id);
+                edit.buffer().geometry.set_fix_size(width, height);
+                edit.state().focused = true;
+                ui.add(edit);
+            }
+            TableEditor::Number { min, max, step } => {
+                let value = value.trim().parse::<f64>().unwrap_or(*min);
+                ui.add(SpinBox::new(value, *step, *min..*max).id(&id).with_size(width, height).with_focused());
+            }
+            TableEditor::Choice(values) => {
+                let mut combo = ComboBox::new(values.clone()).with_size(width, height);
+                if let Some(index) = values.iter().position(|x| *x == value) { combo = combo.with_current_index(index); }
+                combo.id = id.clone();
+                popup_id = ui.add(combo).map(|combo| combo.popup_id().clone());
+            }
+            TableEditor::Check => { ui.add(CheckBox::new(value == "true", "").id(&id)); }
+        }
+        self.layout = ui.layout.replace(previous_layout);
+        self.editor = Some(TableCellEditor { id, kind: kind.clone(), source, popup_id, callback, actions: actions.clone() });
+    }
+
+    pub(crate) fn editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    ///编辑控件的当前值，数值无法解析时返回None
+    fn editor_value(&mut self) -> Option<String> {
+        let editor = self.editor.as_ref()?;
+        let layout = self.layout.as_mut()?;
+        match &editor.kind {
+            TableEditor::Text => Some(layout.get_widget::<TextEdit>(&editor.id)?.text()),
+            TableEditor::Number { min, max, step } => {
+                let text = layout.get_widget::<SpinBox<f64>>(&editor.id)?.text();
+                let value = text.trim().parse::<f64>().ok()?.max(*min).min(*max);
+                Some(if step.fract() == 0.0 { value.round().to_string() } else { value.to_string() })
+            }
+            TableEditor::Choice(_) => {
+                let selected = layout.get_widget::<ComboBox<String>>(&editor.id)?.parent();
+                selected.read().unwrap().clone()
+            }
+            TableEditor::Check => Some(layout.get_widget::<CheckBox>(&editor.id)?.checked().to_string()),
+        }
+    }
+
+    ///提交前调用编辑回调校验，校验失败时保持编辑状态
+    fn commit_editor(&mut self, ui: &mut Ui) {
+        let Some(value) = self.editor_value() else { return; };
+        let editor = self.editor.as_ref().unwrap();
+        if let Some(ref callback) = editor.callback {
+            let app = ui.app.take().unwrap();
+            let accepted = callback(app, ui, editor.source, self.column, &value);
+            ui.app.replace(app);
+            if !accepted { return; }
+        }
+        editor.actions.write().unwrap().push(TableAction::Commit(editor.source, self.column, value));
+        ui.context.window.request_redraw();
+    }
+
+    pub(crate) fn set_width(&mut self, width: f32) {
+        self.geometry.set_context_width(width);
+        self.visual.rect_mut().set_width(width);
+        let height = self.geometry.context_height();
+        if let Some(layout) = self.layout.as_mut() { layout.set_size(width, height); }
+    }
+
+    ///右侧边框附近的可拖动区域
+    fn border_rect(&self) -> Rect {
+        let mut rect = self.visual.rect().clone();
+        rect.set_x_min(rect.dx().max - 4.0);
+        rect.add_max_x(2.0);
+        rect
+    }
+
+    pub(crate) fn set_selected(&mut self, selected: bool) {
+        self.selected = selected;
+    }
+
+    pub(crate) fn set_order(&mut self, order: Option<SortOrder>) {
+        self.order = order;
+    }
+
     fn redraw(&mut self, ui: &mut Ui) {
         if ui.widget_changed.contains(WidgetChange::Position) {
             self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
             let mut cell_rect = self.visual.rect_mut().clone();
             cell_rect.set_x_min(cell_rect.dx().max - 2.0);
             self.cell_line.rect_mut().offset_to_rect(&cell_rect);
+            let mut sort_rect = self.visual.rect().clone();
+            sort_rect.set_x_min(sort_rect.dx().max - 14.0);
+            sort_rect.set_y_min(sort_rect.dy().center() - 3.0);
+            self.sort_up.offset_to_rect(&sort_rect);
+            self.sort_down.offset_to_rect(&sort_rect);
         }
-        self.visual.draw(ui, self.state.disabled, false, false, false);
+        self.visual.draw(ui, self.state.disabled, false, self.selected, false);
         self.cell_line.draw(ui, self.state.disabled, false, false);
+        match self.order {
+            Some(SortOrder::Ascending) => self.sort_up.draw(ui, self.state.disabled, false, false),
+            Some(SortOrder::Descending) => self.sort_down.draw(ui, self.state.disabled, false, false),
+            None => {}
+        }
         self.layout.as_mut().unwrap().update(ui);
     }
 }
@@ -94,12 +341,55 @@ impl Widget for TableCell {
     fn update(&mut self, ui: &mut Ui) -> Response<'_> {
         match ui.update_type {
             UpdateType::Draw => self.redraw(ui),
+            UpdateType::KeyRelease(Key::Enter) if self.editor.is_some() => {
+                self.commit_editor(ui);
+                ui.update_type = UpdateType::None;
+            }
+            UpdateType::KeyRelease(Key::Escape) if self.editor.is_some() => {
+                self.editor.as_ref().unwrap().actions.write().unwrap().push(TableAction::Cancel);
+                ui.update_type = UpdateType::None;
+                ui.context.window.request_redraw();
+            }
+            UpdateType::MousePress => {
+                if self.actions.is_some() && ui.device.device_input.pressed_at(&self.border_rect()) {
+                    self.resizing = Some((self.geometry.context_width(), ui.device.device_input.mouse.x()));
+                    ui.update_type = UpdateType::None;
+                } else if self.actions.is_some() && ui.device.device_input.pressed_at(self.visual.rect()) {
+                    self.dragging = Some(ui.device.device_input.mouse.x());
+                }
+            }
             UpdateType::MouseMove => {
-                let mut rect = self.cell_line.rect_mut().clone();
-                rect.add_min_x(-2.0);
-                rect.add_max_x(2.0);
-                if ui.device.device_input.hovered_at(&rect) {
-                    self.cell_line.style_mut().inactive.border.set_same(2.0);
+                if let Some((width, x)) = self.resizing && let Some(ref actions) = self.actions {
+                    let width = width + ui.device.device_input.mouse.x() - x;
+                    actions.write().unwrap().push(TableAction::Resize(self.column, width));
+                    ui.update_type = UpdateType::None;
+                    ui.context.window.request_redraw();
+                } else if self.dragging.is_some() && ui.device.device_input.mouse.pressed() {
+                    ui.update_type = UpdateType::None;
+                } else {
+                    let hovered = self.actions.is_some() && ui.device.device_input.hovered_at(&self.border_rect());
+                    if self.state.on_hovered(hovered) {
+                        self.cell_line.style_mut().inactive.border.set_same(if hovered { 2.0 } else { 0.0 });
+                        ui.context.window.request_redraw();
+                    }
+                }
+            }
+            UpdateType::MouseRelease => {
+                if let Some(ref actions) = self.actions {
+                    let x = ui.device.device_input.mouse.x();
+                    if self.resizing.take().is_some() {
+                        //双击边框按内容自动调整列宽
+                        let now = crate::time_ms();
+                        if now - self.resized_time < 400 { actions.write().unwrap().push(TableAction::AutoFit(self.column)); }
+                        self.resized_time = now;
+                        ui.update_type = UpdateType::None;
+                    } else if let Some(press_x) = self.dragging.take() && (x - press_x).abs() > 5.0 {
+                        actions.write().unwrap().push(TableAction::Move(self.column, x));
+                        ui.update_type = UpdateType::None;
+                    } else if self.sortable && ui.device.device_input.click_at(self.visual.rect()) {
+                        let multi = ui.device.device_input.mouse.shift_pressed();
+                        actions.write().unwrap().push(TableAction::Sort(self.column, multi));
+                    }
                     ui.context.window.request_redraw();
                 }
             }
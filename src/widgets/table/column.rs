@@ -1,8 +1,14 @@
+use std::cmp::Ordering;
 pub struct TableColumn {
     name: String,
     width: f32,
+    min_width: f32,
+    max_width: f32,
     filter: bool,
+    filter_mode: TableFilterMode,
     sort: bool,
+    editor: Option<TableEditor>,
+    kind: TableCellKind,
 }
 
 impl TableColumn {
@@ -10,8 +16,13 @@ impl TableColumn {
         TableColumn {
             name: name.to_string(),
             width: 100.0,
+            min_width: 30.0,
+            max_width: f32::MAX,
             filter: false,
+            filter_mode: TableFilterMode::Contains,
             sort: false,
+            editor: None,
+            kind: TableCellKind::Text,
         }
     }
 
@@ -23,19 +34,37 @@ impl TableColumn {
         self.width
     }
 
-    // pub(crate) fn filter(&self) -> bool {
-    //     self.filter
-    // }
+    pub(crate) fn filter(&self) -> bool {
+        self.filter
+    }
+
+    pub(crate) fn filter_mode(&self) -> TableFilterMode {
+        self.filter_mode
+    }
 
-    // pub(crate) fn sort(&self) -> bool {
-    //     self.sort
-    // }
+    pub(crate) fn sort(&self) -> bool {
+        self.sort
+    }
+
+    pub(crate) fn editor(&self) -> Option<&TableEditor> {
+        self.editor.as_ref()
+    }
+
+    pub(crate) fn kind(&self) -> &TableCellKind {
+        &self.kind
+    }
 
     pub fn with_filter(mut self, filter: bool) -> Self {
         self.filter=filter;
         self
     }
 
+    ///过滤行文本框的匹配方式，默认按包含匹配
+    pub fn with_filter_mode(mut self, mode: TableFilterMode) -> Self {
+        self.filter_mode = mode;
+        self
+    }
+
     pub fn with_sort(mut self, sort: bool) -> Self {
         self.sort=sort;
         self
@@ -45,4 +74,104 @@ impl TableColumn {
         self.width=width;
         self
     }
-}
\ No newline at end of file
+
+    ///设置单元格的显示类型，值由TableExt::value提供
+    pub fn with_kind(mut self, kind: TableCellKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    ///设置单元格编辑器，双击或按F2编辑
+    pub fn with_editor(mut self, editor: TableEditor) -> Self {
+        self.editor = Some(editor);
+        self
+    }
+
+    pub fn with_min_width(mut self, width: f32) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    pub fn with_max_width(mut self, width: f32) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    ///设置列宽，限制在最小、最大宽度之间
+    pub(crate) fn set_width(&mut self, width: f32) {
+        self.width = width.max(self.min_width).min(self.max_width);
+    }
+}
+
+///列的顺序及宽度，用于保存和恢复表格的列布局
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableColumnLayout {
+    ///显示顺序，值为列索引
+    pub order: Vec<usize>,
+    ///按列索引排列的列宽
+    pub widths: Vec<f32>,
+}
+///单元格的显示类型，值与类型不符时按文本显示
+#[derive(Clone, Debug, Default)]
+pub enum TableCellKind {
+    #[default]
+    Text,
+    ///数值，右对齐并保留decimals位小数
+    Number { decimals: usize },
+    ///只读的复选框
+    Bool,
+    Image,
+    ///进度条，值的范围为0.0..100.0
+    Progress,
+    ///由TableView::set_cell_ui设置的闭包绘制
+    Custom,
+}
+
+///单元格编辑器的类型
+#[derive(Clone, Debug)]
+pub enum TableEditor {
+    ///文本输入框
+    Text,
+    ///数值输入框，步长为整数时按整数提交
+    Number { min: f64, max: f64, step: f64 },
+    ///下拉框，从给定的值中选择
+    Choice(Vec<String>),
+    ///复选框，提交"true"或"false"
+    Check,
+}
+
+///过滤行文本框的匹配方式
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TableFilterMode {
+    ///包含输入的文本
+    #[default]
+    Contains,
+    ///与输入的文本相等
+    Equals,
+}
+
+///列的排序方向
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    ///点击表头时的切换顺序：升序→降序→不排序
+    pub(crate) fn next(order: Option<SortOrder>) -> Option<SortOrder> {
+        match order {
+            None => Some(SortOrder::Ascending),
+            Some(SortOrder::Ascending) => Some(SortOrder::Descending),
+            Some(SortOrder::Descending) => None,
+        }
+    }
+
+    ///按排序方向调整升序的比较结果
+    pub(crate) fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::widgets::table::column::TableFilterMode;
+use crate::widgets::table::TableExt;
+
+pub type TableFilterFn<T> = Box<dyn Fn(&T) -> bool + 'static>;
+
+///单列的过滤条件，各条件同时满足时显示该行
+pub(crate) struct TableFilter<T> {
+    text: String,
+    values: Vec<String>,
+    predicate: Option<TableFilterFn<T>>,
+}
+
+impl<T> TableFilter<T> {
+    pub fn new() -> Self {
+        TableFilter {
+            text: "".to_string(),
+            values: vec![],
+            predicate: None,
+        }
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    pub fn set_values(&mut self, values: Vec<String>) {
+        self.values = values;
+    }
+
+    pub fn values(&self) -> &Vec<String> {
+        &self.values
+    }
+
+    pub fn set_predicate(&mut self, predicate: Option<TableFilterFn<T>>) {
+        self.predicate = predicate;
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.text.is_empty() || !self.values.is_empty() || self.predicate.is_some()
+    }
+}
+
+impl<T: TableExt> TableFilter<T> {
+    ///文本按mode匹配，可选值需包含该列的值
+    pub fn matches(&self, datum: &T, column: usize, mode: TableFilterMode) -> bool {
+        if let Some(ref predicate) = self.predicate && !predicate(datum) { return false; }
+        if self.text.is_empty() && self.values.is_empty() { return true; }
+        let value = datum.cols()[column].to_string();
+        if !self.values.is_empty() && !self.values.contains(&value) { return false; }
+        match mode {
+            TableFilterMode::Contains => value.contains(&self.text),
+            TableFilterMode::Equals => self.text.is_empty() || value == self.text,
+        }
+    }
+}
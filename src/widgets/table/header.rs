@@ -1,16 +1,20 @@
 use crate::ui::Ui;
 use crate::widgets::table::cell::TableCell;
-use crate::widgets::table::column::TableColumn;
+use crate::widgets::table::column::{TableColumn, TableColumnLayout};
 use crate::widgets::table::row::TableRowData;
+use crate::widgets::table::TableActions;
 use crate::TableExt;
+use std::cmp::Ordering;
 
 pub type TableHeaderUi = Box<dyn Fn(&mut Ui, &TableColumn) + 'static>;
-pub type TableBodyUi<T> = Box<dyn Fn(&mut Ui, &TableRowData<T>) + 'static>;
+pub type TableBodyUi<T> = Box<dyn Fn(&mut Ui, &TableRowData<'_, T>) + 'static>;
+pub type TableCompare<T> = Box<dyn Fn(&T, &T) -> Ordering + 'static>;
 
 
 pub struct TableUi<T> {
     hui: TableHeaderUi,
     bui: TableBodyUi<T>,
+    compare: Option<TableCompare<T>>,
 }
 
 impl<T> TableUi<T> {
@@ -18,7 +22,7 @@ impl<T> TableUi<T> {
         (self.hui)(ui, column);
     }
 
-    pub fn show_body(&self, ui: &mut Ui, data: &TableRowData<T>) {
+    pub fn show_body(&self, ui: &mut Ui, data: &TableRowData<'_, T>) {
         (self.bui)(ui, data);
     }
 }
@@ -27,15 +31,29 @@ impl<T: TableExt> TableUi<T> {
     pub fn new() -> Self {
         TableUi {
             hui: Box::new(|ui, column| ui.label(column.name())),
-            bui: Box::new(|ui, row_datum| ui.label(row_datum.column_string())),
+            bui: Box::new(|ui, row_datum| TableCell::text_label(ui, row_datum.column_string(), row_datum.width())),
+            compare: None,
         }
     }
+
+    ///未设置比较函数时比较TableExt::sort_key
+    pub fn compare(&self, a: &T, b: &T, column: usize) -> Ordering {
+        match self.compare {
+            Some(ref compare) => compare(a, b),
+            None => a.sort_key(column).cmp(&b.sort_key(column)),
+        }
+    }
+
+    pub fn has_compare(&self) -> bool {
+        self.compare.is_some()
+    }
 }
 
 pub struct TableHeader<T> {
     height: f32,
     pub(crate) columns: Vec<TableColumn>,
     pub(crate) uis: Vec<TableUi<T>>,
+    pub(crate) order: Vec<usize>, //显示顺序，值为列索引
 }
 
 impl<T: TableExt> TableHeader<T> {
@@ -46,22 +64,79 @@ impl<T: TableExt> TableHeader<T> {
         }
         TableHeader {
             height: 20.0,
+            order: (0..columns.len()).collect(),
             columns: columns,
             uis,
         }
     }
 
-    pub fn show(&self, ui: &mut Ui) -> Vec<TableCell> {
+    pub(crate) fn show(&self, ui: &mut Ui, actions: &TableActions) -> Vec<TableCell> {
+        let mut cells = vec![];
+        for column_index in self.order.iter().cloned() {
+            let column = &self.columns[column_index];
+            let mut cell = TableCell::new(column.width(), self.height).with_column(column_index);
+            cell.show_header(ui, &self.uis[column_index], column, actions);
+            cells.push(cell);
+        }
+        cells
+    }
+
+    pub(crate) fn show_filter(&self, ui: &mut Ui, actions: &TableActions) -> Vec<TableCell> {
         let mut cells = vec![];
-        for (column_index, column) in self.columns.iter().enumerate() {
-            let mut cell = TableCell::new(column.width(), self.height);
-            cell.show_header(ui, &self.uis[column_index], column);
+        for column_index in self.order.iter().cloned() {
+            let column = &self.columns[column_index];
+            let mut cell = TableCell::new(column.width(), self.height).with_column(column_index);
+            if column.filter() {
+                cell.show_filter(ui, column_index, actions);
+            }
             cells.push(cell);
         }
         cells
     }
 
+    pub fn total_width(&self) -> f32 {
+        self.columns.iter().map(|column| column.width()).sum()
+    }
+
+    ///将列移动到显示位置position
+    pub fn move_column(&mut self, column: usize, position: usize) {
+        let Some(current) = self.order.iter().position(|c| *c == column) else { return; };
+        self.order.remove(current);
+        self.order.insert(position.min(self.order.len()), column);
+    }
+
+    pub fn column_layout(&self) -> TableColumnLayout {
+        TableColumnLayout {
+            order: self.order.clone(),
+            widths: self.columns.iter().map(|column| column.width()).collect(),
+        }
+    }
+
+    ///恢复列布局，列数不一致时返回false
+    pub fn set_column_layout(&mut self, layout: &TableColumnLayout) -> bool {
+        let mut order = layout.order.clone();
+        order.sort();
+        if layout.widths.len() != self.columns.len() || order != (0..self.columns.len()).collect::<Vec<_>>() { return false; }
+        self.order = layout.order.clone();
+        for (column, width) in self.columns.iter_mut().zip(layout.widths.iter()) {
+            column.set_width(*width);
+        }
+        true
+    }
+
+    pub fn has_filter(&self) -> bool {
+        self.columns.iter().any(|column| column.filter())
+    }
+
     pub fn set_hui(&mut self, column: usize, hui: TableHeaderUi) {
         self.uis[column].hui = hui;
     }
+
+    pub fn set_bui(&mut self, column: usize, bui: TableBodyUi<T>) {
+        self.uis[column].bui = bui;
+    }
+
+    pub fn set_compare(&mut self, column: usize, compare: TableCompare<T>) {
+        self.uis[column].compare = Some(compare);
+    }
 }
\ No newline at end of file
@@ -1,38 +1,176 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::key::{Key, KeyModifiers};
 use crate::layout::{Layout, LayoutItem};
+use crate::response::{AppNotifies, AppNotify, Callback, TableEditCallB};
 use crate::render::{VisualStyle, WidgetStyle};
 use crate::style::color::Color;
 use crate::ui::Ui;
-use crate::widgets::table::column::TableColumn;
+use crate::widgets::table::column::{SortOrder, TableColumn, TableColumnLayout};
 use crate::widgets::table::header::{TableHeader, TableHeaderUi};
 use crate::widgets::table::param::TableParams;
 use crate::widgets::table::row::TableRow;
-use crate::{Border, Radius, Rect, RecycleLayout, ScrollWidget, Shadow};
+use crate::widgets::table::selection::{TableSelection, TableSelectionMode};
+use crate::widgets::table::value::{CellValue, SortKey};
+use crate::widgets::model::{ModelChange, ModelUpdate, TableModel, ViewRows};
+use crate::text::buffer::TextBuffer;
+use crate::{Border, Radius, Rect, RecycleLayout, RichText, ScrollWidget, Shadow};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
 
 mod param;
 pub mod column;
+mod filter;
 mod row;
 mod header;
 mod cell;
+pub mod selection;
+pub mod value;
 
+pub(crate) enum TableAction {
+    Sort(usize, bool),
+    Select(usize, usize, KeyModifiers),
+    FilterText(usize, String),
+    FilterValues(usize, Vec<String>),
+    FilterOpen(usize),
+    Resize(usize, f32),
+    AutoFit(usize),
+    Move(usize, f32),
+    Edit(usize, usize),
+    Commit(usize, usize, String),
+    Cancel,
+}
+
+pub(crate) type TableActions = Arc<RwLock<Vec<TableAction>>>;
+
+/// # TableView排序、过滤、列布局、选择、编辑及单元格类型示例
+/// ```
+/// use xlui::*;
+///
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl TableExt for Person {
+///     fn cols(&self) -> Vec<impl ToString> {
+///         vec![self.name.clone(), self.age.to_string()]
+///     }
+///
+///     //非文本列的值，按列的TableCellKind绘制
+///     fn value(&self, column: usize) -> CellValue {
+///         match column {
+///             1 => CellValue::Number(self.age as f64),
+///             _ => CellValue::Text(self.name.clone()),
+///         }
+///     }
+///
+///     //编辑提交后写入数据
+///     fn set_col(&mut self, column: usize, value: &str) -> bool {
+///         match column {
+///             0 => self.name = value.to_string(),
+///             _ => match value.parse() {
+///                 Ok(age) => self.age = age,
+///                 Err(_) => return false,
+///             }
+///         }
+///         true
+///     }
+/// }
+///
+/// //提交前校验，返回false时保持编辑
+/// fn person_edited<A: App>(_: &mut A, _: &mut Ui, _row: usize, column: usize, value: &str) -> bool {
+///     column != 0 || !value.is_empty()
+/// }
+///
+/// let columns = vec![
+///     TableColumn::new_name("name").with_sort(true).with_min_width(60.0).with_max_width(300.0)
+///         .with_editor(TableEditor::Text),
+///     TableColumn::new_name("age").with_sort(true).with_filter(true).with_filter_mode(TableFilterMode::Equals)
+///         .with_kind(TableCellKind::Number { decimals: 0 })
+///         .with_editor(TableEditor::Number { min: 0.0, max: 150.0, step: 1.0 }),
+/// ];
+/// let mut table_view: TableView<Person> = TableView::new(columns).with_size(300.0, 200.0);
+/// //点击表头时按升序、降序、不排序切换，Shift+点击可多列排序
+/// table_view.set_compare(0, |a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+/// //过滤列在表头下方显示过滤行，文本框按列的TableFilterMode匹配，下拉框的可选值随数据更新
+/// table_view.set_filter(1, |person| person.age >= 18);
+/// //Text及Custom类型的列可自定义绘制
+/// table_view.set_cell_ui(0, |ui, person| ui.label(person.name.to_uppercase()));
+/// //显示行对应的数据索引
+/// let _ = table_view.source_index(0);
+/// //拖动表头边框调整列宽，双击边框按内容自适应，拖动表头调整列顺序
+/// let layout = table_view.column_layout();
+/// table_view.set_column_layout(&layout);
+/// //Ctrl+点击切换选中，Shift+点击选择范围，方向键及PageUp/PageDown移动当前行
+/// table_view.set_data(vec![Person { name: "a".to_string(), age: 20 }, Person { name: "b".to_string(), age: 16 }]);
+/// table_view.set_selection_mode(TableSelectionMode::Multi);
+/// table_view.select_rows(&[0, 1]);
+/// assert_eq!(table_view.selected_rows(), vec![0, 1]);
+/// //双击单元格或选中行后按F2打开编辑器，回车提交，Esc取消
+/// fn connect<A: App>(table_view: &mut TableView<Person>) {
+///     table_view.set_edit_callback(person_edited::<A>);
+///     table_view.set_selection_callback(|_: &mut A, _| println!("selection changed"));
+/// }
+/// //双击不可编辑的单元格或按回车激活当前行，update后通过take_activated获取
+/// assert_eq!(table_view.take_activated(), None);
+/// //需在App::update中调用table_view.update(ui)
+/// ```
 pub struct TableView<T> {
     // id: String,
     lid: String,
     rect: Rect,
     header: TableHeader<T>,
     params: TableParams<T>,
+    rows: ViewRows<T, dyn TableModel<T>>,
+    actions: TableActions,
+    previous_display: Range<usize>,
+    changed: bool,
+    columns_changed: bool,
+    edit_callback: Option<TableEditCallB>,
+    edit_request: Option<(usize, usize)>, //待打开编辑器的显示行及列
+    editing: bool,
+    selection: TableSelection,
+    notify: AppNotifies, //选择改变的通知，由表头行调用
+    focused: bool, //点击行后接收方向键
+    count_changed: bool, //只有行数改变，不需重新绑定可见行
+    activated: Option<usize>, //双击或回车激活的数据索引
+    filter_changed: bool, //数据改变后需更新过滤行的可选值
+    stale_filters: Vec<bool>, //过滤下拉框的可选值需在打开前重新计算
 }
 
 
 impl<T: TableExt> TableView<T> {
     pub fn new(columns: Vec<TableColumn>) -> Self {
         let header = TableHeader::from_columns(columns);
-        let params = TableParams::new();
+        let params = TableParams::new(header.columns.len());
+        let stale_filters = vec![true; header.columns.len()];
         TableView {
             // id: crate::gen_unique_id(),
             lid: "".to_string(),
             rect: Rect::new(),
             header,
             params,
+            rows: ViewRows::new(vec![]),
+            actions: Arc::new(RwLock::new(vec![])),
+            previous_display: 0..0,
+            changed: false,
+            columns_changed: false,
+            edit_callback: None,
+            edit_request: None,
+            editing: false,
+            selection: TableSelection::new(),
+            notify: Rc::new(RefCell::new(AppNotify::default())),
+            focused: false,
+            count_changed: false,
+            activated: None,
+            filter_changed: false,
+            stale_filters,
         }
     }
 
@@ -53,11 +191,215 @@ impl<T: TableExt> TableView<T> {
     pub fn set_header_ui(&mut self, column: usize, hui: TableHeaderUi) {
         self.header.set_hui(column, hui);
     }
+
+    ///设置列的绘制函数，用于Text及Custom类型的列，默认显示TableExt::cols的文本
+    pub fn set_cell_ui(&mut self, column: usize, cell_ui: impl Fn(&mut Ui, &T) + 'static) {
+        self.header.set_bui(column, Box::new(move |ui, datum| cell_ui(ui, datum.data())));
+    }
+
+    ///设置列的比较函数，未设置时比较TableExt::sort_key
+    pub fn set_compare(&mut self, column: usize, compare: impl Fn(&T, &T) -> Ordering + 'static) {
+        self.header.set_compare(column, Box::new(compare));
+    }
+
+    ///按列排序，multi为true时追加到已有排序之后
+    pub fn sort_by_column(&mut self, column: usize, order: Option<SortOrder>, multi: bool) {
+        self.params.set_sort(column, order, multi);
+        self.refresh();
+        self.changed = true;
+    }
+
+    ///当前的排序列及方向，按优先级排列
+    pub fn sorts(&self) -> &Vec<(usize, SortOrder)> {
+        self.params.sorts()
+    }
+
+    ///设置列的过滤条件，与表头的过滤输入同时生效
+    pub fn set_filter(&mut self, column: usize, predicate: impl Fn(&T) -> bool + 'static) {
+        self.params.filter_mut(column).set_predicate(Some(Box::new(predicate)));
+        self.refresh();
+        self.changed = true;
+    }
+
+    pub fn clear_filter(&mut self, column: usize) {
+        self.params.filter_mut(column).set_predicate(None);
+        self.refresh();
+        self.changed = true;
+    }
+
+    ///当前显示的行数
+    pub fn row_count(&self) -> usize {
+        self.params.view().len()
+    }
+
+    ///显示行(过滤、排序后)对应的数据索引
+    pub fn source_index(&self, row: usize) -> Option<usize> {
+        self.params.view().get(row).cloned()
+    }
+
+    ///数据索引对应的显示行，被过滤时返回None
+    pub fn view_index(&self, source: usize) -> Option<usize> {
+        self.params.view().iter().position(|index| *index == source)
+    }
+
+    ///全部显示行对应的数据索引
+    pub fn source_indices(&self) -> &[usize] {
+        self.params.view()
+    }
+
+    ///当前列的显示顺序及列宽，可用于保存列布局
+    pub fn column_layout(&self) -> TableColumnLayout {
+        self.header.column_layout()
+    }
+
+    ///恢复列布局，与当前列数不一致时忽略并返回false
+    pub fn set_column_layout(&mut self, layout: &TableColumnLayout) -> bool {
+        let res = self.header.set_column_layout(layout);
+        self.columns_changed |= res;
+        res
+    }
+
+    ///设置编辑提交时的回调，参数依次为数据索引、列索引及新值，返回false时拒绝提交并保持编辑
+    pub fn set_edit_callback<A: App>(&mut self, f: fn(&mut A, &mut Ui, usize, usize, &str) -> bool) {
+        self.edit_callback = Some(Callback::create_table_edit(f));
+    }
+
+    ///编辑显示行row的第column列，该列需设置编辑器
+    pub fn edit(&mut self, row: usize, column: usize) {
+        if self.header.columns.get(column).and_then(|x| x.editor()).is_none() || row >= self.params.view().len() { return; }
+        self.edit_request = Some((row, column));
+        self.changed = true;
+    }
+
+    ///设置选择模式，切换时清空选择
+    pub fn set_selection_mode(&mut self, mode: TableSelectionMode) {
+        self.selection.set_mode(mode);
+        self.changed = true;
+    }
+
+    pub fn selection_mode(&self) -> TableSelectionMode {
+        self.selection.mode()
+    }
+
+    ///选中行的数据索引，按升序排列
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.selection.rows().iter().cloned().collect()
+    }
+
+    ///按数据索引选中行，最后一个为当前行，单选模式下只选中最后一个
+    pub fn select_rows(&mut self, rows: &[usize]) {
+        let rows = rows.iter().cloned().filter(|x| *x < self.rows.len()).collect::<Vec<_>>();
+        self.selection.set_rows(&rows);
+        self.changed = true;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.changed = true;
+    }
+
+    ///点击或键盘改变选择时调用，回调中可通过selected_rows获取选择
+    pub fn set_selection_callback<A: App>(&mut self, f: impl Fn(&mut A, &mut Ui) + 'static) {
+        self.notify.borrow_mut().set_callback(Arc::from(Callback::create_list(f)));
+    }
+
+    ///当前行的数据索引
+    pub fn current_index(&self) -> Option<usize> {
+        self.selection.current()
+    }
+
+    ///当前行的数据，设置模型时返回None
+    pub fn current(&self) -> Option<&T> {
+        self.row(self.selection.current()?)
+    }
+
+    ///数据索引为index的行数据，设置模型时返回None，应通过数据索引从模型读取
+    pub fn row(&self, index: usize) -> Option<&T> {
+        self.rows.data().get(index)
+    }
+
+    ///取出双击不可编辑的单元格或按回车激活的行的数据索引，在App::update中调用update后查询
+    pub fn take_activated(&mut self) -> Option<usize> {
+        self.activated.take()
+    }
+
+    ///单元格模式下当前单元格的数据索引及列索引
+    pub fn current_cell(&self) -> Option<(usize, usize)> {
+        Some((self.selection.current()?, self.selection.column()?))
+    }
 }
 
 impl<T: TableExt> TableView<T> {
     pub fn set_data(&mut self, data: Vec<T>) {
-        self.params.set_data(data);
+        self.rows.set_data(data);
+        self.selection.clear();
+        self.refresh();
+        self.changed = true;
+        self.filter_changed = true;
+    }
+
+    pub fn with_model(mut self, model: impl TableModel<T> + 'static) -> Self {
+        self.set_model(model);
+        self
+    }
+
+    ///使用模型作为数据，视图不保存数据，显示时只读取可见行，模型的变化在update中处理，排序及过滤保持生效。
+    ///设置模型后应通过模型修改数据
+    pub fn set_model(&mut self, model: impl TableModel<T> + 'static) {
+        self.rows.set_model(Box::new(model));
+        self.selection.clear();
+        self.refresh();
+        self.changed = true;
+        self.filter_changed = true;
+    }
+
+    ///按当前过滤和排序重建视图
+    fn refresh(&mut self) {
+        self.rows.with_rows(0..self.rows.len(), |rows| self.params.refresh(&self.header, rows));
+    }
+
+    ///处理模型的变化，调整选择并按当前排序及过滤刷新视图。
+    ///可见行对应的数据未改变时只重设滚动范围
+    fn update_model(&mut self, ui: &mut Ui) {
+        let Some(update) = self.rows.update_model(&ui.context.window) else { return; };
+        let bound = self.bound_rows(ui);
+        let before = self.params.view()[bound.clone()].to_vec();
+        let applied = match update {
+            ModelUpdate::Changed(changes, applied) => {
+                for change in changes.iter() {
+                    match change {
+                        ModelChange::Inserted(range) => self.selection.insert(range.start, range.len()),
+                        ModelChange::Removed(range) => {
+                            let removed = self.selection.remove_range(range.clone());
+                            if removed { self.notify.borrow_mut().notify(); }
+                        }
+                        _ => {}
+                    }
+                }
+                self.rows.with_rows(0..applied.len(), |rows| self.params.refresh_changes(&self.header, &applied, rows));
+                Some(applied)
+            }
+            ModelUpdate::Reset => {
+                self.selection.clear();
+                self.notify.borrow_mut().notify();
+                self.refresh();
+                None
+            }
+        };
+        self.filter_changed = true;
+        let after = &self.params.view()[bound.start.min(self.params.view().len())..bound.end.min(self.params.view().len())];
+        let read = applied.iter().flat_map(|x| x.reads()).any(|range| after.iter().any(|source| range.contains(source)));
+        if applied.is_none() || read || before != after { self.changed = true; } else { self.count_changed = true; }
+    }
+
+    ///已绑定数据的可见行(视图顺序)，未显示时为空
+    fn bound_rows(&self, ui: &mut Ui) -> Range<usize> {
+        if self.lid.is_empty() { return 0..0; }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let count = recycle.items().len().saturating_sub(self.header_rows());
+        let len = self.params.view().len();
+        self.previous_display.start.min(len)..(self.previous_display.start + count).min(len)
     }
 
     pub fn show_rows(&mut self, ui: &mut Ui) {
@@ -65,19 +407,29 @@ impl<T: TableExt> TableView<T> {
         let draw_count = layout.draw_count();
         let header_row = TableRow::new(&self.header, self.params.row_height())
             .with_width(self.rect.width());
-        let header_item = header_row.show_header(ui, &self.header);
-        println!("2323-{}-{}", header_item.width, header_item.height);
+        let mut header_item = header_row.show_header(ui, &self.header, &self.actions);
+        header_item.as_mut_::<TableRow>().unwrap().set_orders(self.params.sorts());
+        header_item.as_mut_::<TableRow>().unwrap().set_notify(&self.notify);
         let layout: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
         layout.add_item(LayoutItem::Widget(header_item));
-        for i in 0..self.params.row_data().len() {
+        if self.header.has_filter() {
+            let filter_row = TableRow::new(&self.header, self.params.row_height())
+                .with_width(self.rect.width()).show_filter(ui, &self.header, &self.actions);
+            let layout: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
+            layout.add_item(LayoutItem::Widget(filter_row));
+        }
+        for i in 0..self.params.view().len() {
+            let mut row = None;
             if i <= draw_count {
-                let row = TableRow::new(&self.header, self.params.row_height())
-                    .with_width(self.rect.width()).show(ui, &self.header, &mut self.params.row_mut(i));
-                let layout: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
-                layout.add_item(LayoutItem::Widget(row));
-            } else {
-                let recycle: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
-                recycle.add_item_empty();
+                self.rows.with_row(self.params.view()[i], |datum| {
+                    row = Some(TableRow::new(&self.header, self.params.row_height())
+                        .with_width(self.rect.width()).show(ui, &self.header, datum, i, &self.actions));
+                });
+            }
+            let recycle: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
+            match row {
+                Some(row) => recycle.add_item(LayoutItem::Widget(row)),
+                None => recycle.add_item_empty(),
             }
         }
     }
@@ -103,11 +455,286 @@ impl<T: TableExt> TableView<T> {
         area.show(ui, |ui| {
             self.show_rows(ui);
         });
+        self.previous_display = 0..0;
+        self.stale_filters.fill(true);
+    }
+
+    ///表头占用的行数，首行为表头，有过滤列时第二行为过滤行
+    fn header_rows(&self) -> usize {
+        if self.header.has_filter() { 2 } else { 1 }
+    }
+
+    ///第column列所有不重复的值
+    fn distinct_values(&self, column: usize) -> Vec<String> {
+        let mut values = BTreeSet::new();
+        self.rows.with_rows(0..self.rows.len(), |rows| values.extend(rows.iter().map(|datum| datum.cols()[column].to_string())));
+        values.into_iter().collect()
+    }
+
+    ///数据改变后过滤下拉框的可选值在打开时重新计算，已选择值的列立即计算以移除不存在的值
+    fn update_filter_values(&mut self, ui: &mut Ui) {
+        self.filter_changed = false;
+        if !self.header.has_filter() { return; }
+        for column in 0..self.header.columns.len() {
+            self.stale_filters[column] = self.header.columns[column].filter();
+            if self.stale_filters[column] && !self.params.filter(column).values().is_empty() { self.load_filter_values(ui, column); }
+        }
+    }
+
+    ///计算第column列的可选值并设置到过滤行的下拉框
+    fn load_filter_values(&mut self, ui: &mut Ui, column: usize) {
+        self.stale_filters[column] = false;
+        let values = self.distinct_values(column);
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let mut recycle = area.layout.take().unwrap();
+        let recycle_layout: &mut RecycleLayout = recycle.as_mut_().unwrap();
+        if let Some(item) = recycle_layout.items_mut().iter_mut().nth(1) {
+            let row: &mut TableRow = item.widget_mut().unwrap();
+            row.set_filter_values(ui, column, values);
+        }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.layout = Some(recycle);
+    }
+
+    ///按表头文本和各行内容测量列宽
+    fn fit_width(&self, ui: &mut Ui, column: usize) -> f32 {
+        let name = self.header.columns[column].name().to_string();
+        let mut buffer = TextBuffer::new(RichText::new(name.as_str()));
+        if buffer.line_height(ui).is_err() { return self.header.columns[column].width(); }
+        let mut width = buffer.text_width(&name) + 20.0;
+        self.rows.with_rows(0..self.rows.len(), |rows| for datum in rows {
+            let value = datum.cols()[column].to_string();
+            width = width.max(buffer.text_width(&value) + 12.0);
+        });
+        width
     }
 
-    // pub fn update(&mut self, ui: &mut Ui) {}
+    fn update_actions(&mut self, ui: &mut Ui) {
+        let actions = mem::take(&mut *self.actions.write().unwrap());
+        for action in actions {
+            match action {
+                TableAction::Sort(column, multi) => {
+                    self.params.toggle_sort(column, multi);
+                    self.refresh();
+                }
+                TableAction::Select(row, column, modifiers) => {
+                    self.focused = true;
+                    if self.selection.click(self.params.view(), row, column, modifiers) { self.notify.borrow_mut().notify(); }
+                }
+                TableAction::FilterText(column, text) => {
+                    self.params.filter_mut(column).set_text(&text);
+                    self.refresh();
+                }
+                TableAction::FilterValues(column, values) => {
+                    self.params.filter_mut(column).set_values(values);
+                    self.refresh();
+                }
+                TableAction::FilterOpen(column) => {
+                    if self.filter_changed { self.update_filter_values(ui); }
+                    if self.stale_filters[column] { self.load_filter_values(ui, column); }
+                    continue;
+                }
+                TableAction::Resize(column, width) => {
+                    self.header.columns[column].set_width(width);
+                    self.columns_changed = true;
+                    continue;
+                }
+                TableAction::AutoFit(column) => {
+                    let width = self.fit_width(ui, column);
+                    self.header.columns[column].set_width(width);
+                    self.columns_changed = true;
+                    continue;
+                }
+                TableAction::Edit(row, column) => {
+                    if self.header.columns[column].editor().is_none() { self.activated = self.params.view().get(row).cloned(); }
+                    self.edit(row, column);
+                }
+                TableAction::Commit(source, column, value) => {
+                    self.focused = true;
+                    if let Some(model) = self.rows.model() {
+                        //可见行在处理模型的变化时更新
+                        model.set_col(source, column, &value);
+                    } else if self.rows.data_mut().get_mut(source).is_some_and(|datum| datum.set_col(column, &value)) {
+                        self.refresh();
+                        self.filter_changed = true;
+                    }
+                }
+                TableAction::Cancel => self.focused = true,
+                TableAction::Move(column, x) => {
+                    let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+                    let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+                    let header_row: &mut TableRow = recycle.items_mut()[0].widget_mut().unwrap();
+                    if let Some(position) = header_row.position_at(x) {
+                        self.header.move_column(column, position);
+                        self.columns_changed = true;
+                    }
+                    continue;
+                }
+            }
+            self.changed = true;
+        }
+    }
+
+    ///列宽或列顺序改变后调整已创建的行并重新绑定
+    fn update_columns(&mut self, ui: &mut Ui) {
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        for item in recycle.items_mut().iter_mut() {
+            let row: &mut TableRow = item.widget_mut().unwrap();
+            row.apply_columns(&self.header);
+        }
+        area.reset_context_width(self.header.total_width());
+        self.columns_changed = false;
+        //按新列宽重新省略单元格文本
+        self.changed = true;
+        ui.context.window.request_redraw();
+    }
+
+    ///按显示行数重设滚动范围
+    fn reset_count(&mut self, ui: &mut Ui) {
+        let count = self.params.view().len() + self.header_rows();
+        self.count_changed = false;
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        if recycle.total_count() == count { return; }
+        recycle.set_total_count(count);
+        let h = recycle.size().rh;
+        area.reset_context_height(h);
+    }
+
+    ///重新绑定可见行的数据，前header_rows项为表头
+    fn rebind(&mut self, ui: &mut Ui) {
+        let header_rows = self.header_rows();
+        self.reset_count(ui);
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let mut recycle = area.layout.take().unwrap();
+        let recycle_layout: &mut RecycleLayout = recycle.as_mut_().unwrap();
+        let display = recycle_layout.display_range().clone();
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        for (index, item) in recycle_layout.items_mut().iter_mut().enumerate() {
+            let row: &mut TableRow = item.widget_mut().unwrap();
+            if index == 0 { row.set_orders(self.params.sorts()); }
+            if index < header_rows { continue; }
+            let view_row = display.start + index - header_rows;
+            let Some(data_index) = self.params.view().get(view_row).cloned() else { break; };
+            let selection = &self.selection;
+            self.rows.with_row(data_index, |datum| row.bind(ui, &self.header, datum, view_row, |column| selection.is_selected(data_index, column)));
+        }
+        ui.update_type = previous_update;
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.layout = Some(recycle);
+        self.previous_display = display;
+        self.editing = false;
+    }
+
+    ///在可见行中打开编辑器，行不可见时忽略
+    fn open_editor(&mut self, ui: &mut Ui, row: usize, column: usize) {
+        let Some(kind) = self.header.columns[column].editor() else { return; };
+        let Some(source) = self.params.view().get(row).cloned() else { return; };
+        let mut value = String::new();
+        self.rows.with_row(source, |datum| value = datum.cols()[column].to_string());
+        let header_rows = self.header_rows();
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let mut recycle = area.layout.take().unwrap();
+        let recycle_layout: &mut RecycleLayout = recycle.as_mut_().unwrap();
+        let display = recycle_layout.display_range().clone();
+        let index = header_rows + row;
+        if index >= display.start + header_rows && index - display.start < recycle_layout.items_mut().len() {
+            let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+            let item = &mut recycle_layout.items_mut()[index - display.start];
+            let table_row: &mut TableRow = item.widget_mut().unwrap();
+            table_row.open_editor(ui, column, kind, value, source, self.edit_callback.clone());
+            self.editing = true;
+            ui.update_type = previous_update;
+        }
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.layout = Some(recycle);
+    }
+
+    ///按F2时编辑当前单元格，非单元格模式时编辑当前行的首个可编辑列
+    fn edit_current(&mut self) {
+        let Some(row) = self.selection.current().and_then(|x| self.view_index(x)) else { return; };
+        let column = match self.selection.column() {
+            Some(column) => Some(column),
+            None => self.header.order.iter().find(|x| self.header.columns[**x].editor().is_some()).cloned(),
+        };
+        if let Some(column) = column { self.edit(row, column); }
+    }
+
+    ///方向键及PageUp/PageDown移动当前行，单元格模式下左右键移动当前列
+    fn navigate(&mut self, ui: &mut Ui, key: &Key) {
+        if self.editing || self.params.view().is_empty() { return; }
+        let header_rows = self.header_rows();
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+        let page = recycle.page_count().saturating_sub(header_rows).max(1);
+        let last = self.params.view().len() - 1;
+        let current = self.selection.current().and_then(|x| self.view_index(x));
+        let position = self.selection.column().and_then(|x| self.header.order.iter().position(|c| *c == x));
+        let (row, position) = match key {
+            Key::UpArrow => (current.map_or(0, |x| x.saturating_sub(1)), position),
+            Key::DownArrow => (current.map_or(0, |x| (x + 1).min(last)), position),
+            Key::PageUp => (current.map_or(0, |x| x.saturating_sub(page)), position),
+            Key::PageDown => (current.map_or(0, |x| (x + page).min(last)), position),
+            Key::LeftArrow if self.selection.mode() == TableSelectionMode::Cell => (current.unwrap_or(0), Some(position.map_or(0, |x| x.saturating_sub(1)))),
+            Key::RightArrow if self.selection.mode() == TableSelectionMode::Cell => (current.unwrap_or(0), Some(position.map_or(0, |x| (x + 1).min(self.header.order.len() - 1)))),
+            _ => return,
+        };
+        let column = position.map(|x| self.header.order[x]).or(Some(self.header.order[0]));
+        if !self.selection.move_to(self.params.view(), row, column) { return; }
+        self.notify.borrow_mut().notify();
+        self.changed = true;
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.scroll_to_item(row + header_rows);
+        ui.context.window.request_redraw();
+    }
+
+    pub fn update(&mut self, ui: &mut Ui) {
+        match ui.update_type {
+            UpdateType::MousePress => self.focused = false,
+            UpdateType::KeyRelease(Key::F2) if self.focused => self.edit_current(),
+            UpdateType::KeyRelease(Key::Enter) if self.focused && !self.editing => self.activated = self.selection.current(),
+            UpdateType::KeyRelease(ref key) if self.focused => {
+                let key = key.clone();
+                self.navigate(ui, &key);
+            }
+            _ => {}
+        }
+        self.update_actions(ui);
+        self.update_model(ui);
+        if self.columns_changed && !self.lid.is_empty() { self.update_columns(ui); }
+        if let UpdateType::Draw = ui.update_type {
+            if self.filter_changed && !self.lid.is_empty() { self.update_filter_values(ui); }
+            if self.count_changed { self.reset_count(ui); }
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle_layout: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            if !self.changed && recycle_layout.display_range() == &self.previous_display { return; }
+            self.rebind(ui);
+            self.changed = false;
+            if let Some((row, column)) = self.edit_request.take() { self.open_editor(ui, row, column); }
+        } else if self.changed || self.count_changed {
+            ui.context.window.request_redraw();
+        }
+    }
 }
 
 pub trait TableExt {
+    ///各列的文本，用于显示、过滤、编辑及自适应列宽
     fn cols(&self) -> Vec<impl ToString>;
+
+    ///第column列的值，非文本类型的列按此值绘制，默认为cols的文本
+    fn value(&self, column: usize) -> CellValue {
+        CellValue::Text(self.cols()[column].to_string())
+    }
+
+    ///编辑提交后写入第column列，返回false表示未修改
+    fn set_col(&mut self, column: usize, value: &str) -> bool {
+        let _ = (column, value);
+        false
+    }
+
+    ///第column列排序时的键，默认为value的排序键，排序时每行只计算一次
+    fn sort_key(&self, column: usize) -> SortKey {
+        self.value(column).sort_key()
+    }
 }
\ No newline at end of file
@@ -1,39 +1,134 @@
-use crate::widgets::table::row::TableRowData;
+use crate::widgets::table::column::SortOrder;
+use crate::widgets::table::filter::TableFilter;
+use crate::widgets::table::header::TableHeader;
 use crate::widgets::table::TableExt;
+use crate::widgets::model::AppliedChanges;
+use crate::widgets::table::value::SortKey;
+use std::cmp::Ordering;
+use std::mem;
 
 pub struct TableParams<T> {
     row_height: f32,
-    row_data: Vec<TableRowData<T>>,
-
+    view: Vec<usize>, //视图行对应的数据索引
+    sorts: Vec<(usize, SortOrder)>,
+    filters: Vec<TableFilter<T>>,
 }
 
 impl<T> TableParams<T> {
-    pub fn new() -> Self {
+    pub fn new(columns: usize) -> Self {
         TableParams {
             row_height: 20.0,
-            row_data: vec![],
+            view: vec![],
+            sorts: vec![],
+            filters: (0..columns).map(|_| TableFilter::new()).collect(),
         }
     }
 
-    pub fn row_data(&self) -> &Vec<TableRowData<T>> {
-        &self.row_data
+    pub fn row_height(&self) -> f32 {
+        self.row_height
     }
 
-    // pub fn row_data_mut(&mut self) -> &mut Vec<TableRowData<T>> {
-    //     &mut self.row_data
-    // }
+    pub fn view(&self) -> &Vec<usize> {
+        &self.view
+    }
 
-    pub fn row_mut(&mut self, row: usize) -> &mut TableRowData<T> {
-        &mut self.row_data[row]
+    pub fn sorts(&self) -> &Vec<(usize, SortOrder)> {
+        &self.sorts
     }
 
-    pub fn row_height(&self) -> f32 {
-        self.row_height
+    pub fn filter(&self, column: usize) -> &TableFilter<T> {
+        &self.filters[column]
+    }
+
+    pub fn filter_mut(&mut self, column: usize) -> &mut TableFilter<T> {
+        &mut self.filters[column]
+    }
+
+    ///切换列的排序方向，multi为false时清除其它列的排序
+    pub fn toggle_sort(&mut self, column: usize, multi: bool) {
+        let current = self.sorts.iter().find(|(c, _)| *c == column).map(|(_, order)| *order);
+        self.set_sort(column, SortOrder::next(current), multi);
+    }
+
+    ///设置列的排序方向，None为取消该列排序
+    pub fn set_sort(&mut self, column: usize, order: Option<SortOrder>, multi: bool) {
+        if !multi { self.sorts.retain(|(c, _)| *c == column); }
+        match (self.sorts.iter().position(|(c, _)| *c == column), order) {
+            (Some(index), Some(order)) => self.sorts[index].1 = order,
+            (Some(index), None) => { self.sorts.remove(index); }
+            (None, Some(order)) => self.sorts.push((column, order)),
+            (None, None) => {}
+        }
     }
 }
 
 impl<T: TableExt> TableParams<T> {
-    pub fn set_data(&mut self, data: Vec<T>) {
-        self.row_data = TableRowData::from_vec(data, self.row_height);
+    ///datum是否满足全部过滤条件
+    fn accepts(&self, header: &TableHeader<T>, datum: &T) -> bool {
+        self.filters.iter().enumerate().all(|(column, filter)| {
+            !filter.is_active() || filter.matches(datum, column, header.columns[column].filter_mode())
+        })
     }
-}
\ No newline at end of file
+
+    ///按排序列比较两行，排序相同时按数据顺序
+    fn order(&self, header: &TableHeader<T>, rows: &[&T], a: usize, b: usize) -> Ordering {
+        for (column, order) in self.sorts.iter() {
+            let ordering = header.uis[*column].compare(rows[a], rows[b], *column);
+            let ordering = order.apply(ordering);
+            if ordering != Ordering::Equal { return ordering; }
+        }
+        a.cmp(&b)
+    }
+
+    ///按当前过滤和排序重建视图，rows为全部数据，排序稳定，无排序列时保持数据顺序。
+    ///排序列均未设置比较函数时每行只计算一次排序键
+    pub fn refresh(&mut self, header: &TableHeader<T>, rows: &[&T]) {
+        self.view = (0..rows.len()).filter(|row| self.accepts(header, rows[*row])).collect();
+        if self.sorts.is_empty() { return; }
+        let mut view = mem::take(&mut self.view);
+        if self.sorts.iter().any(|(column, _)| header.uis[*column].has_compare()) {
+            view.sort_by(|a, b| self.order(header, rows, *a, *b));
+            self.view = view;
+            return;
+        }
+        let mut keyed: Vec<(Vec<SortKey>, usize)> = view.into_iter().map(|row| {
+            (self.sorts.iter().map(|(column, _)| rows[row].sort_key(*column)).collect(), row)
+        }).collect();
+        keyed.sort_by(|(a_keys, a), (b_keys, b)| {
+            for ((a_key, b_key), (_, order)) in a_keys.iter().zip(b_keys.iter()).zip(self.sorts.iter()) {
+                let ordering = order.apply(a_key.cmp(b_key));
+                if ordering != Ordering::Equal { return ordering; }
+            }
+            a.cmp(b)
+        });
+        self.view = keyed.into_iter().map(|(_, row)| row).collect();
+    }
+
+    ///视图是否与数据顺序相同，即没有排序和过滤
+    pub fn is_identity(&self) -> bool {
+        self.sorts.is_empty() && self.filters.iter().all(|x| !x.is_active())
+    }
+
+    ///模型变化后更新视图，rows为变化后的全部数据，保留的行只调整索引，读取的行二分查找位置后插入。
+    ///rows与变化后的行数不一致时(读取前模型再次改变)重建视图
+    pub fn refresh_changes(&mut self, header: &TableHeader<T>, applied: &AppliedChanges, rows: &[&T]) {
+        if rows.len() != applied.len() { return self.refresh(header, rows); }
+        if self.is_identity() {
+            self.view.truncate(applied.len());
+            self.view.extend(self.view.len()..applied.len());
+            return;
+        }
+        let previous: Vec<usize> = self.view.iter().filter_map(|x| applied.map(*x)).collect();
+        let mut reads: Vec<(usize, usize)> = applied.reads().iter().flat_map(|x| x.clone()).filter(|row| self.accepts(header, rows[*row]))
+            .map(|row| (previous.partition_point(|x| self.order(header, rows, *x, row) == Ordering::Less), row)).collect();
+        reads.sort_by(|(a_position, a), (b_position, b)| a_position.cmp(b_position).then_with(|| self.order(header, rows, *a, *b)));
+        let mut reads = reads.into_iter().peekable();
+        let mut view = Vec::with_capacity(previous.len() + reads.len());
+        for (position, index) in previous.into_iter().enumerate() {
+            while let Some((_, row)) = reads.next_if(|(x, _)| *x == position) { view.push(row); }
+            view.push(index);
+        }
+        view.extend(reads.map(|(_, row)| row));
+        self.view = view;
+    }
+}
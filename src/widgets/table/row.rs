@@ -1,11 +1,13 @@
 use crate::render::{Visual, VisualStyle};
-use crate::response::Response;
 use crate::size::Geometry;
 use crate::ui::Ui;
 use crate::widgets::table::cell::TableCell;
+use crate::response::{AppNotifies, AppNotify, Response, TableEditCallB};
+use crate::widgets::table::column::{SortOrder, TableEditor};
 use crate::widgets::table::header::TableHeader;
-use crate::widgets::table::TableExt;
-use crate::widgets::{WidgetKind, WidgetSize, WidgetState};
+use crate::key::KeyModifiers;
+use crate::widgets::table::{TableAction, TableActions, TableExt};
+use crate::widgets::{WidgetChange, WidgetKind, WidgetSize, WidgetState};
 use crate::*;
 
 pub struct TableRow {
@@ -14,6 +16,10 @@ pub struct TableRow {
     cells: Vec<TableCell>,
     geometry: Geometry,
     state: WidgetState,
+    index: Option<usize>,
+    actions: Option<TableActions>,
+    clicked_time: u128,
+    notify: Option<AppNotifies>,
 }
 
 impl TableRow {
@@ -22,8 +28,9 @@ impl TableRow {
         style.inactive.border.set_same(0.0);
         style.pressed.fill = Color::rgb(165, 165, 165);
         let mut cells = vec![];
-        for column in &headers.columns {
-            cells.push(TableCell::new(column.width(), row_height));
+        for column_index in headers.order.iter().cloned() {
+            let column = &headers.columns[column_index];
+            cells.push(TableCell::new(column.width(), row_height).with_column(column_index));
         }
         TableRow {
             id: gen_unique_id(),
@@ -31,6 +38,10 @@ impl TableRow {
             cells,
             geometry: Geometry::new().with_fix_height(row_height),
             state: WidgetState::default(),
+            index: None,
+            actions: None,
+            clicked_time: 0,
+            notify: None,
         }
     }
 
@@ -39,30 +50,107 @@ impl TableRow {
         self
     }
 
-    pub fn show_header<T: TableExt>(mut self, ui: &mut Ui, header: &TableHeader<T>) -> WidgetKind {
-        self.cells = header.show(ui);
+    pub(crate) fn show_header<T: TableExt>(mut self, ui: &mut Ui, header: &TableHeader<T>, actions: &TableActions) -> WidgetKind {
+        self.cells = header.show(ui, actions);
         let row = WidgetKind::new(ui, self);
         row
     }
 
-    pub fn show<T: TableExt>(mut self, ui: &mut Ui, header: &TableHeader<T>, datum: &mut TableRowData<T>) -> WidgetKind {
-        for (index, cell) in self.cells.iter_mut().enumerate() {
-            datum.set_column(index);
-            cell.show_body(ui, header, datum);
-        }
-        self.visual.rect_mut().set_size(self.geometry.padding_width(), self.geometry.padding_height());
-        if datum.row % 2 == 0 { self.visual.style_mut().inactive.fill = Color::rgb(245, 245, 245) }
+    pub(crate) fn show_filter<T: TableExt>(mut self, ui: &mut Ui, header: &TableHeader<T>, actions: &TableActions) -> WidgetKind {
+        self.cells = header.show_filter(ui, actions);
+        WidgetKind::new(ui, self)
+    }
 
+    ///更新过滤行中第column列下拉框的可选值
+    pub(crate) fn set_filter_values(&mut self, ui: &mut Ui, column: usize, values: Vec<String>) {
+        if let Some(cell) = self.cells.iter_mut().find(|x| x.column() == column) { cell.set_filter_values(ui, values); }
+    }
+
+    pub(crate) fn show<T: TableExt>(mut self, ui: &mut Ui, header: &TableHeader<T>, datum: &T, row: usize, actions: &TableActions) -> WidgetKind {
+        self.actions = Some(actions.clone());
+        self.visual.rect_mut().set_size(self.geometry.padding_width(), self.geometry.padding_height());
+        self.bind(ui, header, datum, row, |_| false);
         let row = WidgetKind::new(ui, self);
         row
     }
+
+    ///将第row行(视图顺序)的数据绑定到当前行，selected按列索引返回单元格是否选中
+    pub(crate) fn bind<T: TableExt>(&mut self, ui: &mut Ui, header: &TableHeader<T>, datum: &T, row: usize, selected: impl Fn(usize) -> bool) {
+        let mut row_datum = TableRowData::new(datum, row);
+        for cell in self.cells.iter_mut() {
+            row_datum.set_column(cell.column());
+            row_datum.set_width(cell.content_width());
+            cell.reset(ui);
+            cell.show_body(ui, header, &row_datum);
+            cell.set_selected(selected(cell.column()));
+        }
+        self.visual.style_mut().inactive.fill = if row % 2 == 0 { Color::rgb(245, 245, 245) } else { Color::rgb(230, 230, 230) };
+        self.index = Some(row);
+    }
+
+    ///在column列的单元格中打开编辑器
+    pub(crate) fn open_editor(&mut self, ui: &mut Ui, column: usize, kind: &TableEditor, value: String, source: usize, callback: Option<TableEditCallB>) {
+        let Some(ref actions) = self.actions else { return; };
+        let Some(cell) = self.cells.iter_mut().find(|cell| cell.column() == column) else { return; };
+        cell.open_editor(ui, kind, value, source, callback, actions);
+    }
+
+    ///表头行在布局更新时调用选择改变的回调
+    pub(crate) fn set_notify(&mut self, notify: &AppNotifies) {
+        self.notify = Some(notify.clone());
+    }
+
+    pub(crate) fn set_orders(&mut self, sorts: &[(usize, SortOrder)]) {
+        for cell in self.cells.iter_mut() {
+            let order = sorts.iter().find(|(c, _)| *c == cell.column()).map(|(_, order)| *order);
+            cell.set_order(order);
+        }
+    }
+
+    ///按表头的列顺序、列宽调整单元格
+    pub(crate) fn apply_columns<T>(&mut self, header: &TableHeader<T>) {
+        self.cells.sort_by_key(|cell| header.order.iter().position(|c| *c == cell.column()));
+        for cell in self.cells.iter_mut() {
+            cell.set_width(header.columns[cell.column()].width());
+        }
+    }
+
+    ///x所在单元格的显示位置
+    pub(crate) fn position_at(&self, x: f32) -> Option<usize> {
+        let first = self.cells.first()?;
+        if x < first.rect().dx().min { return Some(0); }
+        let position = self.cells.iter().position(|cell| cell.rect().dx().max > x);
+        Some(position.unwrap_or(self.cells.len() - 1))
+    }
 }
 
 impl Widget for TableRow {
     fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        if let Some(ref notify) = self.notify { AppNotify::emit(notify, ui); }
         match ui.update_type {
             #[cfg(feature = "gpu")]
             UpdateType::ReInit => self.visual.re_init(),
+            UpdateType::Draw if ui.widget_changed.contains(WidgetChange::Position) => {
+                self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+            }
+            UpdateType::MouseRelease => {
+                if let Some(ref actions) = self.actions && let Some(index) = self.index
+                    && !self.cells.iter().any(|cell| cell.editing())
+                    && ui.device.device_input.click_at(self.visual.rect()) {
+                    let mut actions = actions.write().unwrap();
+                    let column = self.position_at(ui.device.device_input.mouse.x()).map(|position| self.cells[position].column()).unwrap_or(0);
+                    let mouse = &ui.device.device_input.mouse;
+                    let modifiers = KeyModifiers { shift: mouse.shift_pressed(), ctrl: mouse.ctrl_pressed() };
+                    actions.push(TableAction::Select(index, column, modifiers));
+                    //双击打开单元格编辑器
+                    let now = crate::time_ms();
+                    if now - self.clicked_time < 400 && !modifiers.shift && !modifiers.ctrl {
+                        actions.push(TableAction::Edit(index, column));
+                    }
+                    self.clicked_time = now;
+                    ui.context.window.request_redraw();
+                }
+            }
             _ => {}
         }
         let mut width = 0.0;
@@ -86,10 +174,11 @@ impl Widget for TableRow {
     }
 }
 
-pub struct TableRowData<T> {
+pub struct TableRowData<'a, T> {
     row: usize,
     column: usize,
-    data: T,
+    width: f32,
+    data: &'a T,
     // height: f32,
     // enable: bool,
     // selected: bool,
@@ -97,7 +186,20 @@ pub struct TableRowData<T> {
 }
 
 
-impl<T> TableRowData<T> {
+impl<'a, T> TableRowData<'a, T> {
+    pub fn new(data: &'a T, row: usize) -> Self {
+        TableRowData {
+            row,
+            column: 0,
+            width: 0.0,
+            data,
+            // height,
+            // enable: false,
+            // selected: false,
+            // hidden: false,
+        }
+    }
+
     pub fn set_column(&mut self, column: usize) {
         self.column = column;
     }
@@ -106,13 +208,18 @@ impl<T> TableRowData<T> {
     //     self.height
     // }
 
-    // pub fn data(&self) -> &T {
-    //     &self.data
-    // }
-    //
-    // pub fn data_mut(&mut self) -> &mut T {
-    //     &mut self.data
-    // }
+    pub fn data(&self) -> &T {
+        self.data
+    }
+
+    pub(crate) fn set_width(&mut self, width: f32) {
+        self.width = width;
+    }
+
+    ///单元格内容的可用宽度
+    pub fn width(&self) -> f32 {
+        self.width
+    }
 
     pub fn column_index(&self) -> usize {
         self.column
@@ -121,29 +228,7 @@ impl<T> TableRowData<T> {
     pub fn row_index(&self) -> usize { self.row }
 }
 
-impl<T: TableExt> TableRowData<T> {
-    pub fn new(t: T, row: usize, _height: f32) -> Self {
-        TableRowData {
-            row,
-            column: 0,
-            data: t,
-            // height,
-            // enable: false,
-            // selected: false,
-            // hidden: false,
-        }
-    }
-
-
-    pub fn from_vec(ts: Vec<T>, height: f32) -> Vec<TableRowData<T>> {
-        let mut res = vec![];
-        for (row, t) in ts.into_iter().enumerate() {
-            let row_data = TableRowData::new(t, row, height);
-            res.push(row_data);
-        }
-        res
-    }
-
+impl<T: TableExt> TableRowData<'_, T> {
     pub fn column_string(&self) -> String {
         self.data.cols()[self.column].to_string()
     }
@@ -0,0 +1,141 @@
+use crate::key::KeyModifiers;
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+///表格的选择模式
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TableSelectionMode {
+    ///单行选择
+    #[default]
+    Single,
+    ///多行选择，Ctrl+点击切换选中，Shift+点击选择范围
+    Multi,
+    ///单元格选择
+    Cell,
+}
+
+pub(crate) struct TableSelection {
+    mode: TableSelectionMode,
+    rows: BTreeSet<usize>, //选中行的数据索引
+    current: Option<usize>, //当前行的数据索引
+    anchor: Option<usize>, //Shift选择范围的起点(数据索引)
+    column: Option<usize>, //单元格模式下的当前列
+}
+
+impl TableSelection {
+    pub fn new() -> Self {
+        TableSelection {
+            mode: TableSelectionMode::Single,
+            rows: BTreeSet::new(),
+            current: None,
+            anchor: None,
+            column: None,
+        }
+    }
+
+    pub fn mode(&self) -> TableSelectionMode {
+        self.mode
+    }
+
+    ///切换模式时清空选择
+    pub fn set_mode(&mut self, mode: TableSelectionMode) {
+        self.mode = mode;
+        self.clear();
+    }
+
+    pub fn rows(&self) -> &BTreeSet<usize> {
+        &self.rows
+    }
+
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.current = None;
+        self.anchor = None;
+        self.column = None;
+    }
+
+    ///单元格是否显示为选中，单元格模式下只有当前单元格选中
+    pub fn is_selected(&self, source: usize, column: usize) -> bool {
+        match self.mode {
+            TableSelectionMode::Cell => self.current == Some(source) && self.column == Some(column),
+            _ => self.rows.contains(&source),
+        }
+    }
+
+    ///选中给定的数据索引，单选模式下只保留最后一个
+    pub fn set_rows(&mut self, rows: &[usize]) {
+        self.rows.clear();
+        match self.mode {
+            TableSelectionMode::Multi => self.rows.extend(rows.iter().cloned()),
+            _ => self.rows.extend(rows.last().cloned()),
+        }
+        self.current = rows.last().cloned();
+        self.anchor = self.current;
+        if self.current.is_none() { self.column = None; }
+    }
+
+    ///点击视图行row的第column列，view为视图行对应的数据索引，选择改变时返回true
+    pub fn click(&mut self, view: &[usize], row: usize, column: usize, modifiers: KeyModifiers) -> bool {
+        let Some(source) = view.get(row).cloned() else { return false; };
+        let previous = (self.rows.clone(), self.current, self.column);
+        match self.mode {
+            TableSelectionMode::Multi if modifiers.shift => {
+                let anchor = self.anchor.and_then(|anchor| view.iter().position(|x| *x == anchor)).unwrap_or(row);
+                if !modifiers.ctrl { self.rows.clear(); }
+                self.rows.extend(view[anchor.min(row)..=anchor.max(row)].iter().cloned());
+            }
+            TableSelectionMode::Multi if modifiers.ctrl => {
+                if !self.rows.remove(&source) { self.rows.insert(source); }
+                self.anchor = Some(source);
+            }
+            _ => {
+                self.rows.clear();
+                self.rows.insert(source);
+                self.anchor = Some(source);
+            }
+        }
+        self.current = Some(source);
+        if self.mode == TableSelectionMode::Cell { self.column = Some(column); }
+        previous != (self.rows.clone(), self.current, self.column)
+    }
+
+    ///键盘移动到视图行row，单元格模式下同时移动到column列
+    pub fn move_to(&mut self, view: &[usize], row: usize, column: Option<usize>) -> bool {
+        let Some(source) = view.get(row).cloned() else { return false; };
+        let column = if self.mode == TableSelectionMode::Cell { column.or(self.column) } else { None };
+        if self.current == Some(source) && self.column == column && self.rows.len() == 1 && self.rows.contains(&source) { return false; }
+        self.rows.clear();
+        self.rows.insert(source);
+        self.current = Some(source);
+        self.anchor = Some(source);
+        self.column = column;
+        true
+    }
+
+    ///删除数据索引在range中的行后调整后续索引，删除了选中行或当前行时返回true
+    pub fn remove_range(&mut self, range: Range<usize>) -> bool {
+        let removed = self.rows.range(range.clone()).next().is_some() || self.current.is_some_and(|x| range.contains(&x));
+        let shift = |x: usize| if x >= range.end { x - range.len() } else { x };
+        self.rows = self.rows.iter().filter(|x| !range.contains(x)).map(|x| shift(*x)).collect();
+        self.current = self.current.filter(|x| !range.contains(x)).map(shift);
+        self.anchor = self.anchor.filter(|x| !range.contains(x)).map(shift);
+        if self.current.is_none() { self.column = None; }
+        removed
+    }
+
+    ///在数据索引index处插入count行后调整后续索引
+    pub fn insert(&mut self, index: usize, count: usize) {
+        let shift = |x: usize| if x >= index { x + count } else { x };
+        self.rows = self.rows.iter().map(|x| shift(*x)).collect();
+        self.current = self.current.map(shift);
+        self.anchor = self.anchor.map(shift);
+    }
+}
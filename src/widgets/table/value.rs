@@ -0,0 +1,162 @@
+use crate::render::image::ImageSource;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+///单元格的值，由TableExt::value返回，按列的TableCellKind绘制
+pub enum CellValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Image(ImageSource),
+    ///进度，范围为0.0..100.0
+    Progress(f32),
+}
+
+impl CellValue {
+    ///数值及进度返回其值，文本尝试解析为数值
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            CellValue::Text(text) => text.trim().parse().ok(),
+            CellValue::Number(number) => Some(*number),
+            CellValue::Progress(progress) => Some(*progress as f64),
+            CellValue::Bool(_) | CellValue::Image(_) => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CellValue::Bool(value) => Some(*value),
+            CellValue::Text(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    ///排序键，文本能解析为数值时按数值排序
+    pub fn sort_key(&self) -> SortKey {
+        match self {
+            CellValue::Bool(value) => SortKey::Bool(*value),
+            CellValue::Text(text) => match text.trim().parse() {
+                Ok(number) => SortKey::Number(number),
+                Err(_) => SortKey::Text(text.clone()),
+            },
+            CellValue::Number(number) => SortKey::Number(*number),
+            CellValue::Progress(progress) => SortKey::Number(*progress as f64),
+            CellValue::Image(_) => SortKey::Text(String::new()),
+        }
+    }
+
+    ///按排序键比较，数值排在文本前
+    pub fn compare(&self, other: &CellValue) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+///列排序的键，不同类型间依次为布尔值、数值、文本、组合键，同类型按值比较，保证全序
+#[derive(Clone, Debug)]
+pub enum SortKey {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    ///依次比较各键
+    Composite(Vec<SortKey>),
+}
+
+impl SortKey {
+    fn rank(&self) -> u8 {
+        match self {
+            SortKey::Bool(_) => 0,
+            SortKey::Number(_) => 1,
+            SortKey::Text(_) => 2,
+            SortKey::Composite(_) => 3,
+        }
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Bool(a), SortKey::Bool(b)) => a.cmp(b),
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            (SortKey::Composite(a), SortKey::Composite(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortKey {}
+
+impl Display for CellValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CellValue::Text(text) => write!(f, "{}", text),
+            CellValue::Number(number) => write!(f, "{}", number),
+            CellValue::Bool(value) => write!(f, "{}", value),
+            CellValue::Image(_) => Ok(()),
+            CellValue::Progress(progress) => write!(f, "{}", progress),
+        }
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        CellValue::Text(value)
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        CellValue::Text(value.to_string())
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        CellValue::Number(value)
+    }
+}
+
+impl From<bool> for CellValue {
+    fn from(value: bool) -> Self {
+        CellValue::Bool(value)
+    }
+}
+
+impl From<ImageSource> for CellValue {
+    fn from(value: ImageSource) -> Self {
+        CellValue::Image(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_before_text() {
+        let mut values: Vec<CellValue> = ["1a", "10", "2", "b", "-1"].into_iter().map(CellValue::from).collect();
+        values.sort_by(|a, b| a.compare(b));
+        let texts: Vec<String> = values.iter().map(|x| x.to_string()).collect();
+        assert_eq!(texts, ["-1", "2", "10", "1a", "b"]);
+    }
+
+    #[test]
+    fn composite_orders_by_first_key() {
+        let dir = SortKey::Composite(vec![SortKey::Bool(false), SortKey::Text("z".to_string())]);
+        let file = SortKey::Composite(vec![SortKey::Bool(true), SortKey::Text("a".to_string())]);
+        assert!(dir < file);
+        assert_eq!(SortKey::Number(f64::NAN), SortKey::Number(f64::NAN));
+    }
+}
@@ -1,13 +1,43 @@
 use crate::text::buffer::TextBuffer;
 use crate::text::cchar::CChar;
+use crate::text::piece::PieceTable;
 use crate::ui::Ui;
 use crate::widgets::textedit::cursor::EditCursor;
 use crate::widgets::textedit::select::EditSelection;
 use crate::widgets::textedit::EditKind;
 use crate::{Offset, Padding, RichTextExt, TextWrap};
+use std::ops::Range;
+
+///对source的一次修改，start为字符索引
+pub(crate) struct SourceEdit {
+    pub(crate) start: usize,
+    pub(crate) removed: usize,
+    pub(crate) inserted: usize,
+}
+
+impl SourceEdit {
+    ///修改前的字符索引转换为修改后的索引，被删除的字符映射到插入文本的末尾
+    pub fn map(&self, index: usize) -> usize {
+        if index <= self.start { return index; }
+        if index >= self.start + self.removed { return index - self.removed + self.inserted; }
+        self.start + self.inserted
+    }
+
+    ///不与删除部分重叠的范围转换为修改后的范围，在start处插入时范围后移
+    pub fn map_range(&self, range: &Range<usize>) -> Range<usize> {
+        if range.end <= self.start { return range.clone(); }
+        range.start - self.removed + self.inserted..range.end - self.removed + self.inserted
+    }
+}
 
 pub(crate) struct CharBuffer {
     pub(crate) buffer: TextBuffer,
+    ///文本内容，lines仅用于测量和显示
+    pub(crate) source: PieceTable,
+    ///上次处理后对source的修改，用于增量更新查找结果
+    pub(crate) edits: Vec<SourceEdit>,
+    ///source已修改而buffer.text尚未同步
+    stale: bool,
     font_size: f32,
     line_height: f32,
     max_wrap_width: f32,
@@ -18,8 +48,28 @@ pub(crate) struct CharBuffer {
 
 impl CharBuffer {
     pub fn new(text: impl ToString) -> CharBuffer {
+        let mut buffer = TextBuffer::new(text.to_string().wrap(TextWrap::WrapAny)).fix_width(200.0).padding(Padding::same(3.0));
+        buffer.lazy = true;
+        CharBuffer {
+            source: PieceTable::new(text.to_string()),
+            edits: vec![],
+            stale: false,
+            buffer,
+            font_size: 0.0,
+            line_height: 0.0,
+            max_wrap_width: 0.0,
+            offset: Offset::new(),
+            edit_kind: EditKind::Multi,
+            looking: false,
+        }
+    }
+
+    pub fn from_buffer(buffer: TextBuffer) -> CharBuffer {
         CharBuffer {
-            buffer: TextBuffer::new(text.to_string().wrap(TextWrap::WrapAny)).fix_width(200.0).padding(Padding::same(3.0)),
+            source: PieceTable::new(&buffer.text.text),
+            edits: vec![],
+            stale: false,
+            buffer,
             font_size: 0.0,
             line_height: 0.0,
             max_wrap_width: 0.0,
@@ -41,7 +91,31 @@ impl CharBuffer {
         self.max_wrap_width = max_wrap_width;
     }
 
+    ///替换全部文本
+    pub(crate) fn set_source(&mut self, text: &str) {
+        self.source = PieceTable::new(text);
+        self.edits.clear();
+        self.stale = false;
+    }
+
+    fn source_insert(&mut self, index: usize, text: &str) {
+        self.source.insert(index, text);
+        self.edits.push(SourceEdit { start: index, removed: 0, inserted: text.chars().count() });
+        self.stale = true;
+    }
+
+    fn source_remove(&mut self, range: Range<usize>) {
+        let end = range.end.min(self.source.len());
+        if range.start >= end { return; }
+        self.source.remove(range.start..end);
+        self.edits.push(SourceEdit { start: range.start, removed: end - range.start, inserted: 0 });
+        self.stale = true;
+    }
+
     pub fn remove_by_range(&mut self, ui: &mut Ui, cursor: &mut EditCursor, selection: &mut EditSelection) {
+        let start = self.index_by_position(selection.start_vert, selection.start_horiz);
+        let end = self.index_by_position(cursor.vert, cursor.horiz);
+        self.source_remove(start.min(end)..start.max(end));
         if cursor.vert > selection.start_vert { //向下删除
             let start_line = &mut self.buffer.lines[selection.start_vert];
             while start_line.chars.len() > selection.start_horiz {
@@ -55,10 +129,8 @@ impl CharBuffer {
                 let c = end_line.chars.remove(0);
                 end_line.width -= c.width;
             }
-            for i in selection.start_vert + 1..cursor.vert {
-                self.buffer.lines.remove(cursor.vert - i);
-            }
-            self.rebuild_text(ui);
+            self.buffer.lines.drain(selection.start_vert + 1..cursor.vert);
+            self.rebuild_line(ui, selection.start_vert);
             cursor.set_cursor(selection.start_horiz, selection.start_vert, self);
         } else if selection.start_vert > cursor.vert { //向上删除
             let start_line = &mut self.buffer.lines[selection.start_vert];
@@ -73,10 +145,8 @@ impl CharBuffer {
                 end_line.width -= c.width;
             }
             end_line.auto_wrap = true;
-            for i in cursor.vert + 1..selection.start_vert {
-                self.buffer.lines.remove(selection.start_vert - i);
-            }
-            self.rebuild_text(ui);
+            self.buffer.lines.drain(cursor.vert + 1..selection.start_vert);
+            self.rebuild_line(ui, cursor.vert);
         } else { //同行删除
             let line = &mut self.buffer.lines[cursor.vert];
             if selection.start_horiz < cursor.horiz { //左到右删除
@@ -97,14 +167,75 @@ impl CharBuffer {
                 cursor.set_cursor(cursor.horiz, cursor.vert, self);
                 cursor.changed = true;
             }
-            self.rebuild_text(ui);
+            self.rebuild_line(ui, cursor.vert);
         }
         selection.reset(cursor);
         selection.has_selected = false;
     }
 
+    ///重新测量vert所在的逻辑行，非延迟测量时重建全部文本
+    pub(crate) fn rebuild_line(&mut self, ui: &mut Ui, vert: usize) {
+        if !self.buffer.lazy { return self.rebuild_text(ui); }
+        let range = self.logical_range(vert);
+        self.buffer.measure_range(range).unwrap();
+    }
+
+    ///vert所在逻辑行(自动换行拆分前)包含的行范围
+    pub fn logical_range(&self, vert: usize) -> Range<usize> {
+        let lines = &self.buffer.lines;
+        let mut start = vert;
+        while start > 0 && lines[start - 1].auto_wrap { start -= 1; }
+        let mut end = vert;
+        while end + 1 < lines.len() && lines[end].auto_wrap { end += 1; }
+        start..end + 1
+    }
+
+    ///测量范围内延迟测量的行，行被拆分时同步光标及选择位置，返回是否有行被测量
+    pub fn measure_lines(&mut self, range: Range<usize>, cursor: &mut EditCursor, selection: &mut EditSelection) -> bool {
+        let end = range.end.min(self.buffer.lines.len());
+        let start = range.start.min(end);
+        if self.buffer.lines[start..end].iter().all(|x| x.measured) { return false; }
+        let mut cursor_position = (cursor.vert, cursor.horiz);
+        let mut select_position = (selection.start_vert, selection.start_horiz);
+        let mut vert = start;
+        while vert < end.min(self.buffer.lines.len()) {
+            if self.buffer.lines[vert].measured {
+                vert += 1;
+                continue;
+            }
+            let count = self.buffer.measure_range(vert..vert + 1).unwrap();
+            cursor_position = self.split_position(cursor_position, vert, count);
+            select_position = self.split_position(select_position, vert, count);
+            vert += count;
+        }
+        cursor.set_cursor(cursor_position.1, cursor_position.0, self);
+        (selection.start_vert, selection.start_horiz) = select_position;
+        true
+    }
+
+    ///第vert行被测量拆分为count行后，更新位于该行或其后的位置(vert, horiz)
+    fn split_position(&self, position: (usize, usize), vert: usize, count: usize) -> (usize, usize) {
+        let (position_vert, mut horiz) = position;
+        if position_vert < vert { return position; }
+        if position_vert > vert { return (position_vert + count - 1, horiz); }
+        for index in vert..vert + count - 1 {
+            let len = self.buffer.lines[index].len();
+            if horiz < len { return (index, horiz); }
+            horiz -= len;
+        }
+        let last = vert + count - 1;
+        (last, horiz.min(self.buffer.lines[last].len()))
+    }
+
+    ///延迟测量时编辑只修改source，重新排版前需同步完整文本，未修改时不拼接
+    pub(crate) fn sync_text(&mut self) {
+        if !self.buffer.lazy || !self.stale { return; }
+        self.buffer.text.text = self.source.text();
+        self.stale = false;
+    }
+
     pub(crate) fn rebuild_text(&mut self, ui: &mut Ui) {
-        let text: String = self.buffer.lines.iter().map(|x| x.raw_text()).collect();
+        let text = self.source.text();
         println!("rebuild text: {:?}", text);
         if let EditKind::Password = self.edit_kind && !self.looking {
             let text = vec!["●"; text.chars().count()];
@@ -126,8 +257,10 @@ impl CharBuffer {
         if cursor.horiz == 0 && cursor.vert == 0 { return; }
         println!("delete-before-{}-{}", selection.has_selected, cursor.horiz);
         if !selection.has_selected {
+            let index = self.index_by_position(cursor.vert, cursor.horiz);
+            self.source_remove(index - 1..index);
             cursor.delete_before(self);
-            self.rebuild_text(ui)
+            self.rebuild_line(ui, cursor.vert)
         } else {
             self.remove_by_range(ui, cursor, selection)
         }
@@ -137,8 +270,10 @@ impl CharBuffer {
         println!("{} {} {} {}", cursor.vert, self.buffer.lines.len() - 1, cursor.horiz, self.buffer.lines.last().unwrap().len());
         if cursor.vert == self.buffer.lines.len() - 1 && cursor.horiz == self.buffer.lines.last().unwrap().len() && cursor.horiz == 0 { return; }
         if !selection.has_selected {
+            let index = self.index_by_position(cursor.vert, cursor.horiz);
+            self.source_remove(index..index + 1);
             cursor.delete_after(self);
-            self.rebuild_text(ui);
+            self.rebuild_line(ui, cursor.vert);
         } else {
             self.remove_by_range(ui, cursor, selection);
         }
@@ -148,10 +283,12 @@ impl CharBuffer {
         if selection.has_selected {
             self.remove_by_range(ui, cursor, selection);
         }
+        let index = self.index_by_position(cursor.vert, cursor.horiz);
+        self.source_insert(index, &c.to_string());
         let cchar = CChar::new(c, 0.0);
         let line = &mut self.buffer.lines[cursor.vert];
         line.chars.insert(cursor.horiz, cchar);
-        self.rebuild_text(ui);
+        self.rebuild_line(ui, cursor.vert);
         let line = &mut self.buffer.lines[cursor.vert];
         println!("insert before-{}-{}", line.chars.len(), cursor.horiz);
         let width = if line.len() == 0 { 0.0 } else { line.chars[if cursor.horiz == 0 { 0 } else { cursor.horiz - 1 }].width };
@@ -172,37 +309,56 @@ impl CharBuffer {
         self.buffer.lines.get(cursor.vert)?.chars.get(cursor.horiz)
     }
 
+    ///光标所在单词的字符范围
+    pub fn word_range(&self, cursor: &EditCursor) -> Range<usize> {
+        let chars = &self.buffer.lines[cursor.vert].chars;
+        let is_word = |c: &CChar| c.cchar.is_alphanumeric() || c.cchar == '_';
+        let mut start = cursor.horiz.min(chars.len());
+        let mut end = start;
+        while start > 0 && is_word(&chars[start - 1]) { start -= 1; }
+        while end < chars.len() && is_word(&chars[end]) { end += 1; }
+        if start == end && end < chars.len() { end += 1; }
+        start..end
+    }
+
+    ///行列位置转换为全文字符索引，非自动换行的行末计入'\n'
+    pub fn index_by_position(&self, vert: usize, horiz: usize) -> usize {
+        let before: usize = self.buffer.lines[..vert].iter().map(|x| x.len() + if x.auto_wrap { 0 } else { 1 }).sum();
+        before + horiz
+    }
+
+    ///行列位置转换为原文本中的字符索引，被省略的字符会计入
+    pub(crate) fn source_index_by_position(&self, vert: usize, horiz: usize) -> usize {
+        let before: usize = self.buffer.lines[..vert].iter().map(|x| x.source_len() + if x.auto_wrap { 0 } else { 1 }).sum();
+        before + self.buffer.lines.get(vert).map_or(horiz, |x| x.source_horiz(horiz))
+    }
+
+    ///全文字符索引转换为行列位置(vert, horiz)
+    pub fn position_by_index(&self, index: usize) -> (usize, usize) {
+        let mut start = 0;
+        let last = self.buffer.lines.len() - 1;
+        for (vert, line) in self.buffer.lines.iter().enumerate() {
+            let len = line.len() + if line.auto_wrap { 0 } else { 1 };
+            if index < start + len || vert == last {
+                return (vert, (index - start).min(line.len()));
+            }
+            start += len;
+        }
+        (0, 0)
+    }
+
     pub fn previous_char(&self, cursor: &EditCursor) -> Option<&CChar> {
         if cursor.horiz == 0 { return None; }
         if cursor.horiz - 1 == 0 { return self.buffer.lines.get(cursor.vert)?.chars.get(0); }
         self.buffer.lines.get(cursor.vert)?.chars.get(cursor.horiz - 2)
     }
 
-    #[cfg(not(feature = "winit"))]
     pub fn select_text(&self, select: &EditSelection, cursor: &EditCursor) -> String {
         if !select.has_selected { return "".to_string(); }
-        let mut chars = vec![];
-        if select.start_vert != cursor.vert {
-            let (start_vert, start_horiz, end_vert, end_horiz) = if select.start_vert < cursor.vert {
-                (select.start_vert, select.start_horiz, cursor.vert, cursor.horiz)
-            } else {
-                (cursor.vert, cursor.horiz, select.start_vert, select.start_horiz)
-            };
-            let start_line = &self.buffer.lines[start_vert];
-            chars.push(start_line.get_text_by_range(start_horiz..start_line.len()));
-            for i in start_vert + 1..end_vert {
-                chars.push(self.buffer.lines[i].raw_text());
-            }
-            let end_line = &self.buffer.lines[end_vert];
-            chars.push(end_line.get_text_by_range(0..end_horiz));
-        } else {
-            let (start_horiz, end_horiz) = if select.start_horiz < cursor.horiz {
-                (select.start_horiz, cursor.horiz)
-            } else {
-                (cursor.horiz, select.start_horiz)
-            };
-            chars.push(self.buffer.lines[select.start_vert].get_text_by_range(start_horiz..end_horiz))
-        }
-        chars.join("")
+        let start = self.source_index_by_position(select.start_vert, select.start_horiz);
+        let end = self.source_index_by_position(cursor.vert, cursor.horiz);
+        let text = self.source.slice(start.min(end)..start.max(end).min(self.source.len()));
+        if let EditKind::Password = self.edit_kind && !self.looking { return text.chars().map(|_| '●').collect(); }
+        text
     }
-}
\ No newline at end of file
+}
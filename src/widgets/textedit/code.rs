@@ -0,0 +1,449 @@
+use crate::frame::context::UpdateType;
+use crate::key::Key;
+use crate::render::{RenderParam, VisualStyle, WidgetStyle};
+use crate::response::Response;
+use crate::shape::Shape;
+use crate::size::border::Border;
+use crate::size::radius::Radius;
+use crate::size::Geometry;
+use crate::style::color::Color;
+use crate::text::buffer::TextBuffer;
+use crate::text::piece::PieceTable;
+use crate::text::rich::RichText;
+use crate::text::TextSpan;
+use crate::ui::Ui;
+use crate::widgets::textedit::TextEdit;
+use crate::widgets::{Widget, WidgetSize, WidgetState};
+use crate::{Font, Padding, Shadow, TextWrap};
+use std::collections::HashMap;
+use std::mem;
+use std::ops::Range;
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+///高亮缓存的最大行数，超出后清空
+const HIGHLIGHT_CACHE_SIZE: usize = 4096;
+
+///语法高亮接口，按行返回样式范围
+pub trait Highlighter {
+    ///返回单行文本(不含换行符)的样式范围，range为行内字符索引
+    fn highlight_line(&mut self, line: &str) -> Vec<TextSpan>;
+}
+
+/// ### 关键字高亮
+/// * 高亮关键字、字符串及行注释
+pub struct KeywordHighlighter {
+    keywords: Vec<String>,
+    keyword_color: Color,
+    string_color: Option<Color>,
+    comment: Option<(String, Color)>,
+}
+
+impl KeywordHighlighter {
+    pub fn new(keywords: &[&str], color: Color) -> KeywordHighlighter {
+        KeywordHighlighter {
+            keywords: keywords.iter().map(|x| x.to_string()).collect(),
+            keyword_color: color,
+            string_color: None,
+            comment: None,
+        }
+    }
+
+    ///高亮双引号字符串
+    pub fn with_string(mut self, color: Color) -> Self {
+        self.string_color = Some(color);
+        self
+    }
+
+    ///高亮以prefix开头的行注释
+    pub fn with_comment(mut self, prefix: impl ToString, color: Color) -> Self {
+        self.comment = Some((prefix.to_string(), color));
+        self
+    }
+}
+
+impl Highlighter for KeywordHighlighter {
+    fn highlight_line(&mut self, line: &str) -> Vec<TextSpan> {
+        let chars: Vec<char> = line.chars().collect();
+        let prefix: Vec<char> = self.comment.as_ref().map(|(prefix, _)| prefix.chars().collect()).unwrap_or_default();
+        let mut res = vec![];
+        let mut index = 0;
+        while index < chars.len() {
+            if let Some((_, ref color)) = self.comment && chars[index..].starts_with(&prefix) {
+                res.push(TextSpan::new(index..chars.len(), color.clone()));
+                break;
+            }
+            if let Some(ref color) = self.string_color && chars[index] == '"' {
+                let mut end = index + 1;
+                while end < chars.len() && chars[end] != '"' {
+                    if chars[end] == '\\' { end += 1; }
+                    end += 1;
+                }
+                let end = (end + 1).min(chars.len());
+                res.push(TextSpan::new(index..end, color.clone()));
+                index = end;
+                continue;
+            }
+            if chars[index].is_alphanumeric() || chars[index] == '_' {
+                let start = index;
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') { index += 1; }
+                let word: String = chars[start..index].iter().collect();
+                if self.keywords.contains(&word) { res.push(TextSpan::new(start..index, self.keyword_color.clone())); }
+                continue;
+            }
+            index += 1;
+        }
+        res
+    }
+}
+
+/// ### CodeEdit示例
+/// ```rust
+/// use xlui::*;
+///
+/// fn draw(ui:&mut Ui){
+///     let highlighter=KeywordHighlighter::new(&["fn","let","pub"],Color::BLUE)
+///         .with_string(Color::rgb(163,21,21))
+///         .with_comment("//",Color::rgb(0,128,0));
+///     let edit=CodeEdit::new("fn main() {\n    let a = 1;\n}")
+///         //设置高亮
+///         .with_highlighter(highlighter)
+///         //缩进宽度
+///         .with_tab_size(4)
+///         //字体，默认使用等宽字体
+///         .with_font(&Font::monospace().unwrap())
+///         .with_rows(10)
+///         .with_width(400.0);
+///     ui.add(edit);
+/// }
+/// ```
+pub struct CodeEdit {
+    edit: TextEdit,
+    gutter: TextBuffer,
+    gutter_render: RenderParam,
+    line_render: RenderParam,
+    bracket_renders: Vec<RenderParam>,
+    highlighter: Option<Box<dyn Highlighter>>,
+    highlight_cache: HashMap<String, Vec<TextSpan>>,
+    tab_size: usize,
+    line_count: usize,
+}
+
+impl CodeEdit {
+    pub fn new(text: impl ToString) -> CodeEdit {
+        let mut edit = TextEdit::multi_edit(text);
+        edit.char_layout.buffer.set_wrap(TextWrap::NoWrap);
+        let gutter_style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(245, 245, 245),
+            border: Border::same(0.0),
+            radius: Radius::same(0),
+            shadow: Shadow::new(),
+        });
+        let line_style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(144, 209, 255, 40),
+            border: Border::same(0.0),
+            radius: Radius::same(0),
+            shadow: Shadow::new(),
+        });
+        let bracket_style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(0, 0, 0, 20),
+            border: Border::same(1.0).color(Color::rgb(160, 160, 160)),
+            radius: Radius::same(1),
+            shadow: Shadow::new(),
+        });
+        CodeEdit {
+            edit,
+            gutter: TextBuffer::new(RichText::new("1").color(Color::rgb(150, 150, 150))),
+            gutter_render: RenderParam::new(Shape::rectangle()).with_style(gutter_style),
+            line_render: RenderParam::new(Shape::rectangle()).with_style(line_style),
+            bracket_renders: vec![
+                RenderParam::new(Shape::rectangle()).with_style(bracket_style.clone()),
+                RenderParam::new(Shape::rectangle()).with_style(bracket_style),
+            ],
+            highlighter: None,
+            highlight_cache: HashMap::new(),
+            tab_size: 4,
+            line_count: 0,
+        }
+    }
+
+    pub fn with_highlighter(mut self, highlighter: impl Highlighter + 'static) -> Self {
+        self.highlighter = Some(Box::new(highlighter));
+        self
+    }
+
+    pub fn set_highlighter(&mut self, highlighter: impl Highlighter + 'static) {
+        self.highlighter = Some(Box::new(highlighter));
+        self.highlight_cache.clear();
+        self.edit.state.changed = true;
+    }
+
+    ///Tab插入的空格数
+    pub fn with_tab_size(mut self, tab_size: usize) -> Self {
+        self.tab_size = tab_size.max(1);
+        self
+    }
+
+    ///使用指定字体的名称和大小
+    pub fn with_font(mut self, font: &Font) -> Self {
+        self.edit.char_layout.buffer.text.family = Some(font.family().to_string());
+        self.edit.char_layout.buffer.text.size = Some(font.size());
+        self
+    }
+
+    pub fn with_rows(mut self, row: usize) -> Self {
+        self.edit = self.edit.with_rows(row.max(2));
+        self
+    }
+
+    pub fn with_width(mut self, w: f32) -> Self {
+        self.edit = self.edit.with_width(w);
+        self
+    }
+
+    pub fn with_id(mut self, id: impl ToString) -> Self {
+        self.edit = self.edit.with_id(id);
+        self
+    }
+
+    pub fn connect<A: 'static>(mut self, f: fn(&mut A, &mut Ui, String)) -> Self {
+        self.edit = self.edit.connect(f);
+        self
+    }
+
+    pub fn text(&self) -> String {
+        self.edit.text()
+    }
+
+    ///内部的TextEdit，可用于查找替换等
+    pub fn text_edit(&mut self) -> &mut TextEdit {
+        &mut self.edit
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        let text = &mut self.edit.char_layout.buffer.text;
+        if text.family.is_none() { text.family = Some(ui.context.font.monospace_family()); }
+        self.gutter.text.family = text.family.clone();
+        self.gutter.text.size = text.size;
+        self.edit.init(ui, true);
+        self.gutter.init(ui);
+    }
+
+    ///按行数更新行号及行号栏宽度
+    fn update_gutter(&mut self, ui: &mut Ui) {
+        let count = self.edit.char_layout.buffer.lines.len();
+        if count == self.line_count { return; }
+        self.line_count = count;
+        let digits = count.to_string().len().max(2);
+        let numbers: Vec<String> = (1..=count).map(|x| format!("{:>digits$}", x)).collect();
+        self.gutter.update_buffer_text(ui, &numbers.join("\n"));
+        let padding = self.edit.char_layout.buffer.geometry.padding().clone();
+        self.edit.char_layout.buffer.geometry.set_padding(Padding {
+            left: self.gutter.text.width + 12.0,
+            ..padding
+        });
+    }
+
+    ///仅对可见行执行高亮，结果按行内容缓存
+    fn update_highlight(&mut self, ui: &mut Ui) {
+        let highlighter = match self.highlighter {
+            None => return,
+            Some(ref mut highlighter) => highlighter,
+        };
+        let buffer = &self.edit.char_layout.buffer;
+        if buffer.text.height <= 0.0 { return; }
+        let first = (-buffer.clip_y / buffer.text.height).floor().max(0.0) as usize;
+        let count = (buffer.geometry.context_rect().height() / buffer.text.height).ceil() as usize + 1;
+        if self.highlight_cache.len() > HIGHLIGHT_CACHE_SIZE { self.highlight_cache.clear(); }
+        let mut changed = false;
+        let mut spans = vec![];
+        for line in buffer.lines.iter().skip(first).take(count) {
+            let text: String = line.chars.iter().map(|x| x.cchar).collect();
+            let line_spans = self.highlight_cache.entry(text).or_insert_with_key(|text| highlighter.highlight_line(text));
+            changed |= line.spans != *line_spans;
+            spans.push(line_spans.clone());
+        }
+        if !changed { return; }
+        self.edit.char_layout.buffer.set_spans(ui, first, spans);
+    }
+
+    ///index处括号的匹配括号，只在visible范围内查找
+    fn match_bracket(source: &PieceTable, index: usize, visible: &Range<usize>) -> Option<usize> {
+        if !visible.contains(&index) { return None; }
+        let c = source.chars_from(index).next()?;
+        let (open, close) = *BRACKETS.iter().find(|(open, close)| *open == c || *close == c)?;
+        let mut depth = 0;
+        if c == open {
+            for (i, x) in source.chars_from(index).take(visible.end - index).enumerate() {
+                if x == open { depth += 1; } else if x == close { depth -= 1; }
+                if depth == 0 { return Some(index + i); }
+            }
+        } else {
+            for (i, x) in source.chars_before(index + 1).take(index + 1 - visible.start).enumerate() {
+                if x == close { depth += 1; } else if x == open { depth -= 1; }
+                if depth == 0 { return Some(index - i); }
+            }
+        }
+        None
+    }
+
+    ///光标前或光标处括号及其匹配括号的字符索引，只匹配可见行中的括号
+    fn bracket_pair(&self) -> Option<(usize, usize)> {
+        let cchar = &self.edit.char_layout;
+        let line_height = cchar.buffer.text.height;
+        if line_height <= 0.0 { return None; }
+        let lines = cchar.buffer.lines.len();
+        let first = ((cchar.offset.y / line_height).floor().max(0.0) as usize).min(lines);
+        let count = (cchar.buffer.geometry.context_rect().height() / line_height).ceil() as usize + 1;
+        let visible = cchar.index_by_position(first, 0)..cchar.index_by_position((first + count).min(lines), 0);
+        let cursor = &self.edit.cursor_render;
+        let index = cchar.index_by_position(cursor.vert, cursor.horiz);
+        for index in [index.checked_sub(1), Some(index)].into_iter().flatten() {
+            if let Some(other) = CodeEdit::match_bracket(&cchar.source, index, &visible) { return Some((index, other)); }
+        }
+        None
+    }
+
+    fn update_renders(&mut self) {
+        let cchar = &self.edit.char_layout;
+        let visual_rect = self.edit.visual.rect().clone();
+        let rect = cchar.buffer.geometry.context_rect();
+        let line_height = cchar.buffer.text.height;
+        let gutter_right = rect.dx().min - 6.0;
+        let gutter_rect = self.gutter_render.rect_mut();
+        *gutter_rect = visual_rect.clone();
+        gutter_rect.set_x_max(gutter_right);
+        gutter_rect.add_min_x(1.0);
+        gutter_rect.add_min_y(1.0);
+        gutter_rect.add_max_y(-1.0);
+        let mut gutter_rect = rect.clone();
+        gutter_rect.set_x_min(visual_rect.dx().min + 6.0);
+        gutter_rect.set_x_max(gutter_right);
+        self.gutter.geometry.set_fix_height(rect.height());
+        self.gutter.geometry.offset_to_rect(&gutter_rect);
+        self.gutter.clip_y = cchar.buffer.clip_y;
+
+        let y = rect.dy().min + self.edit.cursor_render.vert as f32 * line_height - cchar.offset.y;
+        let line_rect = self.line_render.rect_mut();
+        line_rect.set_x_min(gutter_right);
+        line_rect.set_x_max(visual_rect.dx().max - 1.0);
+        line_rect.set_y_min(y.max(rect.dy().min));
+        line_rect.set_y_max((y + line_height).min(rect.dy().max));
+
+        let pair = self.bracket_pair();
+        for (index, render) in self.bracket_renders.iter_mut().enumerate() {
+            let char_index = pair.map(|(current, other)| if index == 0 { current } else { other });
+            let render_rect = render.rect_mut();
+            render_rect.set_size(0.0, 0.0);
+            let (vert, horiz) = match char_index {
+                None => continue,
+                Some(char_index) => cchar.position_by_index(char_index),
+            };
+            let line = &cchar.buffer.lines[vert];
+            if horiz >= line.len() { continue; }
+            let x = rect.dx().min + line.get_width_in_char(horiz) + cchar.offset.x;
+            let y = rect.dy().min + vert as f32 * line_height - cchar.offset.y;
+            if x < rect.dx().min || x + line.chars[horiz].width > rect.dx().max || y < rect.dy().min || y + line_height > rect.dy().max + 1.0 { continue; }
+            render_rect.set_x_min(x);
+            render_rect.set_x_max(x + line.chars[horiz].width);
+            render_rect.set_y_min(y);
+            render_rect.set_y_max(y + line_height);
+        }
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        self.update_gutter(ui);
+        self.edit.update_buffer(ui);
+        self.update_highlight(ui);
+        if !ui.widget_changed.unchanged() { self.update_renders(); }
+        let state = &self.edit.state;
+        self.edit.visual.draw(ui, state.disabled, state.hovered, state.focused, false);
+        self.gutter_render.draw(ui, false, false, false);
+        if self.edit.state.focused { self.line_render.draw(ui, false, false, false); }
+        for render in self.bracket_renders.iter_mut() {
+            if render.rect().width() == 0.0 { continue; }
+            render.draw(ui, false, false, false);
+        }
+        self.edit.draw_context(ui);
+        self.gutter.redraw(ui);
+    }
+
+    ///当前行光标前的缩进
+    fn current_indent(&self) -> String {
+        let cursor = &self.edit.cursor_render;
+        let line = &self.edit.char_layout.buffer.lines[cursor.vert];
+        line.chars[..cursor.horiz].iter().map(|x| x.cchar).take_while(|x| *x == ' ' || *x == '\t').collect()
+    }
+
+    ///处理缩进相关按键，返回是否已处理
+    fn key_input(&mut self, key: &Key, ui: &mut Ui) -> bool {
+        let cursor = &self.edit.cursor_render;
+        let has_selected = self.edit.select_render.has_selected;
+        match key {
+            Key::Tab => {
+                let count = self.tab_size - cursor.horiz % self.tab_size;
+                self.edit.insert_text(ui, &" ".repeat(count));
+            }
+            Key::Enter => {
+                let mut indent = self.current_indent();
+                let line = &self.edit.char_layout.buffer.lines[cursor.vert];
+                if cursor.horiz > 0 && BRACKETS.iter().any(|(open, _)| *open == line.chars[cursor.horiz - 1].cchar) {
+                    indent += &" ".repeat(self.tab_size);
+                }
+                self.edit.insert_text(ui, &format!("\n{}", indent));
+            }
+            Key::Backspace if !has_selected && cursor.horiz > 0 && self.current_indent().chars().count() == cursor.horiz && self.current_indent().chars().all(|x| x == ' ') => {
+                let count = (cursor.horiz - 1) % self.tab_size + 1;
+                for _ in 0..count {
+                    self.edit.char_layout.remove_chars_before_cursor(ui, &mut self.edit.cursor_render, &mut self.edit.select_render);
+                }
+                self.edit.char_layout.buffer.clip_x = self.edit.char_layout.offset.x;
+            }
+            _ => return false,
+        }
+        self.edit.state.changed = true;
+        self.edit.select_render.reset(&self.edit.cursor_render);
+        self.edit.text_changed(ui);
+        true
+    }
+}
+
+impl Widget for CodeEdit {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Init => self.init(ui),
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::KeyRelease(ref mut key) if self.edit.state.focused => {
+                let key = mem::take(key);
+                if !self.key_input(&key, ui) {
+                    ui.update_type = UpdateType::KeyRelease(key);
+                    self.edit.update(ui);
+                }
+            }
+            _ => { self.edit.update(ui); }
+        }
+        Response::new(&self.edit.id, WidgetSize::same(self.edit.visual.rect().width(), self.edit.visual.rect().height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        self.edit.geometry()
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        self.edit.state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_keywords_strings_and_comment() {
+        let mut highlighter = KeywordHighlighter::new(&["let"], Color::BLUE)
+            .with_string(Color::RED)
+            .with_comment("//", Color::GREEN);
+        let spans = highlighter.highlight_line("let s = \"a//b\"; // 注释");
+        let ranges: Vec<_> = spans.iter().map(|x| x.range.clone()).collect();
+        assert_eq!(ranges, vec![0..3, 8..14, 16..21]);
+    }
+}
@@ -248,7 +248,7 @@ impl EditCursor {
             self.horiz = horiz;
             self.offset.x = sum_width + cchar.offset.x;
         }
-        self.offset.y = self.line_height * line_index as f32;
+        self.offset.y = self.line_height * line_index as f32 - cchar.offset.y;
         if self.offset.x + self.min_pos.x > self.max_pos.x { self.offset.x = self.max_pos.x - self.min_pos.x; }
         if self.offset.y + self.min_pos.y > self.max_pos.y { self.offset.y = self.max_pos.y - self.min_pos.y; }
         println!("1111111111111111111111111111-{}-{}", self.vert, self.horiz);
@@ -264,7 +264,7 @@ impl EditCursor {
         self.horiz = horiz;
         self.vert = vert;
         self.offset.x = cchar.buffer.lines[vert].get_width_in_char(horiz) + cchar.offset.x;
-        self.offset.y = vert as f32 * self.line_height;
+        self.offset.y = vert as f32 * self.line_height - cchar.offset.y;
         self.changed = true;
     }
 }
\ No newline at end of file
@@ -0,0 +1,305 @@
+use crate::render::{RenderParam, VisualStyle, WidgetStyle};
+use crate::shape::Shape;
+use crate::size::border::Border;
+use crate::size::radius::Radius;
+use crate::style::color::Color;
+use crate::text::piece::PieceTable;
+use crate::ui::Ui;
+use crate::widgets::textedit::buffer::{CharBuffer, SourceEdit};
+use crate::Shadow;
+use std::ops::Range;
+
+///文本查找选项
+#[derive(Clone, Default)]
+pub struct FindOptions {
+    ///区分大小写
+    pub case_sensitive: bool,
+    ///全词匹配
+    pub whole_word: bool,
+}
+
+impl FindOptions {
+    pub fn new() -> FindOptions {
+        FindOptions::default()
+    }
+
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+
+    pub fn whole_word(mut self) -> Self {
+        self.whole_word = true;
+        self
+    }
+}
+
+pub(crate) struct EditFind {
+    pub(crate) pattern: String,
+    pub(crate) options: FindOptions,
+    pub(crate) matches: Vec<Range<usize>>,
+    pub(crate) current: Option<usize>,
+    style: VisualStyle,
+    renders: Vec<(usize, RenderParam)>,
+    ///renders中本次绘制使用的数量
+    visible: usize,
+}
+
+impl EditFind {
+    pub fn new() -> EditFind {
+        let mut style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(255, 200, 0, 90),
+            border: Border::same(0.0),
+            radius: Radius::same(0),
+            shadow: Shadow::new(),
+        });
+        style.hovered.fill = Color::rgba(255, 140, 0, 160);
+        EditFind {
+            pattern: String::new(),
+            options: FindOptions::default(),
+            matches: vec![],
+            current: None,
+            style,
+            renders: vec![],
+            visible: 0,
+        }
+    }
+
+    ///查找全部匹配项，返回字符索引范围
+    pub fn find(text: &str, pattern: &str, options: &FindOptions) -> Vec<Range<usize>> {
+        let fold = |c: char| if options.case_sensitive { c } else { c.to_lowercase().next().unwrap_or(c) };
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let chars: Vec<char> = text.chars().collect();
+        let pattern: Vec<char> = pattern.chars().map(fold).collect();
+        let mut res = vec![];
+        if pattern.is_empty() { return res; }
+        let mut index = 0;
+        while index + pattern.len() <= chars.len() {
+            let end = index + pattern.len();
+            let matched = chars[index..end].iter().zip(pattern.iter()).all(|(c, p)| fold(*c) == *p);
+            let bounded = !options.whole_word || ((index == 0 || !is_word(chars[index - 1])) && (end == chars.len() || !is_word(chars[end])));
+            if matched && bounded {
+                res.push(index..end);
+                index = end;
+            } else {
+                index += 1;
+            }
+        }
+        res
+    }
+
+    ///在source的字符范围内查找，range需按行对齐。查找文本不含换行时逐行查找，避免拼接完整文本
+    pub fn find_in(source: &PieceTable, range: Range<usize>, pattern: &str, options: &FindOptions) -> Vec<Range<usize>> {
+        if pattern.contains('\n') {
+            let found = EditFind::find(&source.slice(range.clone()), pattern, options);
+            return found.into_iter().map(|x| x.start + range.start..x.end + range.start).collect();
+        }
+        let mut res = vec![];
+        let mut line = String::new();
+        let mut start = range.start;
+        for (index, c) in source.chars_from(range.start).take(range.len()).enumerate() {
+            if c != '\n' {
+                line.push(c);
+                continue;
+            }
+            res.extend(EditFind::find(&line, pattern, options).into_iter().map(|x| x.start + start..x.end + start));
+            line.clear();
+            start = range.start + index + 1;
+        }
+        res.extend(EditFind::find(&line, pattern, options).into_iter().map(|x| x.start + start..x.end + start));
+        res
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.pattern.is_empty()
+    }
+
+    ///重新查找，并保留仍然存在的当前匹配项
+    pub fn research(&mut self, source: &PieceTable) {
+        let current = self.current.and_then(|x| self.matches.get(x).cloned());
+        self.matches = EditFind::find_in(source, 0..source.len(), &self.pattern, &self.options);
+        self.current = current.and_then(|x| self.matches.iter().position(|m| *m == x));
+    }
+
+    ///按文本修改更新匹配项，只重新查找修改所在的逻辑行。source为全部修改后的文本
+    pub fn apply_edits(&mut self, source: &PieceTable, edits: &[SourceEdit]) {
+        if edits.is_empty() || !self.is_active() { return; }
+        //匹配项可能跨行，无法按行更新
+        if self.pattern.contains('\n') { return self.research(source); }
+        let mut current = self.current.and_then(|x| self.matches.get(x).cloned());
+        let mut dirty: Vec<Range<usize>> = vec![];
+        for edit in edits {
+            let removed_end = edit.start + edit.removed;
+            let keep = |x: &Range<usize>| x.end <= edit.start || x.start >= removed_end;
+            self.matches.retain(keep);
+            self.matches.iter_mut().for_each(|x| *x = edit.map_range(x));
+            current = current.filter(keep).map(|x| edit.map_range(&x));
+            dirty.iter_mut().for_each(|x| *x = edit.map(x.start)..edit.map(x.end));
+            dirty.push(edit.start..edit.start + edit.inserted);
+        }
+        //扩展到所在的逻辑行后合并
+        let mut lines: Vec<Range<usize>> = dirty.into_iter().map(|x| {
+            let start = x.start - source.chars_before(x.start).take_while(|c| *c != '\n').count();
+            let end = x.end + source.chars_from(x.end).take_while(|c| *c != '\n').count();
+            start..end
+        }).collect();
+        lines.sort_by_key(|x| x.start);
+        let mut merged: Vec<Range<usize>> = vec![];
+        for line in lines {
+            match merged.last_mut() {
+                Some(last) if line.start <= last.end => last.end = last.end.max(line.end),
+                _ => merged.push(line),
+            }
+        }
+        for range in merged {
+            self.matches.retain(|x| x.end <= range.start || x.start >= range.end);
+            let position = self.matches.partition_point(|x| x.start < range.start);
+            let found = EditFind::find_in(source, range, &self.pattern, &self.options);
+            self.matches.splice(position..position, found);
+        }
+        self.current = current.and_then(|x| self.matches.iter().position(|m| *m == x));
+    }
+
+    pub fn clear(&mut self) {
+        self.pattern.clear();
+        self.matches.clear();
+        self.current = None;
+        self.visible = 0;
+    }
+
+    ///下一个匹配项，无当前项时从index处开始
+    pub fn next(&mut self, index: usize) -> Option<Range<usize>> {
+        if self.matches.is_empty() {
+            self.current = None;
+            return None;
+        }
+        let next = match self.current {
+            Some(current) => (current + 1) % self.matches.len(),
+            None => self.matches.iter().position(|x| x.start >= index).unwrap_or(0),
+        };
+        self.current = Some(next);
+        Some(self.matches[next].clone())
+    }
+
+    ///上一个匹配项，无当前项时从index处开始
+    pub fn previous(&mut self, index: usize) -> Option<Range<usize>> {
+        if self.matches.is_empty() {
+            self.current = None;
+            return None;
+        }
+        let previous = match self.current {
+            Some(current) => (current + self.matches.len() - 1) % self.matches.len(),
+            None => self.matches.iter().rposition(|x| x.end <= index).unwrap_or(self.matches.len() - 1),
+        };
+        self.current = Some(previous);
+        Some(self.matches[previous].clone())
+    }
+
+    ///只为可视行内的匹配项生成矩形，按行二分查找匹配项，已创建的绘制对象重复使用
+    pub fn update_renders(&mut self, cchar: &CharBuffer) {
+        self.visible = 0;
+        let rect = cchar.buffer.geometry.context_rect();
+        let line_height = cchar.buffer.text.height;
+        let lines = &cchar.buffer.lines;
+        if self.matches.is_empty() || line_height <= 0.0 || lines.is_empty() { return; }
+        let first = ((cchar.offset.y / line_height).floor().max(0.0) as usize).min(lines.len());
+        let count = (rect.height() / line_height).ceil() as usize + 1;
+        let mut line_start = cchar.index_by_position(first, 0);
+        for (vert, line) in lines.iter().enumerate().skip(first).take(count) {
+            let line_end = line_start + line.len();
+            let y = rect.dy().min + vert as f32 * line_height - cchar.offset.y;
+            let shown = y >= rect.dy().min && y + line_height <= rect.dy().max + 1.0;
+            let mut index = self.matches.partition_point(|x| x.end <= line_start);
+            while shown && index < self.matches.len() && self.matches[index].start < line_end {
+                let range = &self.matches[index];
+                let start = range.start.saturating_sub(line_start);
+                let end = (range.end - line_start).min(line.len());
+                let x_min = (rect.dx().min + line.get_width_in_char(start) + cchar.offset.x).max(rect.dx().min);
+                let x_max = (rect.dx().min + line.get_width_in_char(end) + cchar.offset.x).min(rect.dx().max);
+                if x_max > x_min {
+                    if self.renders.len() == self.visible {
+                        self.renders.push((index, RenderParam::new(Shape::rectangle()).with_style(self.style.clone())));
+                    }
+                    let (render_index, render) = &mut self.renders[self.visible];
+                    *render_index = index;
+                    render.rect_mut().set_x_min(x_min);
+                    render.rect_mut().set_x_max(x_max);
+                    render.rect_mut().set_y_min(y);
+                    render.rect_mut().set_y_max(y + line_height);
+                    self.visible += 1;
+                }
+                index += 1;
+            }
+            line_start = line_end + if line.auto_wrap { 0 } else { 1 };
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut Ui) {
+        for (index, render) in self.renders.iter_mut().take(self.visible) {
+            render.draw(ui, false, self.current == Some(*index), false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditFind, FindOptions};
+    use crate::text::piece::PieceTable;
+    use crate::widgets::textedit::buffer::SourceEdit;
+
+    fn searched(text: &str, pattern: &str, options: FindOptions) -> (PieceTable, EditFind) {
+        let source = PieceTable::new(text);
+        let mut find = EditFind::new();
+        find.pattern = pattern.to_string();
+        find.options = options;
+        find.research(&source);
+        (source, find)
+    }
+
+    fn replace(source: &mut PieceTable, start: usize, removed: usize, text: &str) -> SourceEdit {
+        source.remove(start..start + removed);
+        source.insert(start, text);
+        SourceEdit { start, removed, inserted: text.chars().count() }
+    }
+
+    #[test]
+    fn apply_edits_matches_research() {
+        let (mut source, mut find) = searched("foo bar\nfoo\nbaz foo foo\n", "foo", FindOptions::new());
+        assert_eq!(find.matches, vec![0..3, 8..11, 16..19, 20..23]);
+        find.current = Some(2);
+        let edits = vec![
+            //第二行的foo被改为fo
+            replace(&mut source, 10, 1, ""),
+            //第一行插入新的foo
+            replace(&mut source, 4, 0, "foo "),
+            //新增一行
+            replace(&mut source, 27, 0, "xfoo"),
+        ];
+        find.apply_edits(&source, &edits);
+        assert_eq!(source.text(), "foo foo bar\nfo\nbaz foo foo\nxfoo");
+        assert_eq!(find.matches, EditFind::find(&source.text(), "foo", &find.options));
+        //当前匹配项随修改后移
+        assert_eq!(find.current.map(|x| find.matches[x].clone()), Some(19..22));
+    }
+
+    #[test]
+    fn apply_edits_drops_removed_current_and_respects_options() {
+        let (mut source, mut find) = searched("Foo food\nfoo", "foo", FindOptions::new().whole_word());
+        assert_eq!(find.matches, vec![0..3, 9..12]);
+        find.current = Some(1);
+        let edits = vec![replace(&mut source, 9, 3, "foox"), replace(&mut source, 4, 4, "foo")];
+        find.apply_edits(&source, &edits);
+        assert_eq!(source.text(), "Foo foo\nfoox");
+        assert_eq!(find.matches, vec![0..3, 4..7]);
+        assert_eq!(find.current, None);
+    }
+
+    #[test]
+    fn apply_edits_with_multiline_pattern_researches() {
+        let (mut source, mut find) = searched("a\nb\na\nb", "a\nb", FindOptions::new());
+        assert_eq!(find.matches, vec![0..3, 4..7]);
+        let edits = vec![replace(&mut source, 2, 0, "a\n")];
+        find.apply_edits(&source, &edits);
+        assert_eq!(find.matches, EditFind::find(&source.text(), "a\nb", &find.options));
+    }
+}
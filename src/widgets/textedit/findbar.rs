@@ -0,0 +1,180 @@
+use crate::frame::context::UpdateType;
+use crate::key::Key;
+use crate::render::{RenderParam, VisualStyle, WidgetStyle};
+use crate::shape::Shape;
+use crate::size::border::Border;
+use crate::size::radius::Radius;
+use crate::size::rect::Rect;
+use crate::style::color::Color;
+use crate::ui::Ui;
+use crate::widgets::button::Button;
+use crate::widgets::textedit::TextEdit;
+use crate::widgets::{Widget, WidgetChange};
+use crate::Shadow;
+use std::mem;
+use std::sync::{Arc, RwLock};
+
+const PADDING: f32 = 4.0;
+const ROW: f32 = 24.0;
+const EDIT_WIDTH: f32 = 160.0;
+const ARROW_WIDTH: f32 = 24.0;
+const REPLACE_WIDTH: f32 = 44.0;
+const REPLACE_ALL_WIDTH: f32 = 70.0;
+const WIDTH: f32 = PADDING * 4.0 + EDIT_WIDTH + REPLACE_WIDTH + REPLACE_ALL_WIDTH;
+const HEIGHT: f32 = PADDING * 3.0 + ROW * 2.0;
+
+pub(crate) enum FindAction {
+    Pattern(String),
+    Next,
+    Previous,
+    Replace,
+    ReplaceAll,
+    Close,
+}
+
+fn rect_at(x: f32, y: f32, width: f32, height: f32) -> Rect {
+    let mut rect = Rect::new();
+    rect.set_x_min(x);
+    rect.set_x_max(x + width);
+    rect.set_y_min(y);
+    rect.set_y_max(y + height);
+    rect
+}
+
+///查找替换栏，显示在多行文本框的右上角
+pub(crate) struct FindBar {
+    pattern_edit: TextEdit,
+    replace_edit: TextEdit,
+    //上一个、下一个、关闭、替换、全部替换
+    buttons: Vec<(Button, f32)>,
+    actions: Arc<RwLock<Vec<FindAction>>>,
+    background: RenderParam,
+}
+
+impl FindBar {
+    pub fn new(ui: &mut Ui, pattern: &str) -> FindBar {
+        let actions = Arc::new(RwLock::new(vec![]));
+        let pattern_actions = actions.clone();
+        let mut pattern_edit = TextEdit::single_edit(pattern).with_width(EDIT_WIDTH).connect_inner(move |text| {
+            pattern_actions.write().unwrap().push(FindAction::Pattern(text.to_string()));
+        });
+        let mut replace_edit = TextEdit::single_edit("").with_width(EDIT_WIDTH);
+        let buttons = [("↑", ARROW_WIDTH), ("↓", ARROW_WIDTH), ("×", ARROW_WIDTH), ("替换", REPLACE_WIDTH), ("全部替换", REPLACE_ALL_WIDTH)];
+        let mut buttons: Vec<_> = buttons.into_iter().enumerate().map(|(index, (text, width))| {
+            let mut button = Button::new(text).width(width).height(ROW - 4.0);
+            let actions = actions.clone();
+            button.set_inner_callback(move || {
+                let action = match index {
+                    0 => FindAction::Previous,
+                    1 => FindAction::Next,
+                    2 => FindAction::Close,
+                    3 => FindAction::Replace,
+                    _ => FindAction::ReplaceAll,
+                };
+                actions.write().unwrap().push(action);
+            });
+            (button, width)
+        }).collect();
+        let previous = mem::replace(&mut ui.update_type, UpdateType::Init);
+        pattern_edit.update(ui);
+        replace_edit.update(ui);
+        for (button, _) in buttons.iter_mut() { button.update(ui); }
+        ui.update_type = previous;
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(240, 240, 240),
+            border: Border::same(1.0).color(Color::rgb(200, 200, 200)),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        FindBar {
+            pattern_edit,
+            replace_edit,
+            buttons,
+            actions,
+            background: RenderParam::new(Shape::rectangle()).with_style(style),
+        }
+    }
+
+    pub fn rect(&self) -> &Rect {
+        self.background.rect()
+    }
+
+    pub fn has_focus(&mut self) -> bool {
+        self.pattern_edit.state().focused || self.replace_edit.state().focused
+    }
+
+    ///查找框获得焦点，pattern不为空时替换查找框中的文本
+    pub fn focus_pattern(&mut self, ui: &mut Ui, pattern: Option<String>) {
+        if let Some(pattern) = pattern { self.pattern_edit.update_text(ui, pattern); }
+        self.replace_edit.state().focused = false;
+        self.pattern_edit.state().focused = true;
+        ui.context.window.ime().request_ime(true);
+        ui.context.window.request_redraw();
+    }
+
+    pub fn replace_text(&self) -> String {
+        self.replace_edit.text()
+    }
+
+    pub fn take_actions(&mut self) -> Vec<FindAction> {
+        mem::take(&mut *self.actions.write().unwrap())
+    }
+
+    ///将事件传给查找栏，返回事件是否由查找栏处理
+    pub fn update(&mut self, ui: &mut Ui) -> bool {
+        let consumed = match ui.update_type {
+            UpdateType::MousePress => ui.device.device_input.pressed_at(self.rect()),
+            UpdateType::MouseMove => ui.device.device_input.hovered_at(self.rect()),
+            UpdateType::MouseRelease => ui.device.device_input.click_at(self.rect()),
+            UpdateType::KeyPress(Key::CtrlF | Key::F3 | Key::ShiftF3) => return false,
+            UpdateType::KeyRelease(Key::Escape) if self.has_focus() => {
+                self.actions.write().unwrap().push(FindAction::Close);
+                return true;
+            }
+            UpdateType::KeyRelease(Key::Enter) if self.has_focus() => {
+                let action = if self.pattern_edit.state().focused { FindAction::Next } else { FindAction::Replace };
+                self.actions.write().unwrap().push(action);
+                return true;
+            }
+            UpdateType::KeyPress(_) | UpdateType::KeyRelease(_) | UpdateType::IME(_) | UpdateType::Clipboard(_) => {
+                if !self.has_focus() { return false; }
+                true
+            }
+            _ => return false,
+        };
+        self.pattern_edit.update(ui);
+        self.replace_edit.update(ui);
+        for (button, _) in self.buttons.iter_mut() { button.update(ui); }
+        consumed
+    }
+
+    ///在文本框rect的右上角绘制查找栏
+    pub fn redraw(&mut self, ui: &mut Ui, rect: &Rect) {
+        let previous_rect = ui.draw_rect.clone();
+        let changed = ui.widget_changed;
+        let x = (rect.dx().max - WIDTH - PADDING).max(rect.dx().min);
+        let y = rect.dy().min + PADDING;
+        *self.background.rect_mut() = rect_at(x, y, WIDTH, HEIGHT);
+        self.background.draw(ui, false, false, false);
+        let left = x + PADDING;
+        let top = y + PADDING;
+        ui.widget_changed = changed | WidgetChange::Position;
+        ui.draw_rect = rect_at(left, top, EDIT_WIDTH, ROW);
+        self.pattern_edit.update(ui);
+        ui.widget_changed = changed | WidgetChange::Position;
+        ui.draw_rect = rect_at(left, top + ROW + PADDING, EDIT_WIDTH, ROW);
+        self.replace_edit.update(ui);
+        let left = left + EDIT_WIDTH + PADDING;
+        let mut offset = 0.0;
+        for (index, (button, width)) in self.buttons.iter_mut().enumerate() {
+            if index == 3 { offset = 0.0; }
+            let top = if index < 3 { top } else { top + ROW + PADDING };
+            ui.widget_changed = changed | WidgetChange::Position;
+            ui.draw_rect = rect_at(left + offset, top + 2.0, *width, ROW - 4.0);
+            button.update(ui);
+            offset += *width + PADDING;
+        }
+        ui.widget_changed = changed;
+        ui.draw_rect = previous_rect;
+    }
+}
@@ -3,7 +3,7 @@ use crate::frame::context::{ContextUpdate, UpdateType};
 use crate::key::Key;
 use crate::layout::LayoutDirection;
 use crate::render::{Visual, VisualStyle, WidgetStyle};
-use crate::response::{Callback, Response};
+use crate::response::{ActionCallB, Callback, InnerTextCallB, Response};
 use crate::size::border::Border;
 use crate::size::radius::Radius;
 use crate::size::Geometry;
@@ -12,16 +12,22 @@ use crate::text::buffer::TextBuffer;
 use crate::ui::Ui;
 use crate::widgets::textedit::buffer::CharBuffer;
 use crate::widgets::textedit::cursor::EditCursor;
+use crate::widgets::textedit::find::{EditFind, FindOptions};
+use crate::widgets::textedit::findbar::{FindAction, FindBar};
 use crate::widgets::textedit::select::EditSelection;
 use crate::widgets::{Widget, WidgetChange, WidgetSize, WidgetState};
 use crate::window::ime::IMEData;
 use crate::window::ClipboardData;
 use crate::{App, Shadow, TextWrap};
 use std::mem;
+use std::ops::Range;
 
 pub(crate) mod buffer;
-mod select;
-mod cursor;
+pub(crate) mod select;
+pub(crate) mod cursor;
+pub(crate) mod find;
+pub(crate) mod findbar;
+pub(crate) mod code;
 
 #[derive(PartialEq)]
 enum EditKind {
@@ -34,11 +40,15 @@ enum EditKind {
 pub struct TextEdit {
     id: String,
     callback: Option<Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, String)>>,
+    changed_callback: Option<ActionCallB>,
+    inner_callback: Option<InnerTextCallB>,
     contact_ids: Vec<String>,
     visual: Visual,
     select_render: EditSelection,
     cursor_render: EditCursor,
     char_layout: CharBuffer,
+    find: EditFind,
+    find_bar: Option<Box<FindBar>>,
     desire_lines: usize,
     psd_buffer: TextBuffer,
     state: WidgetState,
@@ -62,11 +72,15 @@ impl TextEdit {
         TextEdit {
             id: crate::gen_unique_id(),
             callback: None,
+            changed_callback: None,
+            inner_callback: None,
             contact_ids: vec![],
             visual: Visual::new().with_enable().with_style(fill_style),
             select_render: EditSelection::new(),
             cursor_render: EditCursor::new(),
             char_layout: CharBuffer::new(text),
+            find: EditFind::new(),
+            find_bar: None,
             desire_lines: 8,
             psd_buffer: TextBuffer::new("🔒").with_align(Align::Center), //👁🔓
             state: WidgetState::default(),
@@ -83,6 +97,7 @@ impl TextEdit {
 
     pub fn password(mut self) -> TextEdit {
         self.char_layout.edit_kind = EditKind::Password;
+        self.char_layout.buffer.lazy = false;
         self
     }
 
@@ -108,13 +123,18 @@ impl TextEdit {
 
 
     pub(crate) fn update_text(&mut self, ui: &mut Ui, text: String) {
-        self.char_layout.buffer.update_buffer_text(ui, &text);
+        self.char_layout.set_source(&text);
+        if self.find.is_active() { self.find.research(&self.char_layout.source); }
+        //延迟测量时text.text可能已过期，不能用于判断是否变化
+        self.char_layout.buffer.set_text(text);
+        self.char_layout.buffer.change = true;
+        self.char_layout.buffer.update_buffer(ui);
         self.select_render.reset(&self.cursor_render);
         self.state.changed = true;
     }
 
     pub fn text(&self) -> String {
-        self.char_layout.buffer.lines.iter().map(|x| x.raw_text()).collect()
+        self.char_layout.source.text()
     }
 
     pub fn contact(mut self, id: impl ToString) -> Self {
@@ -126,12 +146,283 @@ impl TextEdit {
         self.callback = Some(Callback::create_textedit(f));
         self
     }
+
+    ///文本改变时调用f，不传递文本，需要时再通过text获取，适用于大文本
+    pub fn connect_changed<A: 'static>(mut self, f: fn(&mut A, &mut Ui)) -> Self {
+        self.changed_callback = Some(Callback::create_action(f));
+        self
+    }
+
+    pub(crate) fn connect_inner(mut self, callback: impl FnMut(&str) + 'static) -> Self {
+        self.inner_callback = Some(Box::new(callback));
+        self
+    }
     pub(crate) fn buffer(&mut self) -> &mut TextBuffer {
         &mut self.char_layout.buffer
     }
 
+    pub fn with_find_options(mut self, options: FindOptions) -> Self {
+        self.find.options = options;
+        self
+    }
+
+    pub fn set_find_options(&mut self, options: FindOptions) {
+        self.find.options = options;
+        self.find.current = None;
+        self.find.research(&self.char_layout.source);
+        self.state.changed = true;
+    }
+
+    ///按当前查找选项查找全部匹配项，返回字符索引范围
+    pub fn find(&self, pattern: &str) -> Vec<Range<usize>> {
+        EditFind::find_in(&self.char_layout.source, 0..self.char_layout.source.len(), pattern, &self.find.options)
+    }
+
+    ///设置查找文本，并高亮全部匹配项
+    pub fn set_find_text(&mut self, pattern: impl ToString) {
+        self.find.pattern = pattern.to_string();
+        self.find.current = None;
+        self.find.research(&self.char_layout.source);
+        self.state.changed = true;
+    }
+
+    ///清除查找文本及高亮
+    pub fn clear_find(&mut self) {
+        self.find.clear();
+        self.state.changed = true;
+    }
+
+    pub fn find_matches(&self) -> &[Range<usize>] {
+        &self.find.matches
+    }
+
+    ///当前匹配项在find_matches中的索引
+    pub fn current_match(&self) -> Option<usize> {
+        self.find.current
+    }
+
+    ///跳转到下一个匹配项
+    pub fn find_next(&mut self) -> Option<Range<usize>> {
+        let index = self.char_layout.index_by_position(self.cursor_render.vert, self.cursor_render.horiz);
+        self.find_from(index)
+    }
+
+    ///跳转到index处或之后的第一个匹配项
+    fn find_from(&mut self, index: usize) -> Option<Range<usize>> {
+        let range = self.find.next(index)?;
+        self.scroll_to_range(&range);
+        Some(range)
+    }
+
+    ///跳转到上一个匹配项
+    pub fn find_previous(&mut self) -> Option<Range<usize>> {
+        let (vert, horiz) = match self.find.current.and_then(|x| self.find.matches.get(x)) {
+            Some(range) => self.char_layout.position_by_index(range.start),
+            None => (self.cursor_render.vert, self.cursor_render.horiz),
+        };
+        let index = self.char_layout.index_by_position(vert, horiz);
+        let range = self.find.previous(index)?;
+        self.scroll_to_range(&range);
+        Some(range)
+    }
+
+    ///显示查找替换栏，存在单行选择时作为查找文本，仅用于多行文本框
+    pub fn show_find_bar(&mut self, ui: &mut Ui) {
+        if self.char_layout.edit_kind != EditKind::Multi { return; }
+        let select_text = self.char_layout.select_text(&self.select_render, &self.cursor_render);
+        let pattern = if select_text.is_empty() || select_text.contains('\n') { None } else { Some(select_text) };
+        if let Some(ref pattern) = pattern { self.set_find_text(pattern); }
+        let bar = self.find_bar.get_or_insert_with(|| Box::new(FindBar::new(ui, &self.find.pattern)));
+        bar.focus_pattern(ui, pattern);
+        self.state.focused = false;
+        self.state.changed = true;
+    }
+
+    ///关闭查找替换栏并清除查找高亮
+    pub fn close_find_bar(&mut self, ui: &mut Ui) {
+        if self.find_bar.take().is_none() { return; }
+        self.clear_find();
+        self.state.focused = true;
+        ui.context.window.ime().request_ime(true);
+        ui.context.window.request_redraw();
+    }
+
+    ///将事件传给查找栏并执行其中的操作，返回事件是否由查找栏处理
+    fn update_find_bar(&mut self, ui: &mut Ui) -> bool {
+        let Some(ref mut bar) = self.find_bar else { return false; };
+        let consumed = bar.update(ui);
+        if consumed && let UpdateType::MousePress = ui.update_type { self.state.focused = false; }
+        for action in bar.take_actions() {
+            match action {
+                FindAction::Pattern(pattern) => {
+                    //增量查找时从当前匹配项的起点开始，避免跳过仍然匹配的当前项
+                    let index = match self.find.current.and_then(|x| self.find.matches.get(x)) {
+                        Some(range) => range.start,
+                        None => self.char_layout.index_by_position(self.cursor_render.vert, self.cursor_render.horiz),
+                    };
+                    self.set_find_text(pattern);
+                    self.find_from(index);
+                }
+                FindAction::Next => { self.find_next(); }
+                FindAction::Previous => { self.find_previous(); }
+                FindAction::Replace => {
+                    let text = self.find_bar.as_ref().map(|x| x.replace_text()).unwrap_or_default();
+                    self.replace(ui, &text);
+                }
+                FindAction::ReplaceAll => {
+                    let text = self.find_bar.as_ref().map(|x| x.replace_text()).unwrap_or_default();
+                    self.replace_all(ui, &text);
+                }
+                FindAction::Close => self.close_find_bar(ui),
+            }
+            ui.context.window.request_redraw();
+        }
+        consumed
+    }
+
+    ///替换当前匹配项并跳转到下一个，无当前项时先跳转
+    pub fn replace(&mut self, ui: &mut Ui, text: &str) -> bool {
+        let range = match self.find.current.and_then(|x| self.find.matches.get(x).cloned()) {
+            None => match self.find_next() {
+                None => return false,
+                Some(range) => range,
+            },
+            Some(range) => range,
+        };
+        self.replace_range(ui, range, text);
+        self.find.current = None;
+        self.sync_find();
+        self.text_changed(ui);
+        self.find_next();
+        true
+    }
+
+    ///替换全部匹配项，返回替换数量
+    pub fn replace_all(&mut self, ui: &mut Ui, text: &str) -> usize {
+        let matches = mem::take(&mut self.find.matches);
+        for range in matches.iter().rev() {
+            self.replace_range(ui, range.clone(), text);
+        }
+        self.find.current = None;
+        self.char_layout.edits.clear();
+        self.find.research(&self.char_layout.source);
+        if !matches.is_empty() { self.text_changed(ui); }
+        matches.len()
+    }
+
+    fn replace_range(&mut self, ui: &mut Ui, range: Range<usize>, text: &str) {
+        self.measure_range(&range);
+        let (start_vert, start_horiz) = self.char_layout.position_by_index(range.start);
+        let (end_vert, end_horiz) = self.char_layout.position_by_index(range.end);
+        self.cursor_render.set_cursor(end_horiz, end_vert, &self.char_layout);
+        self.select_render.start_vert = start_vert;
+        self.select_render.start_horiz = start_horiz;
+        self.select_render.has_selected = true;
+        if text.is_empty() {
+            self.char_layout.remove_by_range(ui, &mut self.cursor_render, &mut self.select_render);
+        }
+        self.insert_text(ui, text);
+    }
+
+    ///在光标处插入文本，存在选择时先删除选择内容
+    pub(crate) fn insert_text(&mut self, ui: &mut Ui, text: &str) {
+        for c in text.chars() {
+            self.char_layout.inset_char(c, ui, &mut self.cursor_render, &mut self.select_render);
+        }
+        self.char_layout.buffer.clip_x = self.char_layout.offset.x;
+    }
+
+    ///滚动使字符范围处于可视区域，光标移至范围末尾
+    fn scroll_to_range(&mut self, range: &Range<usize>) {
+        self.measure_range(range);
+        let (start_vert, start_horiz) = self.char_layout.position_by_index(range.start);
+        let (end_vert, end_horiz) = self.char_layout.position_by_index(range.end);
+        let rect = self.char_layout.buffer.geometry.context_rect();
+        let line_height = self.char_layout.buffer.text.height;
+        let start_x = self.char_layout.buffer.lines[start_vert].get_width_in_char(start_horiz);
+        let end_x = self.char_layout.buffer.lines[end_vert].get_width_in_char(end_horiz);
+        let offset = &mut self.char_layout.offset;
+        if start_vert != end_vert || start_x + offset.x < 0.0 {
+            offset.x = -start_x;
+        } else if end_x + offset.x > rect.width() {
+            offset.x = rect.width() - end_x;
+        }
+        if offset.x > 0.0 { offset.x = 0.0; }
+        let top = start_vert as f32 * line_height;
+        let bottom = (end_vert + 1) as f32 * line_height;
+        if top < offset.y {
+            offset.y = top;
+        } else if bottom > offset.y + rect.height() {
+            offset.y = (bottom - rect.height()).max(0.0);
+        }
+        self.char_layout.buffer.clip_x = self.char_layout.offset.x;
+        self.char_layout.buffer.clip_y = -self.char_layout.offset.y;
+        self.cursor_render.set_cursor(end_horiz, end_vert, &self.char_layout);
+        self.select_render.reset(&self.cursor_render);
+        let mut select_rect = rect;
+        select_rect.add_min_y(-self.char_layout.offset.y);
+        self.select_render.update_position(select_rect);
+        self.state.changed = true;
+    }
+
+    ///按文本修改增量更新查找结果
+    fn sync_find(&mut self) {
+        let edits = mem::take(&mut self.char_layout.edits);
+        self.find.apply_edits(&self.char_layout.source, &edits);
+    }
+
+    pub(crate) fn text_changed(&mut self, ui: &mut Ui) {
+        ui.context.window.request_redraw();
+        if let Some(ref mut callback) = self.changed_callback {
+            let app = ui.app.take().unwrap();
+            callback(app, ui);
+            ui.app.replace(app);
+        }
+        //没有接收方时不需要拼接完整文本
+        if self.callback.is_none() && self.inner_callback.is_none() && self.contact_ids.is_empty() { return; }
+        let text = self.text();
+        if let Some(ref mut callback) = self.callback {
+            let app = ui.app.take().unwrap();
+            callback(app, ui, text.clone());
+            ui.app.replace(app);
+        }
+        if let Some(ref mut callback) = self.inner_callback { callback(&text); }
+        ui.send_updates(&self.contact_ids, ContextUpdate::String(text));
+    }
+
+
+    ///测量字符范围所在的行
+    fn measure_range(&mut self, range: &Range<usize>) {
+        let (start_vert, _) = self.char_layout.position_by_index(range.start);
+        let (end_vert, _) = self.char_layout.position_by_index(range.end);
+        self.measure_lines(start_vert..end_vert + 1);
+    }
+
+    fn measure_lines(&mut self, range: Range<usize>) -> bool {
+        self.char_layout.measure_lines(range, &mut self.cursor_render, &mut self.select_render)
+    }
+
+    ///可视区域的行范围
+    fn visible_lines(&self) -> Range<usize> {
+        let line_height = self.char_layout.buffer.text.height;
+        if line_height <= 0.0 { return 0..0; }
+        let first = (self.char_layout.offset.y / line_height).floor().max(0.0) as usize;
+        first..first + self.desire_lines + 1
+    }
+
+    ///垂直滚动文本，offset_y为正时向下滚动
+    fn scroll_by(&mut self, offset_y: f32) {
+        let line_height = self.char_layout.buffer.text.height;
+        let max = (self.char_layout.buffer.lines.len() as f32 * line_height - self.char_layout.buffer.geometry.context_height()).max(0.0);
+        self.char_layout.offset.y = (self.char_layout.offset.y + offset_y).clamp(0.0, max);
+        self.char_layout.buffer.clip_y = -self.char_layout.offset.y;
+        self.measure_lines(self.visible_lines());
+        self.cursor_render.set_cursor(self.cursor_render.horiz, self.cursor_render.vert, &self.char_layout);
+        self.state.changed = true;
+    }
 
     fn update_buffer(&mut self, ui: &mut Ui) {
+        if self.measure_lines(self.visible_lines()) { self.state.changed = true; }
         if self.state.changed { ui.widget_changed |= WidgetChange::Value; }
         self.state.changed = false;
         if ui.widget_changed.contains(WidgetChange::Position) {
@@ -141,7 +432,9 @@ impl TextEdit {
             cursor_rect.set_width(2.0);
             cursor_rect.set_height(self.char_layout.buffer.text.height);
             self.cursor_render.update_position(cursor_rect, &self.char_layout);
-            self.select_render.update_position(self.char_layout.buffer.geometry.context_rect());
+            let mut select_rect = self.char_layout.buffer.geometry.context_rect();
+            select_rect.add_min_y(-self.char_layout.offset.y);
+            self.select_render.update_position(select_rect);
             let mut psd_rect = self.visual.rect_mut().clone();
             psd_rect.set_x_direction(LayoutDirection::Max);
             psd_rect.add_min_y(2.0);
@@ -150,6 +443,10 @@ impl TextEdit {
 
         if ui.widget_changed.contains(WidgetChange::Value) {
             self.cursor_render.update();
+            self.sync_find();
+        }
+        if self.find.is_active() && !ui.widget_changed.unchanged() {
+            self.find.update_renders(&self.char_layout);
         }
     }
 
@@ -157,6 +454,7 @@ impl TextEdit {
         let line_height = self.char_layout.buffer.line_height(ui).unwrap();
         let height = line_height * self.desire_lines as f32 + 6.0;
         self.char_layout.buffer.geometry.set_fix_height(height);
+        self.char_layout.sync_text();
         self.char_layout.buffer.init(ui); //计算行高
         self.visual.rect_mut().set_size(self.char_layout.buffer.geometry.padding_width(), height);
     }
@@ -178,16 +476,16 @@ impl TextEdit {
 
     fn key_input(&mut self, key: Key, ui: &mut Ui) {
         self.state.changed = true;
+        let vert = self.cursor_render.vert;
+        self.measure_lines(vert.saturating_sub(1)..vert + 2);
         match key {
             Key::Backspace => {
                 self.char_layout.remove_chars_before_cursor(ui, &mut self.cursor_render, &mut self.select_render);
                 self.char_layout.buffer.clip_x = self.char_layout.offset.x;
             }
             Key::Enter => self.char_layout.inset_char('\n', ui, &mut self.cursor_render, &mut self.select_render),
-            Key::Space => {
-                self.char_layout.inset_char(' ', ui, &mut self.cursor_render, &mut self.select_render);
-                self.char_layout.buffer.clip_x = self.char_layout.offset.x;
-            }
+            Key::Space => self.insert_text(ui, " "),
+            Key::Tab => self.insert_text(ui, "\t"),
             Key::Home => {
                 self.char_layout.buffer.clip_x = 0.0;
                 self.char_layout.offset.x = 0.0;
@@ -204,8 +502,7 @@ impl TextEdit {
             Key::Delete => self.char_layout.remove_chars_after_cursor(ui, &mut self.cursor_render, &mut self.select_render),
             Key::Char(c) => {
                 println!("1输入字符: {:?}", c);
-                self.char_layout.inset_char(c, ui, &mut self.cursor_render, &mut self.select_render);
-                self.char_layout.buffer.clip_x = self.char_layout.offset.x;
+                self.insert_text(ui, &c.to_string());
             }
             Key::LeftArrow => {
                 if self.cursor_render.cursor_min() <= self.cursor_render.min_pos.x && let Some(cchar) = self.char_layout.previous_char(&self.cursor_render) {
@@ -227,30 +524,35 @@ impl TextEdit {
             _ => {}
         }
         self.select_render.reset(&self.cursor_render);
-        let text: String = self.char_layout.buffer.lines.iter().map(|x| x.raw_text()).collect();
-        if let Some(ref mut callback) = self.callback {
-            let app = ui.app.take().unwrap();
-            callback(app, ui, text.clone());
-            ui.app.replace(app);
-        }
-        ui.send_updates(&self.contact_ids, ContextUpdate::String(text));
-        ui.context.window.request_redraw();
+        self.text_changed(ui);
     }
 
     pub(crate) fn redraw(&mut self, ui: &mut Ui) {
+        //通过Ui::set_value设置的文本
+        if let Some(ContextUpdate::String(text)) = ui.context.updates.remove(&self.id) { self.update_text(ui, text); }
         self.update_buffer(ui);
         self.visual.draw(ui, self.state.disabled, self.state.hovered, self.state.focused, false);
+        self.draw_context(ui);
+    }
+
+    ///绘制选择、查找高亮、光标及文本
+    fn draw_context(&mut self, ui: &mut Ui) {
         self.select_render.render(ui, self.char_layout.buffer.lines.len());
+        if self.find.is_active() { self.find.render(ui); }
         if self.state.focused { self.cursor_render.render(ui); }
         self.char_layout.buffer.redraw(ui);
         if let EditKind::Password = self.char_layout.edit_kind {
             self.psd_buffer.redraw(ui);
         }
+        if let Some(ref mut bar) = self.find_bar { bar.redraw(ui, self.visual.rect()); }
     }
 }
 
 impl Widget for TextEdit {
     fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        if self.update_find_bar(ui) {
+            return Response::new(&self.id, WidgetSize::same(self.visual.rect().width(), self.visual.rect().height()));
+        }
         match ui.update_type {
             UpdateType::Draw => self.redraw(ui),
             UpdateType::Init => self.init(ui, true),
@@ -275,6 +577,10 @@ impl Widget for TextEdit {
                     ui.context.window.request_redraw();
                 }
             }
+            UpdateType::MouseWheel if self.char_layout.edit_kind != EditKind::Single && ui.device.device_input.hovered_at(self.visual.rect()) => {
+                self.scroll_by(-ui.device.device_input.mouse.delta_y() * 10.0);
+                ui.context.window.request_redraw();
+            }
             UpdateType::MouseRelease => {
                 let clicked = ui.device.device_input.click_at(self.visual.rect());
                 if self.state.on_clicked(clicked) && let EditKind::Password = self.char_layout.edit_kind {
@@ -287,7 +593,8 @@ impl Widget for TextEdit {
             }
             #[cfg(not(feature = "winit"))]
             UpdateType::KeyPress(ref mut key) => {
-                if self.state.focused {
+                let bar_focused = self.find_bar.as_mut().is_some_and(|x| x.has_focus());
+                if self.state.focused || bar_focused {
                     match key {
                         Key::CtrlC => {
                             println!("copy");
@@ -320,6 +627,8 @@ impl Widget for TextEdit {
                             ui.context.window.request_redraw();
                         }
                         Key::CtrlA => {
+                            let last = self.char_layout.buffer.lines.len() - 1;
+                            self.measure_lines(last..last + 1);
                             let horiz = self.char_layout.buffer.lines.last().unwrap().chars.len();
                             let vert = self.char_layout.buffer.lines.len() - 1;
                             if self.char_layout.buffer.lines.len() == 1 {
@@ -337,6 +646,18 @@ impl Widget for TextEdit {
                             self.select_render.select_by_ime(0, 0, &self.char_layout, &self.cursor_render);
                             ui.context.window.request_redraw();
                         }
+                        Key::CtrlF if self.char_layout.edit_kind == EditKind::Multi => {
+                            self.show_find_bar(ui);
+                            ui.context.window.request_redraw();
+                        }
+                        Key::F3 if self.char_layout.edit_kind != EditKind::Password => {
+                            self.find_next();
+                            ui.context.window.request_redraw();
+                        }
+                        Key::ShiftF3 if self.char_layout.edit_kind != EditKind::Password => {
+                            self.find_previous();
+                            ui.context.window.request_redraw();
+                        }
                         _ => {}
                     }
                 }
@@ -348,13 +669,13 @@ impl Widget for TextEdit {
                             for c in t.chars() {
                                 self.char_layout.inset_char(c, ui, &mut self.cursor_render, &mut self.select_render);
                             }
-                            ui.send_updates(&self.contact_ids, ContextUpdate::String(self.text()));
-                            ui.context.window.request_redraw();
+                            self.text_changed(ui);
                         }
                         _ => {}
                     }
                 }
             }
+            UpdateType::KeyRelease(Key::Escape) if self.state.focused && self.find_bar.is_some() => self.close_find_bar(ui),
             UpdateType::KeyRelease(ref mut key) => {
                 if self.state.focused { self.key_input(mem::take(key), ui); }
             }
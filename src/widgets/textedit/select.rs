@@ -31,6 +31,7 @@ impl EditSelection {
     }
 
     pub fn init(&mut self, rows: usize, line_height: f32) {
+        self.renders.clear();
         let mut select_style = VisualStyle::same(WidgetStyle {
             fill: Color::rgba(144, 209, 255, 100),
             border: Border::same(0.0),
@@ -166,6 +167,8 @@ impl EditSelection {
         self.start_horiz = start_horiz;
         self.start_vert = start_vert;
         self.has_selected = true;
+        self.changed = true;
+        if start_vert.max(cursor.vert) >= self.renders.len() { return; }
         if start_vert == cursor.vert { //处于同一行中
             let line = &cchar.buffer.lines[cursor.vert];
             let mut x_min = line.get_width_in_char(start_horiz) + cursor.min_pos.x;
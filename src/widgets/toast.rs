@@ -0,0 +1,299 @@
+use crate::render::{RenderParam, VisualStyle, WidgetStyle};
+use crate::response::{ActionCallB, Callback};
+use crate::shape::Shape;
+use crate::text::buffer::TextBuffer;
+use crate::text::rich::RichText;
+use crate::ui::Ui;
+use crate::*;
+use std::mem;
+
+///出现及消失的动画时长
+const FADE_MS: u128 = 200;
+const SLIDE: f32 = 40.0;
+const MARGIN: f32 = 12.0;
+const SPACE: f32 = 8.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn color(&self) -> Color {
+        match self {
+            ToastKind::Info => Color::rgb(33, 150, 243),
+            ToastKind::Success => Color::rgb(67, 160, 71),
+            ToastKind::Warning => Color::rgb(251, 140, 0),
+            ToastKind::Error => Color::rgb(229, 57, 53),
+        }
+    }
+}
+
+/// ### Toast的示例用法
+/// ```
+/// use xlui::*;
+///
+/// struct XlUi;
+///
+/// impl XlUi {
+///     fn undo(&mut self, ui: &mut Ui) {
+///         ui.toast("已撤销", ToastKind::Info, 2000);
+///     }
+/// }
+///
+/// fn saved(ui: &mut Ui) {
+///     //显示3秒后自动关闭
+///     ui.toast("已保存", ToastKind::Success, 3000)
+///         //可选的操作按钮，点击后关闭提示
+///         .set_action("撤销", XlUi::undo);
+///     //duration为0时不会自动关闭，点击提示关闭
+///     ui.toast("连接已断开", ToastKind::Error, 0);
+///     //提示显示的位置，默认在右下角
+///     ui.set_toast_align(Align::RightTop);
+/// }
+/// ```
+pub struct Toast {
+    text: TextBuffer,
+    color: Color,
+    kind: ToastKind,
+    duration: u64,
+    created: u128,
+    closing: Option<u128>,
+    action: Option<(TextBuffer, ActionCallB)>,
+    init: bool,
+    ///当前的纵向位置，堆叠位置改变时逐渐移动
+    y: Option<f32>,
+    rect: Rect,
+    action_rect: Rect,
+    frame_render: RenderParam,
+    accent_render: RenderParam,
+}
+
+impl Toast {
+    pub(crate) fn new(text: impl Into<RichText>, kind: ToastKind, duration: u64) -> Toast {
+        let text = TextBuffer::new(text);
+        Toast {
+            color: text.text.color.clone(),
+            text,
+            kind,
+            duration,
+            created: time_ms(),
+            closing: None,
+            action: None,
+            init: false,
+            y: None,
+            rect: Rect::new(),
+            action_rect: Rect::new(),
+            frame_render: RenderParam::new(Shape::rectangle()),
+            accent_render: RenderParam::new(Shape::rectangle()),
+        }
+    }
+
+    ///提示中的操作按钮，点击后调用f并关闭提示
+    pub fn set_action<A: 'static>(&mut self, text: impl Into<RichText>, f: fn(&mut A, &mut Ui)) {
+        self.action = Some((TextBuffer::new(text), Callback::create_action(f)));
+        self.init = false;
+    }
+
+    pub fn kind(&self) -> ToastKind {
+        self.kind
+    }
+
+    ///开始消失动画
+    pub fn close(&mut self) {
+        if self.closing.is_none() { self.closing = Some(time_ms()); }
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        self.text.init(ui);
+        let mut width = self.text.geometry.context_width() + 28.0;
+        if let Some((ref mut action, _)) = self.action {
+            action.init(ui);
+            width += action.geometry.context_width() + 16.0;
+        }
+        self.rect.set_size(width.max(160.0), self.text.geometry.context_height() + 16.0);
+        self.init = true;
+    }
+
+    ///出现及消失的进度，0为完全隐藏
+    fn progress(&self, now: u128) -> f32 {
+        let appear = (now - self.created).min(FADE_MS) as f32 / FADE_MS as f32;
+        let disappear = self.closing.map_or(1.0, |t| 1.0 - (now - t).min(FADE_MS) as f32 / FADE_MS as f32);
+        appear.min(disappear)
+    }
+
+    fn closed(&self, now: u128) -> bool {
+        self.closing.is_some_and(|t| now - t >= FADE_MS)
+    }
+
+    ///距离下次需要重绘的时间
+    fn next_frame(&self, now: u128, target_y: f32) -> Option<u128> {
+        if now - self.created < FADE_MS || self.closing.is_some() { return Some(16); }
+        if self.y.is_some_and(|y| (y - target_y).abs() > 0.5) { return Some(16); }
+        if self.duration == 0 { return None; }
+        Some((self.created + self.duration as u128).saturating_sub(now).max(1))
+    }
+
+    fn draw(&mut self, ui: &mut Ui, x: f32, target_y: f32, slide: (f32, f32), now: u128) {
+        let y = match self.y {
+            None => target_y,
+            Some(y) if (y - target_y).abs() <= 0.5 => target_y,
+            Some(y) => y + (target_y - y) * 0.3,
+        };
+        self.y = Some(y);
+        let progress = self.progress(now);
+        let eased = 1.0 - (1.0 - progress) * (1.0 - progress);
+        self.rect.offset_to(x + slide.0 * (1.0 - eased), y + slide.1 * (1.0 - eased));
+        let alpha = (progress * 255.0) as u8;
+        let fill = Color::rgba(255, 255, 255, alpha);
+        let accent = self.kind.color();
+        self.frame_render.set_style(VisualStyle::same(WidgetStyle {
+            fill,
+            border: Border::same(1.0).color(Color::rgba(210, 210, 210, alpha)),
+            radius: Radius::same(4),
+            shadow: Shadow::new(),
+        }));
+        *self.frame_render.rect_mut() = self.rect.clone();
+        self.frame_render.draw(ui, false, false, false);
+        let mut accent_rect = self.rect.clone();
+        accent_rect.set_width(4.0);
+        self.accent_render.set_style(VisualStyle::same((Color::rgba(accent.r, accent.g, accent.b, alpha), 0.0, 2).into()));
+        *self.accent_render.rect_mut() = accent_rect;
+        self.accent_render.draw(ui, false, false, false);
+        //文本不支持透明度，按进度与背景混合
+        let mut text_rect = self.rect.clone();
+        text_rect.add_min_x(16.0);
+        text_rect.add_min_y(8.0);
+        self.text.text.color = blend(&self.color, progress);
+        self.text.geometry.offset_to_rect(&text_rect);
+        self.text.redraw(ui);
+        if let Some((ref mut action, _)) = self.action {
+            let width = action.geometry.context_width();
+            self.action_rect = self.rect.clone();
+            self.action_rect.add_min_x(self.rect.width() - width - 24.0);
+            let mut rect = self.action_rect.clone();
+            rect.add_min_x(12.0);
+            rect.add_min_y(8.0);
+            action.text.color = blend(&accent, progress);
+            action.geometry.offset_to_rect(&rect);
+            action.redraw(ui);
+        }
+    }
+}
+
+fn blend(color: &Color, progress: f32) -> Color {
+    let mix = |c: u8| (255.0 + (c as f32 - 255.0) * progress) as u8;
+    Color::rgb(mix(color.r), mix(color.g), mix(color.b))
+}
+
+///窗口角落中堆叠显示的提示，绘制在布局及InnerWindow之上
+pub(crate) struct Toasts {
+    items: Vec<Toast>,
+    align: Align,
+    ///已安排的重绘时间
+    wakeup: u128,
+}
+
+impl Toasts {
+    pub(crate) fn new() -> Toasts {
+        Toasts {
+            items: vec![],
+            align: Align::RightBottom,
+            wakeup: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, toast: Toast) -> &mut Toast {
+        self.items.push(toast);
+        self.items.last_mut().unwrap()
+    }
+
+    pub(crate) fn set_align(&mut self, align: Align) {
+        self.align = align;
+    }
+
+    ///取出后处理，处理期间(如回调中)新加的提示追加到末尾
+    pub(crate) fn with<R>(ui: &mut Ui, f: impl FnOnce(&mut Toasts, &mut Ui) -> R) -> R {
+        let mut toasts = mem::take(&mut ui.context.toasts);
+        let res = f(&mut toasts, ui);
+        let added = mem::replace(&mut ui.context.toasts, toasts);
+        ui.context.toasts.items.extend(added.items);
+        res
+    }
+
+    fn request_redraw(&mut self, ui: &mut Ui, delay: u128) {
+        let now = time_ms();
+        if self.wakeup > now && self.wakeup <= now + delay { return; }
+        self.wakeup = now + delay;
+        let window = ui.context.window.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(delay as u64));
+            window.request_redraw();
+        });
+    }
+
+    ///按下位置在提示上时返回true，不再传递给布局
+    pub(crate) fn pressed(&mut self, ui: &mut Ui) -> bool {
+        self.items.iter().any(|x| x.init && ui.device.device_input.pressed_at(&x.rect))
+    }
+
+    ///点击操作按钮时调用回调，点击提示时关闭，返回是否处理了点击
+    pub(crate) fn clicked(&mut self, ui: &mut Ui) -> bool {
+        let Some(index) = self.items.iter().position(|x| x.init && x.closing.is_none() && ui.device.device_input.click_at(&x.rect)) else { return false; };
+        let toast = &mut self.items[index];
+        toast.close();
+        if let Some((_, ref mut callback)) = toast.action && ui.device.device_input.hovered_at(&toast.action_rect) {
+            let app = ui.app.take().unwrap();
+            callback(app, ui);
+            ui.app.replace(app);
+        }
+        self.request_redraw(ui, 16);
+        true
+    }
+
+    pub(crate) fn redraw(&mut self, ui: &mut Ui) {
+        let now = time_ms();
+        self.items.retain(|x| !x.closed(now));
+        if self.items.is_empty() { return; }
+        let size = ui.context.window.size();
+        let (left, center, top) = match self.align {
+            Align::LeftTop => (true, false, true),
+            Align::LeftBottom | Align::LeftCenter => (true, false, false),
+            Align::RightTop => (false, false, true),
+            Align::RightBottom | Align::RightCenter => (false, false, false),
+            Align::CenterTop => (false, true, true),
+            Align::CenterBottom | Align::Center => (false, true, false),
+        };
+        let slide = match (left, center, top) {
+            (_, true, true) => (0.0, -SLIDE),
+            (_, true, false) => (0.0, SLIDE),
+            (true, _, _) => (-SLIDE, 0.0),
+            _ => (SLIDE, 0.0),
+        };
+        //最新的提示靠近窗口角落
+        let mut offset = MARGIN;
+        let mut next = None;
+        for toast in self.items.iter_mut().rev() {
+            if !toast.init { toast.init(ui); }
+            if toast.closing.is_none() && toast.duration != 0 && now >= toast.created + toast.duration as u128 { toast.close(); }
+            let (width, height) = toast.rect.size();
+            let x = if center { (size.width - width) / 2.0 } else if left { MARGIN } else { size.width - width - MARGIN };
+            let y = if top { offset } else { size.height - offset - height };
+            toast.draw(ui, x, y, slide, now);
+            offset += height + SPACE;
+            if let Some(delay) = toast.next_frame(now, y) {
+                next = Some(next.map_or(delay, |x: u128| x.min(delay)));
+            }
+        }
+        if let Some(delay) = next { self.request_redraw(ui, delay); }
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Toasts::new()
+    }
+}
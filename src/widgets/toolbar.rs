@@ -0,0 +1,654 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::layout::popup::Popup;
+use crate::render::image::ImageSource;
+use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
+use crate::response::{ActionCallB, Callback, Response};
+use crate::shape::Shape;
+use crate::size::Geometry;
+use crate::text::buffer::TextBuffer;
+use crate::ui::Ui;
+use crate::widgets::button::Button;
+use crate::widgets::{Widget, WidgetKind, WidgetSize, WidgetState};
+use crate::*;
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+const BUTTON_SIZE: f32 = 28.0;
+const SEPARATOR: f32 = 9.0;
+const PADDING: f32 = 3.0;
+const MENU_ROW: f32 = 26.0;
+const MENU_WIDTH: f32 = 160.0;
+///溢出按钮的动作索引
+const MORE: usize = usize::MAX;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ToolButtonStyle {
+    IconOnly,
+    TextOnly,
+    IconText,
+}
+
+type CheckCallB = Box<dyn FnMut(&mut Box<dyn App>, &mut Ui, bool)>;
+
+enum ToolItemKind {
+    Action(ActionCallB),
+    Toggle(CheckCallB),
+    Separator(Box<RenderParam>),
+    ///嵌入的控件，溢出时由弹窗中的菜单显示
+    Widget(Rc<RefCell<WidgetKind>>),
+}
+
+struct ToolItem {
+    kind: ToolItemKind,
+    widget: Option<WidgetKind>,
+}
+
+impl ToolItem {
+    fn size(&self, horizontal: bool) -> (f32, f32) {
+        if let ToolItemKind::Widget(ref widget) = self.kind {
+            let widget = widget.borrow();
+            return (widget.width(), widget.height());
+        }
+        match self.widget {
+            Some(ref widget) => (widget.width(), widget.height()),
+            None if horizontal => (SEPARATOR, BUTTON_SIZE),
+            None => (BUTTON_SIZE, SEPARATOR),
+        }
+    }
+}
+
+///嵌入的控件在溢出菜单中的行高
+fn widget_row_height(widget: &WidgetKind) -> f32 {
+    (widget.height() + 6.0).max(MENU_ROW)
+}
+
+///工具栏、工具栏按钮及溢出菜单共享的状态
+#[derive(Default)]
+struct ToolBarShared {
+    ///第一个溢出项的索引
+    overflow: usize,
+    ///溢出菜单中的项，依次为项索引、文本及是否可选中
+    entries: Vec<(usize, String, bool)>,
+    checked: Vec<bool>,
+    actions: Vec<usize>,
+}
+
+fn button_style(checked: bool) -> VisualStyle {
+    let mut style = VisualStyle::same(WidgetStyle {
+        fill: if checked { Color::rgb(204, 232, 255) } else { Color::TRANSPARENT },
+        border: Border::same(if checked { 1.0 } else { 0.0 }).color(Color::rgb(144, 209, 255)),
+        radius: Radius::same(3),
+        shadow: Shadow::new(),
+    });
+    style.hovered.fill = Color::rgb(220, 220, 220);
+    style.pressed.fill = Color::rgb(195, 195, 195);
+    style
+}
+
+/// ### ToolBar的示例用法
+/// ```
+/// use xlui::*;
+///
+/// struct XlUi;
+///
+/// impl XlUi {
+///     fn save(&mut self, _: &mut Ui) {
+///         println!("保存");
+///     }
+///
+///     fn bold(&mut self, _: &mut Ui, checked: bool) {
+///         println!("粗体 {}", checked);
+///     }
+/// }
+///
+/// fn draw(ui: &mut Ui) {
+///     //按钮样式需在添加项之前设置，IconOnly时文本作为悬停提示
+///     let mut tool_bar = ToolBar::horizontal().with_button_style(ToolButtonStyle::IconText);
+///     tool_bar.add_action(ui, "logo.jpg", "保存", XlUi::save);
+///     tool_bar.add_separator();
+///     tool_bar.add_toggle(ui, "logo.jpg", "粗体", false, XlUi::bold);
+///     //嵌入的控件
+///     tool_bar.add_widget(ui, TextEdit::single_edit("").with_width(120.0));
+///     //未设置宽度时使用布局的剩余宽度，放不下的项(包括嵌入的控件)移到“»”弹窗中
+///     ui.add(tool_bar);
+///     //垂直工具栏
+///     let mut tool_bar = ToolBar::vertical().with_button_style(ToolButtonStyle::IconOnly).with_height(200.0);
+///     tool_bar.add_action(ui, "logo.jpg", "保存", XlUi::save);
+///     ui.add(tool_bar);
+/// }
+/// ```
+pub struct ToolBar {
+    id: String,
+    horizontal: bool,
+    button_style: ToolButtonStyle,
+    items: Vec<ToolItem>,
+    more: Option<WidgetKind>,
+    shared: Arc<RwLock<ToolBarShared>>,
+    popup_id: String,
+    popup_rect: Rect,
+    ///添加了新的项，需重建溢出菜单
+    menu_changed: bool,
+    space: f32,
+    ///可用长度，未固定时为上次绘制时布局的剩余长度
+    length: f32,
+    fixed_length: Option<f32>,
+    visual: Visual,
+    geometry: Geometry,
+    state: WidgetState,
+}
+
+impl ToolBar {
+    fn new(horizontal: bool) -> ToolBar {
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(245, 245, 245),
+            border: Border::same(1.0).color(Color::rgb(220, 220, 220)),
+            radius: Radius::same(0),
+            shadow: Shadow::new(),
+        });
+        ToolBar {
+            id: gen_unique_id(),
+            horizontal,
+            button_style: ToolButtonStyle::IconText,
+            items: vec![],
+            more: None,
+            shared: Arc::new(RwLock::new(ToolBarShared::default())),
+            popup_id: "".to_string(),
+            popup_rect: Rect::new(),
+            menu_changed: false,
+            space: 2.0,
+            length: 0.0,
+            fixed_length: None,
+            visual: Visual::new().with_enable().with_style(style),
+            geometry: Geometry::new(),
+            state: WidgetState::default(),
+        }
+    }
+
+    pub fn horizontal() -> ToolBar {
+        ToolBar::new(true)
+    }
+
+    pub fn vertical() -> ToolBar {
+        ToolBar::new(false)
+    }
+
+    ///按钮的显示方式，默认为图标加文本
+    pub fn with_button_style(mut self, style: ToolButtonStyle) -> Self {
+        self.button_style = style;
+        self
+    }
+
+    pub fn with_space(mut self, space: f32) -> Self {
+        self.space = space;
+        self
+    }
+
+    ///水平工具栏的长度，未设置时使用布局的剩余宽度
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.geometry.set_fix_width(width);
+        if self.horizontal { self.fixed_length = Some(width); }
+        self
+    }
+
+    ///垂直工具栏的长度，未设置时使用布局的剩余高度
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.geometry.set_fix_height(height);
+        if !self.horizontal { self.fixed_length = Some(height); }
+        self
+    }
+
+    pub fn with_size(self, width: f32, height: f32) -> Self {
+        self.with_width(width).with_height(height)
+    }
+
+    fn create_button(&self, ui: &mut Ui, icon: impl Into<ImageSource>, text: &str, checked: bool) -> WidgetKind {
+        let mut button = match self.button_style {
+            ToolButtonStyle::IconOnly => Button::image_and_text(icon, "").width(BUTTON_SIZE),
+            ToolButtonStyle::TextOnly => Button::new(text),
+            ToolButtonStyle::IconText => Button::image_and_text(icon, text),
+        }.height(BUTTON_SIZE).with_style(button_style(checked));
+        let index = self.items.len();
+        let shared = self.shared.clone();
+        button.set_inner_callback(move || shared.write().unwrap().actions.push(index));
+        let previous = mem::replace(&mut ui.update_type, UpdateType::Init);
+        let widget = WidgetKind::new(ui, button);
+        ui.update_type = previous;
+        if self.button_style == ToolButtonStyle::IconOnly { ui.set_tooltip(widget.id(), text); }
+        widget
+    }
+
+    ///entry为溢出菜单中的文本及是否可选中
+    fn push(&mut self, kind: ToolItemKind, widget: Option<WidgetKind>, entry: Option<(String, bool)>, checked: bool) -> Option<&mut WidgetKind> {
+        let mut shared = self.shared.write().unwrap();
+        let index = self.items.len();
+        shared.checked.push(checked);
+        if let Some((text, checkable)) = entry { shared.entries.push((index, text, checkable)); }
+        drop(shared);
+        self.items.push(ToolItem { kind, widget });
+        //显示后添加的项在下次绘制时重建溢出菜单
+        self.menu_changed = !self.popup_id.is_empty();
+        self.items.last_mut().unwrap().widget.as_mut()
+    }
+
+    ///添加按钮，点击时调用f
+    pub fn add_action<A: 'static>(&mut self, ui: &mut Ui, icon: impl Into<ImageSource>, text: impl ToString, f: fn(&mut A, &mut Ui)) -> &mut Button {
+        let text = text.to_string();
+        let widget = self.create_button(ui, icon, &text, false);
+        let widget = self.push(ToolItemKind::Action(Callback::create_action(f)), Some(widget), Some((text, false)), false);
+        widget.unwrap().as_mut_().unwrap()
+    }
+
+    ///添加可选中的按钮，点击时切换选中状态并调用f
+    pub fn add_toggle<A: 'static>(&mut self, ui: &mut Ui, icon: impl Into<ImageSource>, text: impl ToString, checked: bool, f: fn(&mut A, &mut Ui, bool)) -> &mut Button {
+        let text = text.to_string();
+        let widget = self.create_button(ui, icon, &text, checked);
+        let widget = self.push(ToolItemKind::Toggle(Callback::create_check(f)), Some(widget), Some((text, true)), checked);
+        widget.unwrap().as_mut_().unwrap()
+    }
+
+    pub fn add_separator(&mut self) {
+        let render = RenderParam::new(Shape::rectangle()).with_style(VisualStyle::same((Color::rgb(200, 200, 200), 0.0, 0).into()));
+        self.push(ToolItemKind::Separator(Box::new(render)), None, None, false);
+    }
+
+    ///添加嵌入的控件，如搜索框，溢出时显示在“»”弹窗中
+    pub fn add_widget<W: Widget>(&mut self, ui: &mut Ui, widget: W) -> &mut W {
+        let previous = mem::replace(&mut ui.update_type, UpdateType::Init);
+        let widget = WidgetKind::new(ui, widget);
+        ui.update_type = previous;
+        self.push(ToolItemKind::Widget(Rc::new(RefCell::new(widget))), None, None, false);
+        let ToolItemKind::Widget(ref mut widget) = self.items.last_mut().unwrap().kind else { unreachable!() };
+        //溢出菜单在下次绘制时才引用新添加的控件
+        Rc::get_mut(widget).unwrap().get_mut().as_mut_().unwrap()
+    }
+
+    ///项index的选中状态
+    pub fn is_checked(&self, index: usize) -> bool {
+        self.shared.read().unwrap().checked.get(index).cloned().unwrap_or(false)
+    }
+
+    pub fn set_checked(&mut self, index: usize, checked: bool) {
+        let Some(item) = self.items.get_mut(index) else { return; };
+        if !matches!(item.kind, ToolItemKind::Toggle(_)) { return; }
+        self.shared.write().unwrap().checked[index] = checked;
+        if let Some(button) = item.widget.as_mut().and_then(|x| x.as_mut_::<Button>()) { button.set_style(button_style(checked)); }
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        let mut more = Button::new("»").height(BUTTON_SIZE).with_style(button_style(false));
+        let shared = self.shared.clone();
+        more.set_inner_callback(move || shared.write().unwrap().actions.push(MORE));
+        self.more = Some(WidgetKind::new(ui, more));
+        self.build_menu(ui);
+        self.length = self.fixed_length.unwrap_or(self.content_length());
+    }
+
+    ///按当前的项创建溢出弹窗，弹窗已存在时保留其ID及打开状态
+    fn build_menu(&mut self, ui: &mut Ui) {
+        self.menu_changed = false;
+        let widgets: Vec<_> = self.items.iter().enumerate().filter_map(|(index, item)| match item.kind {
+            ToolItemKind::Widget(ref widget) => Some((index, widget.clone())),
+            _ => None,
+        }).collect();
+        let width = widgets.iter().map(|(_, widget)| widget.borrow().width() + 26.0).fold(MENU_WIDTH, f32::max);
+        let height = self.menu_height(0).max(MENU_ROW);
+        self.popup_rect.set_size(width + 10.0, height + 10.0);
+        let mut popup = Popup::new(width, height);
+        match ui.popups.as_mut().unwrap().get_mut(&self.popup_id) {
+            Some(previous) => {
+                popup.id = self.popup_id.clone();
+                popup.set_rect(previous.rect().clone());
+                popup.inherit_state(previous);
+            }
+            None => self.popup_id = popup.id.clone(),
+        }
+        let mut menu = Some(ToolMenu::new(self.shared.clone(), widgets, width));
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        popup.show(ui, |ui| if let Some(menu) = menu.take() { ui.add(menu); });
+        ui.update_type = previous_update;
+    }
+
+    fn main_size(&self, size: (f32, f32)) -> f32 {
+        if self.horizontal { size.0 } else { size.1 }
+    }
+
+    fn cross_size(&self) -> f32 {
+        let cross = self.items.iter().map(|x| x.size(self.horizontal)).map(|(w, h)| if self.horizontal { h } else { w }).fold(BUTTON_SIZE, f32::max);
+        cross + PADDING * 2.0
+    }
+
+    ///所有项都显示时需要的长度
+    fn content_length(&self) -> f32 {
+        let total = self.items.iter().map(|x| self.main_size(x.size(self.horizontal)) + self.space).sum::<f32>();
+        total - self.space + PADDING * 2.0
+    }
+
+    ///第from项起的项在溢出菜单中的高度
+    fn menu_height(&self, from: usize) -> f32 {
+        self.items.iter().skip(from).map(|item| match item.kind {
+            ToolItemKind::Action(_) | ToolItemKind::Toggle(_) => MENU_ROW,
+            ToolItemKind::Separator(_) => 0.0,
+            ToolItemKind::Widget(ref widget) => widget_row_height(&widget.borrow()),
+        }).sum()
+    }
+
+    ///按可用长度计算第一个溢出项
+    fn arrange(&mut self) -> usize {
+        if self.content_length() <= self.length { return self.items.len(); }
+        let more = self.more.as_ref().map_or(BUTTON_SIZE, |x| self.main_size((x.width(), x.height())));
+        let available = self.length - PADDING * 2.0 - more - self.space;
+        let mut offset = 0.0;
+        for (index, item) in self.items.iter().enumerate() {
+            offset += self.main_size(item.size(self.horizontal));
+            if offset > available { return index; }
+            offset += self.space;
+        }
+        self.items.len()
+    }
+
+    fn item_rect(&self, rect: &Rect, offset: f32, size: (f32, f32), cross: f32) -> Rect {
+        let mut item_rect = Rect::new().with_size(size.0, size.1);
+        if self.horizontal {
+            item_rect.offset_to(rect.dx().min + offset, rect.dy().min + (cross - size.1) / 2.0);
+        } else {
+            item_rect.offset_to(rect.dx().min + (cross - size.0) / 2.0, rect.dy().min + offset);
+        }
+        item_rect
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        if self.menu_changed { self.build_menu(ui); }
+        let rect = ui.draw_rect.clone();
+        self.length = self.fixed_length.unwrap_or(if self.horizontal { rect.width() } else { rect.height() });
+        let overflow = self.arrange();
+        self.shared.write().unwrap().overflow = overflow;
+        let cross = self.cross_size();
+        let (width, height) = if self.horizontal { (self.length, cross) } else { (cross, self.length) };
+        self.visual.rect_mut().set_size(width, height);
+        self.visual.rect_mut().offset_to_rect(&rect);
+        self.visual.draw(ui, self.state.disabled, false, false, false);
+        let mut offset = PADDING;
+        for index in 0..overflow {
+            let size = self.items[index].size(self.horizontal);
+            let item_rect = self.item_rect(&rect, offset, size, cross);
+            offset += self.main_size(size) + self.space;
+            let item = &mut self.items[index];
+            match item.kind {
+                ToolItemKind::Separator(ref mut render) => {
+                    let mut line = item_rect.clone();
+                    if self.horizontal {
+                        line.add_min_x(SEPARATOR / 2.0);
+                        line.set_width(1.0);
+                    } else {
+                        line.add_min_y(SEPARATOR / 2.0);
+                        line.set_height(1.0);
+                    }
+                    *render.rect_mut() = line;
+                    render.draw(ui, false, false, false);
+                }
+                ToolItemKind::Widget(ref widget) => {
+                    ui.draw_rect = item_rect;
+                    widget.borrow_mut().update(ui);
+                }
+                _ => if let Some(ref mut widget) = item.widget {
+                    ui.draw_rect = item_rect;
+                    widget.update(ui);
+                }
+            }
+        }
+        if overflow < self.items.len() && let Some(mut more) = self.more.take() {
+            let size = (more.width(), more.height());
+            let offset = self.length - PADDING - self.main_size(size);
+            ui.draw_rect = self.item_rect(&rect, offset, size, cross);
+            let more_rect = ui.draw_rect.clone();
+            more.update(ui);
+            self.more = Some(more);
+            self.popup_rect.set_height(self.menu_height(overflow) + 10.0);
+            if self.horizontal {
+                self.popup_rect.offset_to(more_rect.dx().max - self.popup_rect.width(), more_rect.dy().max + 3.0);
+            } else {
+                self.popup_rect.offset_to(more_rect.dx().max + 3.0, more_rect.dy().min);
+            }
+            ui.popups.as_mut().unwrap()[&self.popup_id].set_rect(self.popup_rect.clone());
+        }
+        ui.draw_rect = rect;
+    }
+
+    ///将事件传递给显示的项
+    fn update_items(&mut self, ui: &mut Ui) {
+        let overflow = self.shared.read().unwrap().overflow;
+        for item in self.items.iter_mut().take(overflow) {
+            match item.kind {
+                ToolItemKind::Widget(ref widget) => { widget.borrow_mut().update(ui); }
+                _ => if let Some(ref mut widget) = item.widget { widget.update(ui); }
+            }
+        }
+        if overflow < self.items.len() && let Some(ref mut more) = self.more { more.update(ui); }
+    }
+
+    ///处理按钮及溢出菜单的点击
+    fn update_actions(&mut self, ui: &mut Ui) {
+        let actions = mem::take(&mut self.shared.write().unwrap().actions);
+        for index in actions {
+            if index == MORE {
+                ui.popups.as_mut().unwrap()[&self.popup_id].toggle();
+                continue;
+            }
+            ui.popups.as_mut().unwrap()[&self.popup_id].request_state(false);
+            let checked = !self.is_checked(index);
+            if let ToolItemKind::Toggle(_) = self.items[index].kind { self.set_checked(index, checked); }
+            let app = ui.app.take().unwrap();
+            match self.items[index].kind {
+                ToolItemKind::Action(ref mut callback) => callback(app, ui),
+                ToolItemKind::Toggle(ref mut callback) => callback(app, ui, checked),
+                _ => {}
+            }
+            ui.app.replace(app);
+            ui.context.window.request_redraw();
+        }
+    }
+}
+
+impl Widget for ToolBar {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Init => self.init(ui),
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::ReInit => {
+                #[cfg(feature = "gpu")]
+                self.visual.re_init();
+                for item in self.items.iter_mut() {
+                    match item.kind {
+                        #[cfg(feature = "gpu")]
+                        ToolItemKind::Separator(ref mut render) => render.re_init(),
+                        ToolItemKind::Widget(ref widget) => { widget.borrow_mut().update(ui); }
+                        _ => if let Some(ref mut widget) = item.widget { widget.update(ui); }
+                    }
+                }
+                if let Some(ref mut more) = self.more { more.update(ui); }
+            }
+            _ => self.update_items(ui),
+        }
+        if ui.app.is_some() { self.update_actions(ui); }
+        let (width, height) = if self.horizontal { (self.length, self.cross_size()) } else { (self.cross_size(), self.length) };
+        Response::new(&self.id, WidgetSize::same(width, height))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
+
+///溢出菜单的行
+enum MenuRow {
+    ///按钮，依次为项索引及在entries中的位置
+    Entry(usize, usize),
+    ///嵌入的控件，依次为项索引及控件
+    Widget(usize, Rc<RefCell<WidgetKind>>),
+}
+
+impl MenuRow {
+    fn index(&self) -> usize {
+        match self {
+            MenuRow::Entry(index, _) | MenuRow::Widget(index, _) => *index,
+        }
+    }
+}
+
+///溢出弹窗中的菜单
+struct ToolMenu {
+    id: String,
+    shared: Arc<RwLock<ToolBarShared>>,
+    ///按项索引排列的行
+    menu_rows: Vec<MenuRow>,
+    width: f32,
+    texts: Vec<TextBuffer>,
+    check: TextBuffer,
+    hovered: Option<usize>,
+    ///上次绘制时各菜单项的区域，依次为项索引及区域
+    rows: Vec<(usize, Rect)>,
+    hover_render: RenderParam,
+    geometry: Geometry,
+    state: WidgetState,
+}
+
+impl ToolMenu {
+    fn new(shared: Arc<RwLock<ToolBarShared>>, widgets: Vec<(usize, Rc<RefCell<WidgetKind>>)>, width: f32) -> ToolMenu {
+        let locked = shared.read().unwrap();
+        let entries = &locked.entries;
+        let texts = entries.iter().map(|x| TextBuffer::new(x.1.as_str())).collect();
+        let mut menu_rows: Vec<MenuRow> = entries.iter().enumerate().map(|(position, x)| MenuRow::Entry(x.0, position)).collect();
+        menu_rows.extend(widgets.into_iter().map(|(index, widget)| MenuRow::Widget(index, widget)));
+        menu_rows.sort_by_key(|x| x.index());
+        drop(locked);
+        ToolMenu {
+            id: gen_unique_id(),
+            shared,
+            menu_rows,
+            width,
+            texts,
+            check: TextBuffer::new("✓"),
+            hovered: None,
+            rows: vec![],
+            hover_render: RenderParam::new(Shape::rectangle()).with_style(VisualStyle::same((Color::rgb(204, 232, 255), 0.0, 3).into())),
+            geometry: Geometry::new(),
+            state: WidgetState::default(),
+        }
+    }
+
+    fn init(&mut self, ui: &mut Ui) {
+        for text in self.texts.iter_mut() { text.init(ui); }
+        self.check.init(ui);
+        let height = self.menu_rows.iter().map(|row| match row {
+            MenuRow::Entry(..) => MENU_ROW,
+            MenuRow::Widget(_, widget) => widget_row_height(&widget.borrow()),
+        }).sum();
+        self.geometry.set_context_size(self.width, height);
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        let shared = self.shared.read().unwrap();
+        self.rows.clear();
+        let previous_rect = ui.draw_rect.clone();
+        let mut rect = ui.draw_rect.clone();
+        rect.set_size(self.width, MENU_ROW);
+        for row in self.menu_rows.iter() {
+            if row.index() < shared.overflow { continue; }
+            let (index, position) = match row {
+                MenuRow::Entry(index, position) => (index, *position),
+                MenuRow::Widget(_, widget) => {
+                    let mut widget = widget.borrow_mut();
+                    let height = widget_row_height(&widget);
+                    let mut widget_rect = rect.clone();
+                    widget_rect.add_min_x(22.0);
+                    widget_rect.add_min_y((height - widget.height()) / 2.0);
+                    ui.draw_rect = widget_rect;
+                    widget.update(ui);
+                    rect.add_min_y(height);
+                    rect.set_height(MENU_ROW);
+                    continue;
+                }
+            };
+            let checkable = shared.entries[position].2;
+            if self.hovered == Some(*index) {
+                *self.hover_render.rect_mut() = rect.clone();
+                self.hover_render.draw(ui, false, false, false);
+            }
+            let mut text_rect = rect.clone();
+            text_rect.add_min_y((MENU_ROW - self.check.geometry.context_height()) / 2.0);
+            if checkable && shared.checked[*index] {
+                let mut check_rect = text_rect.clone();
+                check_rect.add_min_x(4.0);
+                self.check.geometry.offset_to_rect(&check_rect);
+                self.check.redraw(ui);
+            }
+            text_rect.add_min_x(22.0);
+            self.texts[position].geometry.offset_to_rect(&text_rect);
+            self.texts[position].redraw(ui);
+            self.rows.push((*index, rect.clone()));
+            rect.add_min_y(MENU_ROW);
+            rect.set_height(MENU_ROW);
+        }
+        ui.draw_rect = previous_rect;
+    }
+
+    ///将事件传递给溢出的嵌入控件
+    fn update_widgets(&mut self, ui: &mut Ui) {
+        let overflow = self.shared.read().unwrap().overflow;
+        for row in self.menu_rows.iter() {
+            if let MenuRow::Widget(index, widget) = row && *index >= overflow { widget.borrow_mut().update(ui); }
+        }
+    }
+
+    fn row_at(&self, ui: &mut Ui) -> Option<usize> {
+        let pos = ui.device.device_input.mouse.lastest.relative;
+        self.rows.iter().find(|x| x.1.has_position(pos)).map(|x| x.0)
+    }
+}
+
+impl Widget for ToolMenu {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        match ui.update_type {
+            UpdateType::Init => self.init(ui),
+            UpdateType::ReInit => {
+                #[cfg(feature = "gpu")]
+                self.hover_render.re_init();
+            }
+            UpdateType::Draw => self.redraw(ui),
+            _ => self.update_widgets(ui),
+        }
+        match ui.update_type {
+            UpdateType::MouseMove => {
+                let hovered = self.row_at(ui);
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    ui.context.window.request_redraw();
+                }
+            }
+            UpdateType::MouseRelease => {
+                if let Some(index) = self.row_at(ui) && ui.device.device_input.click_at(&self.rows.iter().find(|x| x.0 == index).unwrap().1) {
+                    self.shared.write().unwrap().actions.push(index);
+                    self.hovered = None;
+                    ui.context.window.request_redraw();
+                }
+            }
+            _ => {}
+        }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
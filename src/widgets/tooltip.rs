@@ -0,0 +1,197 @@
+use crate::map::Map;
+use crate::render::{Visual, VisualStyle, WidgetStyle};
+use crate::text::buffer::TextBuffer;
+use crate::text::rich::RichText;
+use crate::ui::Ui;
+use crate::widgets::Widget;
+use crate::*;
+use std::rc::{Rc, Weak};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+/// ### 控件悬停提示的示例用法
+/// ```
+/// use xlui::*;
+///
+/// fn draw(ui:&mut Ui){
+///     //通过WidgetExt为任意控件设置提示
+///     ui.add(Button::new("保存").tooltip("保存当前文件(Ctrl+S)"));
+///     //也可以通过控件ID设置，支持RichText
+///     ui.add(Label::new("删除").with_id("delete"));
+///     ui.set_tooltip("delete", "删除后无法恢复".color(Color::RED));
+///     //悬停多久后显示提示，默认500ms
+///     ui.set_tooltip_delay(800);
+/// }
+/// ```
+pub trait WidgetExt: Widget + Sized {
+    ///鼠标悬停时显示的提示
+    fn tooltip(mut self, text: impl Into<RichText>) -> Self {
+        self.state().tooltip = Some(text.into());
+        self
+    }
+}
+
+impl<W: Widget> WidgetExt for W {}
+
+struct TooltipText {
+    buffer: TextBuffer,
+    init: bool,
+    ///设置提示的控件，控件释放后清除提示
+    owner: Option<Weak<()>>,
+}
+
+///由控件持有，释放后对应的提示在下次绘制时清除
+pub(crate) struct TooltipOwner {
+    _owner: Rc<()>,
+}
+
+///所有控件的悬停提示，鼠标移动时记录悬停的控件，延迟后在最上层绘制
+pub(crate) struct Tooltips {
+    texts: Map<String, TooltipText>,
+    delay: u64,
+    ///当前悬停的控件及开始悬停的时间
+    target: Option<String>,
+    since: u128,
+    ///本次鼠标移动中悬停的控件，后更新的控件优先
+    candidate: Option<String>,
+    ///按下后直到离开控件前不再显示
+    suppressed: bool,
+    ///显示时的鼠标位置
+    anchor: Option<Pos>,
+    visual: Visual,
+    ///每个窗口共用一个计时线程，发送延迟后到时请求重绘
+    timer: Option<Sender<u64>>,
+}
+
+impl Tooltips {
+    pub(crate) fn new() -> Tooltips {
+        let style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgb(255, 255, 225),
+            border: Border::same(1.0).color(Color::rgb(160, 160, 160)),
+            radius: Radius::same(3),
+            shadow: Shadow::new(),
+        });
+        Tooltips {
+            texts: Map::new(),
+            delay: 500,
+            target: None,
+            since: 0,
+            candidate: None,
+            suppressed: false,
+            anchor: None,
+            visual: Visual::new().with_enable().with_style(style),
+            timer: None,
+        }
+    }
+
+    pub(crate) fn set(&mut self, id: String, text: RichText) {
+        self.texts.insert(id, TooltipText { buffer: TextBuffer::new(text), init: false, owner: None });
+    }
+
+    pub(crate) fn set_owned(&mut self, id: String, text: RichText) -> TooltipOwner {
+        let owner = Rc::new(());
+        self.texts.insert(id, TooltipText { buffer: TextBuffer::new(text), init: false, owner: Some(Rc::downgrade(&owner)) });
+        TooltipOwner { _owner: owner }
+    }
+
+    pub(crate) fn remove(&mut self, id: &String) {
+        self.texts.remove(id);
+    }
+
+    pub(crate) fn set_delay(&mut self, delay: u64) {
+        self.delay = delay;
+    }
+
+    pub(crate) fn contains(&mut self, id: &String) -> bool {
+        self.texts.has_key(id)
+    }
+
+    pub(crate) fn begin_move(&mut self) {
+        self.candidate = None;
+    }
+
+    pub(crate) fn hovered(&mut self, id: &str) {
+        self.candidate = Some(id.to_string());
+    }
+
+    ///鼠标移动结束，悬停的控件改变时重新计时
+    pub(crate) fn end_move(&mut self, ui: &mut Ui) {
+        if self.candidate == self.target { return; }
+        if self.anchor.is_some() { ui.context.window.request_redraw(); }
+        self.target = self.candidate.take();
+        self.since = time_ms();
+        self.suppressed = false;
+        self.anchor = None;
+        if self.target.is_none() { return; }
+        let delay = self.delay + 10;
+        if let Some(ref timer) = self.timer && timer.send(delay).is_ok() { return; }
+        let (sender, receiver) = channel::<u64>();
+        let window = ui.context.window.clone();
+        //新的延迟会替换未到时的计时，Tooltips释放后线程退出
+        std::thread::spawn(move || {
+            let mut deadline = Some(Instant::now() + Duration::from_millis(delay));
+            loop {
+                let res = match deadline {
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                };
+                match res {
+                    Ok(delay) => deadline = Some(Instant::now() + Duration::from_millis(delay)),
+                    Err(RecvTimeoutError::Timeout) => {
+                        deadline = None;
+                        window.request_redraw();
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+        self.timer = Some(sender);
+    }
+
+    ///按下或滚动时隐藏提示
+    pub(crate) fn hide(&mut self, ui: &mut Ui) {
+        if self.target.is_none() { return; }
+        if self.anchor.is_some() { ui.context.window.request_redraw(); }
+        self.suppressed = true;
+        self.anchor = None;
+    }
+
+    pub(crate) fn redraw(&mut self, ui: &mut Ui) {
+        let released = |x: &TooltipText| x.owner.as_ref().is_some_and(|x| x.strong_count() == 0);
+        if self.texts.iter().any(released) { self.texts.retain(|_, x| !released(x)); }
+        let Some(target) = self.target.as_ref() else { return; };
+        if self.suppressed || time_ms() - self.since < self.delay as u128 { return; }
+        let Some(text) = self.texts.get_mut(target) else { return; };
+        if !text.init {
+            text.buffer.init(ui);
+            text.init = true;
+        }
+        let anchor = self.anchor.get_or_insert_with(|| *ui.device.device_input.mouse.lastest());
+        let width = text.buffer.geometry.context_width() + 12.0;
+        let height = text.buffer.geometry.context_height() + 8.0;
+        //默认在鼠标右下方，超出窗口时向左移动或翻转到鼠标上方
+        let size = ui.context.window.size();
+        let mut x = anchor.x + 4.0;
+        let mut y = anchor.y + 20.0;
+        if x + width > size.width { x = size.width - width; }
+        if y + height > size.height { y = anchor.y - height - 4.0; }
+        let mut rect = Rect::new().with_size(width, height);
+        rect.set_x_min(x.max(0.0));
+        rect.set_x_max(x.max(0.0) + width);
+        rect.set_y_min(y.max(0.0));
+        rect.set_y_max(y.max(0.0) + height);
+        self.visual.rect_mut().set_size(width, height);
+        self.visual.rect_mut().offset_to_rect(&rect);
+        self.visual.draw(ui, false, false, false, false);
+        rect.add_min_x(6.0);
+        rect.add_min_y(4.0);
+        text.buffer.geometry.offset_to_rect(&rect);
+        text.buffer.redraw(ui);
+    }
+}
+
+impl Default for Tooltips {
+    fn default() -> Self {
+        Tooltips::new()
+    }
+}
@@ -0,0 +1,398 @@
+use crate::frame::context::UpdateType;
+use crate::frame::App;
+use crate::key::Key;
+use crate::layout::{Layout, LayoutItem};
+use crate::render::{VisualStyle, WidgetStyle};
+use crate::response::{AppNotifies, AppNotify, Callback};
+use crate::style::color::Color;
+use crate::ui::Ui;
+use crate::widgets::tree::node::TreeNodes;
+use crate::widgets::tree::row::TreeRow;
+use crate::widgets::WidgetKind;
+use crate::{Border, Radius, Rect, RecycleLayout, ScrollWidget, Shadow};
+use std::cell::RefCell;
+use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+mod node;
+mod row;
+
+pub(crate) enum TreeAction {
+    Select(usize),
+    Toggle(usize),
+}
+
+pub(crate) type TreeActions = Arc<RwLock<Vec<TreeAction>>>;
+
+type TreeItemUi<T> = Box<dyn Fn(&mut Ui, &T)>;
+
+///树节点的提供者，子节点在首次展开时加载
+pub trait TreeProvider<T> {
+    ///节点的显示文本，未设置item_widget时使用
+    fn text(&self, node: &T) -> String;
+
+    ///节点是否有子节点，为true时显示展开箭头
+    fn has_children(&self, node: &T) -> bool;
+
+    ///加载节点的子节点，每个节点只调用一次
+    fn children(&self, node: &T) -> Vec<T>;
+}
+
+/// # TreeView的使用示例
+/// ```
+/// use xlui::*;
+///
+/// struct Numbers;
+///
+/// //节点为数字，每个小于1000的节点有10个子节点
+/// impl TreeProvider<u32> for Numbers {
+///     fn text(&self, node: &u32) -> String {
+///         format!("node-{}", node)
+///     }
+///
+///     fn has_children(&self, node: &u32) -> bool {
+///         *node < 1000
+///     }
+///
+///     fn children(&self, node: &u32) -> Vec<u32> {
+///         (0..10).map(|x| node * 10 + x + 1).collect()
+///     }
+/// }
+///
+/// let mut tree_view = TreeView::new(vec![0], Numbers).with_size(300.0, 400.0);
+/// //自定义节点的显示
+/// tree_view.set_item_widget(|ui, node| ui.label(format!("#{}", node)));
+/// //节点ID为加载顺序的索引，展开时才加载子节点
+/// let root = tree_view.roots()[0];
+/// tree_view.expand(root);
+/// assert_eq!(tree_view.children(root).len(), 10);
+/// assert_eq!(tree_view.visible_count(), 11);
+/// //选中节点时展开其所有祖先
+/// let child = tree_view.children(root)[2];
+/// tree_view.collapse_all();
+/// tree_view.select(child);
+/// assert_eq!(tree_view.current(), Some(&3));
+/// //点击箭头或双击展开、折叠，方向键移动当前节点，左右键折叠、展开
+/// fn connect<A: App>(tree_view: &mut TreeView<u32>) {
+///     tree_view.set_selection_callback(|_: &mut A, _| println!("selection changed"));
+/// }
+/// //需在App::update中调用tree_view.update(ui)
+/// ```
+pub struct TreeView<T> {
+    lid: String,
+    rect: Rect,
+    nodes: TreeNodes<T>,
+    provider: Box<dyn TreeProvider<T>>,
+    item_widget: Option<TreeItemUi<T>>,
+    row_height: f32,
+    indent: f32,
+    actions: TreeActions,
+    notify: AppNotifies, //选择改变的通知，由可见行调用
+    current: Option<usize>, //当前节点ID
+    scroll_request: Option<usize>, //待滚动到可见的节点ID
+    previous_display: Range<usize>,
+    changed: bool,
+    focused: bool, //点击节点后接收方向键
+}
+
+impl<T: 'static> TreeView<T> {
+    pub fn new(roots: Vec<T>, provider: impl TreeProvider<T> + 'static) -> Self {
+        let mut nodes = TreeNodes::new();
+        nodes.set_roots(roots, &provider);
+        TreeView {
+            lid: "".to_string(),
+            rect: Rect::new().with_size(200.0, 300.0),
+            nodes,
+            provider: Box::new(provider),
+            item_widget: None,
+            row_height: 24.0,
+            indent: 18.0,
+            actions: Arc::new(RwLock::new(vec![])),
+            notify: Rc::new(RefCell::new(AppNotify::default())),
+            current: None,
+            scroll_request: None,
+            previous_display: 0..0,
+            changed: false,
+            focused: false,
+        }
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.rect.set_width(width);
+        self
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.rect.set_height(height);
+        self
+    }
+
+    pub fn with_size(self, width: f32, height: f32) -> Self {
+        self.with_width(width).with_height(height)
+    }
+
+    pub fn with_row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    ///每层的缩进宽度
+    pub fn with_indent(mut self, indent: f32) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    ///设置节点的显示，默认显示TreeProvider::text
+    pub fn set_item_widget(&mut self, item_widget: impl Fn(&mut Ui, &T) + 'static) {
+        self.item_widget = Some(Box::new(item_widget));
+        self.changed = true;
+    }
+
+    ///替换全部根节点，已加载的节点及选择被清空
+    pub fn set_roots(&mut self, roots: Vec<T>) {
+        self.nodes.set_roots(roots, self.provider.as_ref());
+        self.current = None;
+        self.changed = true;
+    }
+
+    pub fn roots(&self) -> &[usize] {
+        self.nodes.roots()
+    }
+
+    ///已加载的子节点ID，未展开过的节点返回空
+    pub fn children(&self, id: usize) -> &[usize] {
+        self.nodes.children(id)
+    }
+
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.nodes.get(id)?.parent
+    }
+
+    pub fn depth(&self, id: usize) -> Option<usize> {
+        Some(self.nodes.get(id)?.depth)
+    }
+
+    pub fn node(&self, id: usize) -> Option<&T> {
+        Some(&self.nodes.get(id)?.data)
+    }
+
+    pub fn node_mut(&mut self, id: usize) -> Option<&mut T> {
+        self.changed = true;
+        self.nodes.data_mut(id)
+    }
+
+    pub fn is_expanded(&self, id: usize) -> bool {
+        self.nodes.get(id).is_some_and(|node| node.expanded)
+    }
+
+    ///展开节点，首次展开时加载子节点
+    pub fn expand(&mut self, id: usize) {
+        self.changed |= self.nodes.expand(id, self.provider.as_ref());
+    }
+
+    pub fn collapse(&mut self, id: usize) {
+        self.changed |= self.nodes.collapse(id);
+    }
+
+    pub fn toggle(&mut self, id: usize) {
+        if self.is_expanded(id) { self.collapse(id); } else { self.expand(id); }
+    }
+
+    ///展开全部节点，会加载所有子节点
+    pub fn expand_all(&mut self) {
+        self.nodes.expand_all(self.provider.as_ref());
+        self.changed = true;
+    }
+
+    pub fn collapse_all(&mut self) {
+        self.nodes.collapse_all();
+        self.changed = true;
+    }
+
+    ///当前可见(祖先均已展开)的节点数
+    pub fn visible_count(&self) -> usize {
+        self.nodes.visible().len()
+    }
+
+    ///可见节点的ID，按显示顺序排列
+    pub fn visible_nodes(&self) -> &[usize] {
+        self.nodes.visible()
+    }
+
+    ///选中节点，同时展开其祖先并滚动到可见
+    pub fn select(&mut self, id: usize) {
+        if self.nodes.get(id).is_none() { return; }
+        self.nodes.expand_to(id, self.provider.as_ref());
+        self.current = Some(id);
+        self.scroll_request = Some(id);
+        self.changed = true;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.current = None;
+        self.changed = true;
+    }
+
+    ///点击或键盘改变当前节点时调用
+    pub fn set_selection_callback<A: App>(&mut self, f: impl Fn(&mut A, &mut Ui) + 'static) {
+        self.notify.borrow_mut().set_callback(Arc::from(Callback::create_list(f)));
+    }
+
+    ///当前节点的ID
+    pub fn current_index(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.node(self.current?)
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        let layout = RecycleLayout::new().with_item_height(self.row_height).with_space(0.0);
+        let mut area = ScrollWidget::vertical().with_layout(layout).with_size(self.rect.width(), self.rect.height());
+        self.lid = area.id.clone();
+        let fill_style = VisualStyle::same(WidgetStyle {
+            fill: Color::TRANSPARENT,
+            border: Border::same(1.0).color(Color::rgba(144, 209, 255, 255)),
+            radius: Radius::same(2),
+            shadow: Shadow::new(),
+        });
+        area.set_style(fill_style);
+        area.show(ui, |ui| {
+            //可见节点数会随展开改变，始终创建可显示的全部行
+            let recycle: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
+            let draw_count = recycle.draw_count();
+            for _ in 0..draw_count {
+                let row = TreeRow::new(self.rect.width() - 18.0, self.row_height, self.indent, &self.actions, &self.notify);
+                let row = WidgetKind::new(ui, row);
+                ui.layout().add_item(LayoutItem::Widget(row));
+            }
+            let recycle: &mut RecycleLayout = ui.layout().as_mut_().unwrap();
+            recycle.set_total_count(self.nodes.visible().len());
+        });
+        self.previous_display = 0..0;
+        self.changed = true;
+    }
+
+    ///重新绑定可见行的节点
+    fn rebind(&mut self, ui: &mut Ui) {
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        let mut recycle = area.layout.take().unwrap();
+        let recycle_layout: &mut RecycleLayout = recycle.as_mut_().unwrap();
+        let count = self.nodes.visible().len();
+        if recycle_layout.total_count() != count {
+            recycle_layout.set_total_count(count);
+            let h = recycle_layout.size().rh;
+            area.layout = Some(recycle);
+            area.reset_context_height(h);
+            recycle = area.layout.take().unwrap();
+        }
+        let recycle_layout: &mut RecycleLayout = recycle.as_mut_().unwrap();
+        let display = recycle_layout.display_range().clone();
+        let previous_update = mem::replace(&mut ui.update_type, UpdateType::Init);
+        for (index, item) in recycle_layout.items_mut().iter_mut().enumerate() {
+            let position = display.start + index;
+            let Some(id) = self.nodes.visible().get(position).cloned() else { break; };
+            let node = self.nodes.get(id).unwrap();
+            let row: &mut TreeRow = item.widget_mut().unwrap();
+            row.bind(ui, position, node, self.current == Some(id), |ui| match self.item_widget {
+                Some(ref item_widget) => item_widget(ui, &node.data),
+                None => ui.label(self.provider.text(&node.data)),
+            });
+        }
+        ui.update_type = previous_update;
+        let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+        area.layout = Some(recycle);
+        self.previous_display = display;
+    }
+
+    fn set_current(&mut self, id: usize) {
+        if self.current == Some(id) { return; }
+        self.current = Some(id);
+        self.notify.borrow_mut().notify();
+        self.changed = true;
+    }
+
+    fn update_actions(&mut self) {
+        let actions = mem::take(&mut *self.actions.write().unwrap());
+        for action in actions {
+            match action {
+                TreeAction::Select(position) => {
+                    self.focused = true;
+                    if let Some(id) = self.nodes.visible().get(position).cloned() { self.set_current(id); }
+                }
+                TreeAction::Toggle(position) => {
+                    if let Some(id) = self.nodes.visible().get(position).cloned() { self.toggle(id); }
+                }
+            }
+        }
+    }
+
+    ///上下键及PageUp/PageDown/Home/End移动当前节点，左键折叠或移到父节点，右键展开或移到首个子节点
+    fn navigate(&mut self, key: &Key, page: usize) {
+        let visible = self.nodes.visible();
+        if visible.is_empty() { return; }
+        let last = visible.len() - 1;
+        let position = self.current.and_then(|x| self.nodes.position(x));
+        let id = match key {
+            Key::UpArrow => visible[position.map_or(0, |x| x.saturating_sub(1))],
+            Key::DownArrow => visible[position.map_or(0, |x| (x + 1).min(last))],
+            Key::PageUp => visible[position.map_or(0, |x| x.saturating_sub(page))],
+            Key::PageDown => visible[position.map_or(0, |x| (x + page).min(last))],
+            Key::Home => visible[0],
+            Key::End => visible[last],
+            Key::LeftArrow => {
+                let Some(id) = self.current else { return; };
+                if self.is_expanded(id) { return self.collapse(id); }
+                let Some(parent) = self.parent(id) else { return; };
+                parent
+            }
+            Key::RightArrow => {
+                let Some(id) = self.current else { return; };
+                if !self.is_expanded(id) { return self.expand(id); }
+                let Some(child) = self.children(id).first().cloned() else { return; };
+                child
+            }
+            _ => return,
+        };
+        self.set_current(id);
+        self.scroll_request = Some(id);
+    }
+
+    pub fn update(&mut self, ui: &mut Ui) {
+        match ui.update_type {
+            UpdateType::MousePress => self.focused = false,
+            UpdateType::KeyRelease(ref key) if self.focused && !self.lid.is_empty() => {
+                let key = key.clone();
+                let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+                let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+                let page = recycle.page_count();
+                self.navigate(&key, page);
+            }
+            _ => {}
+        }
+        self.update_actions();
+        if self.lid.is_empty() { return; }
+        if let Some(id) = self.scroll_request.take() && let Some(position) = self.nodes.position(id) {
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            if recycle.total_count() != self.nodes.visible().len() {
+                recycle.set_total_count(self.nodes.visible().len());
+                let h = recycle.size().rh;
+                area.reset_context_height(h);
+            }
+            area.scroll_to_item(position);
+        }
+        if let UpdateType::Draw = ui.update_type {
+            let area: &mut ScrollWidget = ui.layout().get_widget(&self.lid).unwrap();
+            let recycle_layout: &mut RecycleLayout = area.layout.as_mut().unwrap().as_mut_().unwrap();
+            if !self.changed && recycle_layout.display_range() == &self.previous_display { return; }
+            self.rebind(ui);
+            self.changed = false;
+        } else if self.changed {
+            ui.context.window.request_redraw();
+        }
+    }
+}
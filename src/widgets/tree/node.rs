@@ -0,0 +1,237 @@
+use crate::widgets::tree::TreeProvider;
+
+pub(crate) struct TreeNode<T> {
+    pub(crate) data: T,
+    pub(crate) parent: Option<usize>,
+    pub(crate) depth: usize,
+    children: Option<Vec<usize>>, //None为未加载
+    pub(crate) expandable: bool,
+    pub(crate) expanded: bool,
+}
+
+///树节点的存储，节点ID为加载顺序的索引，visible为展开后按显示顺序排列的节点
+pub(crate) struct TreeNodes<T> {
+    nodes: Vec<TreeNode<T>>,
+    roots: Vec<usize>,
+    visible: Vec<usize>,
+    ///节点在visible中的位置，None为不可见
+    positions: Vec<Option<usize>>,
+}
+
+impl<T> TreeNodes<T> {
+    pub fn new() -> Self {
+        TreeNodes {
+            nodes: vec![],
+            roots: vec![],
+            visible: vec![],
+            positions: vec![],
+        }
+    }
+
+    pub fn set_roots(&mut self, roots: Vec<T>, provider: &dyn TreeProvider<T>) {
+        self.nodes.clear();
+        self.positions.clear();
+        self.roots = roots.into_iter().map(|datum| self.push(datum, None, provider)).collect();
+        self.visible = self.roots.clone();
+        self.reindex(0);
+    }
+
+    fn push(&mut self, data: T, parent: Option<usize>, provider: &dyn TreeProvider<T>) -> usize {
+        let depth = parent.map_or(0, |parent| self.nodes[parent].depth + 1);
+        let expandable = provider.has_children(&data);
+        self.nodes.push(TreeNode { data, parent, depth, children: None, expandable, expanded: false });
+        self.positions.push(None);
+        self.nodes.len() - 1
+    }
+
+    ///首次展开时通过provider加载子节点
+    fn load(&mut self, id: usize, provider: &dyn TreeProvider<T>) {
+        if self.nodes[id].children.is_some() { return; }
+        let children = provider.children(&self.nodes[id].data);
+        let children = children.into_iter().map(|datum| self.push(datum, Some(id), provider)).collect::<Vec<_>>();
+        self.nodes[id].expandable = !children.is_empty();
+        self.nodes[id].children = Some(children);
+    }
+
+    pub fn get(&self, id: usize) -> Option<&TreeNode<T>> {
+        self.nodes.get(id)
+    }
+
+    pub fn data_mut(&mut self, id: usize) -> Option<&mut T> {
+        Some(&mut self.nodes.get_mut(id)?.data)
+    }
+
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    ///已加载的子节点
+    pub fn children(&self, id: usize) -> &[usize] {
+        self.nodes.get(id).and_then(|node| node.children.as_deref()).unwrap_or(&[])
+    }
+
+    pub fn visible(&self) -> &[usize] {
+        &self.visible
+    }
+
+    pub fn position(&self, id: usize) -> Option<usize> {
+        self.positions.get(id).copied().flatten()
+    }
+
+    ///更新visible中from之后节点的位置
+    fn reindex(&mut self, from: usize) {
+        for (position, id) in self.visible.iter().enumerate().skip(from) {
+            self.positions[*id] = Some(position);
+        }
+    }
+
+    ///id之下按显示顺序排列的已展开节点
+    fn collect_visible(&self, id: usize, res: &mut Vec<usize>) {
+        if !self.nodes[id].expanded { return; }
+        let mut stack = self.children(id).iter().rev().copied().collect::<Vec<_>>();
+        while let Some(id) = stack.pop() {
+            res.push(id);
+            if self.nodes[id].expanded { stack.extend(self.children(id).iter().rev()); }
+        }
+    }
+
+    ///展开节点，节点可见时将其已展开的子孙插入显示列表，返回是否改变
+    pub fn expand(&mut self, id: usize, provider: &dyn TreeProvider<T>) -> bool {
+        if id >= self.nodes.len() || self.nodes[id].expanded || !self.nodes[id].expandable { return false; }
+        self.load(id, provider);
+        if !self.nodes[id].expandable { return true; }
+        self.nodes[id].expanded = true;
+        if let Some(position) = self.position(id) {
+            let mut descendants = vec![];
+            self.collect_visible(id, &mut descendants);
+            self.visible.splice(position + 1..position + 1, descendants);
+            self.reindex(position + 1);
+        }
+        true
+    }
+
+    ///折叠节点，节点可见时从显示列表移除其子孙，返回是否改变
+    pub fn collapse(&mut self, id: usize) -> bool {
+        if id >= self.nodes.len() || !self.nodes[id].expanded { return false; }
+        self.nodes[id].expanded = false;
+        if let Some(position) = self.position(id) {
+            let depth = self.nodes[id].depth;
+            let count = self.visible[position + 1..].iter().take_while(|x| self.nodes[**x].depth > depth).count();
+            for id in self.visible.drain(position + 1..position + 1 + count) {
+                self.positions[id] = None;
+            }
+            self.reindex(position + 1);
+        }
+        true
+    }
+
+    ///展开全部节点，会加载所有子节点
+    pub fn expand_all(&mut self, provider: &dyn TreeProvider<T>) {
+        let mut stack = self.roots.clone();
+        while let Some(id) = stack.pop() {
+            if !self.nodes[id].expandable { continue; }
+            self.load(id, provider);
+            self.nodes[id].expanded = self.nodes[id].expandable;
+            stack.extend_from_slice(self.children(id));
+        }
+        self.rebuild();
+    }
+
+    pub fn collapse_all(&mut self) {
+        self.nodes.iter_mut().for_each(|node| node.expanded = false);
+        self.positions.fill(None);
+        self.visible = self.roots.clone();
+        self.reindex(0);
+    }
+
+    ///展开id的所有祖先节点，使其可见
+    pub fn expand_to(&mut self, id: usize, provider: &dyn TreeProvider<T>) {
+        let mut ancestors = vec![];
+        let mut parent = self.nodes.get(id).and_then(|node| node.parent);
+        while let Some(id) = parent {
+            ancestors.push(id);
+            parent = self.nodes[id].parent;
+        }
+        for ancestor in ancestors.into_iter().rev() {
+            self.expand(ancestor, provider);
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let mut visible = vec![];
+        for root in self.roots.iter() {
+            visible.push(*root);
+            self.collect_visible(*root, &mut visible);
+        }
+        self.visible = visible;
+        self.positions.fill(None);
+        self.reindex(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TreeNodes;
+    use crate::widgets::tree::TreeProvider;
+
+    ///节点n的子节点为n*10+1..=n*10+3，只展开到三位数
+    struct Numbers;
+
+    impl TreeProvider<u32> for Numbers {
+        fn text(&self, node: &u32) -> String {
+            node.to_string()
+        }
+
+        fn has_children(&self, node: &u32) -> bool {
+            *node < 100
+        }
+
+        fn children(&self, node: &u32) -> Vec<u32> {
+            (1..=3).map(|x| node * 10 + x).collect()
+        }
+    }
+
+    fn visible_data(nodes: &TreeNodes<u32>) -> Vec<u32> {
+        nodes.visible().iter().map(|x| nodes.get(*x).unwrap().data).collect()
+    }
+
+    fn assert_positions(nodes: &TreeNodes<u32>) {
+        for (position, id) in nodes.visible().iter().enumerate() {
+            assert_eq!(nodes.position(*id), Some(position));
+        }
+        let hidden = (0..nodes.nodes.len()).filter(|x| !nodes.visible().contains(x));
+        hidden.for_each(|id| assert_eq!(nodes.position(id), None));
+    }
+
+    #[test]
+    fn expand_collapse_keeps_positions() {
+        let mut nodes = TreeNodes::new();
+        nodes.set_roots(vec![1, 2], &Numbers);
+        assert!(nodes.expand(0, &Numbers));
+        let id = nodes.visible()[2];
+        assert!(nodes.expand(id, &Numbers));
+        assert_eq!(visible_data(&nodes), vec![1, 11, 12, 121, 122, 123, 13, 2]);
+        assert_positions(&nodes);
+        assert!(nodes.collapse(0));
+        assert_eq!(visible_data(&nodes), vec![1, 2]);
+        assert_positions(&nodes);
+        //再次展开时恢复已展开的子孙
+        assert!(nodes.expand(0, &Numbers));
+        assert_eq!(visible_data(&nodes), vec![1, 11, 12, 121, 122, 123, 13, 2]);
+        assert_positions(&nodes);
+    }
+
+    #[test]
+    fn expand_all_and_collapse_all() {
+        let mut nodes = TreeNodes::new();
+        nodes.set_roots(vec![1], &Numbers);
+        nodes.expand_all(&Numbers);
+        let data = visible_data(&nodes);
+        assert_eq!(data.len(), 1 + 3 + 9);
+        assert_eq!(&data[..6], &[1, 11, 111, 112, 113, 12]);
+        assert_positions(&nodes);
+        nodes.collapse_all();
+        assert_eq!(visible_data(&nodes), vec![1]);
+        assert_positions(&nodes);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::frame::context::UpdateType;
+use crate::render::{RenderParam, Visual, VisualStyle, WidgetStyle};
+use crate::response::{AppNotifies, AppNotify, Response};
+use crate::shape::Shape;
+use crate::size::Geometry;
+use crate::ui::Ui;
+use crate::widgets::tree::node::TreeNode;
+use crate::widgets::tree::{TreeAction, TreeActions};
+use crate::widgets::{Widget, WidgetSize, WidgetState};
+use crate::*;
+
+///树的一行，绑定显示列表中的一个节点
+pub(crate) struct TreeRow {
+    id: String,
+    visual: Visual,
+    arrow_right: RenderParam,
+    arrow_down: RenderParam,
+    guide_style: VisualStyle,
+    guides: Vec<RenderParam>,
+    layout: Option<LayoutKind>,
+    geometry: Geometry,
+    state: WidgetState,
+    indent: f32,
+    depth: usize,
+    expandable: bool,
+    expanded: bool,
+    selected: bool,
+    index: Option<usize>, //显示位置
+    actions: TreeActions,
+    notify: AppNotifies,
+    clicked_time: u128,
+}
+
+impl TreeRow {
+    pub fn new(width: f32, height: f32, indent: f32, actions: &TreeActions, notify: &AppNotifies) -> TreeRow {
+        let mut style = VisualStyle::same(WidgetStyle {
+            fill: Color::rgba(153, 193, 241, 220),
+            border: Border::same(0.0),
+            radius: Radius::same(2),
+            shadow: Shadow::new(),
+        });
+        style.inactive.fill = Color::TRANSPARENT;
+        style.hovered.fill = Color::rgb(230, 230, 230);
+        let arrow_style = VisualStyle::same((Color::rgb(110, 110, 110), 0.0, 0).into());
+        let mut arrow_right = RenderParam::new(Shape::triangle()).with_style(arrow_style.clone());
+        arrow_right.set_poses((0.0, 0.0).into(), (6.0, 4.0).into(), (0.0, 8.0).into());
+        let mut arrow_down = RenderParam::new(Shape::triangle()).with_style(arrow_style);
+        arrow_down.set_poses((0.0, 0.0).into(), (8.0, 0.0).into(), (4.0, 6.0).into());
+        TreeRow {
+            id: gen_unique_id(),
+            visual: Visual::new().with_enable().with_style(style).with_size(width, height),
+            arrow_right,
+            arrow_down,
+            guide_style: VisualStyle::same((Color::rgb(200, 200, 200), 0.0, 0).into()),
+            guides: vec![],
+            layout: None,
+            geometry: Geometry::new().with_context_size(width, height),
+            state: WidgetState::default(),
+            indent,
+            depth: 0,
+            expandable: false,
+            expanded: false,
+            selected: false,
+            index: None,
+            actions: actions.clone(),
+            notify: notify.clone(),
+            clicked_time: 0,
+        }
+    }
+
+    ///将显示位置index的节点绑定到当前行，content绘制节点内容
+    pub fn bind<T>(&mut self, ui: &mut Ui, index: usize, node: &TreeNode<T>, selected: bool, content: impl FnOnce(&mut Ui)) {
+        let depth = node.depth;
+        let left = (depth + 1) as f32 * self.indent;
+        let layout = HorizontalLayout::left_to_right().with_size(self.geometry.context_width(), self.geometry.context_height())
+            .with_padding(Padding::same(0.0).left(left));
+        let previous_layout = ui.layout.replace(LayoutKind::new(layout)).unwrap();
+        content(ui);
+        self.layout = ui.layout.replace(previous_layout);
+        let height = self.geometry.context_height();
+        let guide_style = &self.guide_style;
+        self.guides.resize_with(depth, || RenderParam::new(Shape::rectangle()).with_style(guide_style.clone()).with_size(1.0, height));
+        self.index = Some(index);
+        self.depth = depth;
+        self.expandable = node.expandable;
+        self.expanded = node.expanded;
+        self.selected = selected;
+    }
+
+    ///展开箭头所在的区域
+    fn arrow_rect(&self) -> Rect {
+        let mut rect = self.visual.rect().clone();
+        rect.add_min_x(self.depth as f32 * self.indent);
+        rect.set_width(self.indent);
+        rect
+    }
+
+    fn redraw(&mut self, ui: &mut Ui) {
+        self.visual.rect_mut().offset_to_rect(&ui.draw_rect);
+        for (depth, guide) in self.guides.iter_mut().enumerate() {
+            let mut rect = ui.draw_rect.clone();
+            rect.add_min_x(depth as f32 * self.indent + self.indent / 2.0);
+            guide.offset_to_rect(&rect);
+        }
+        let mut arrow_rect = ui.draw_rect.clone();
+        arrow_rect.add_min_x(self.depth as f32 * self.indent + (self.indent - 8.0) / 2.0);
+        arrow_rect.set_y_min(ui.draw_rect.dy().center() - 4.0);
+        self.arrow_right.offset_to_rect(&arrow_rect);
+        self.arrow_down.offset_to_rect(&arrow_rect);
+        self.visual.draw(ui, self.state.disabled, self.state.hovered || self.selected, self.selected, false);
+        for guide in self.guides.iter_mut() {
+            guide.draw(ui, false, false, false);
+        }
+        match (self.expandable, self.expanded) {
+            (true, true) => self.arrow_down.draw(ui, false, false, false),
+            (true, false) => self.arrow_right.draw(ui, false, false, false),
+            _ => {}
+        }
+    }
+}
+
+impl Widget for TreeRow {
+    fn update(&mut self, ui: &mut Ui) -> Response<'_> {
+        AppNotify::emit(&self.notify, ui);
+        match ui.update_type {
+            #[cfg(feature = "gpu")]
+            UpdateType::ReInit => self.visual.re_init(),
+            UpdateType::Draw => self.redraw(ui),
+            UpdateType::MouseMove => {
+                let hovered = ui.device.device_input.hovered_at(self.visual.rect());
+                if self.state.on_hovered(hovered) { ui.context.window.request_redraw(); }
+            }
+            UpdateType::MouseRelease => {
+                if let Some(index) = self.index && ui.device.device_input.click_at(self.visual.rect()) {
+                    let mut actions = self.actions.write().unwrap();
+                    let now = crate::time_ms();
+                    if self.expandable && ui.device.device_input.click_at(&self.arrow_rect()) {
+                        actions.push(TreeAction::Toggle(index));
+                    } else {
+                        actions.push(TreeAction::Select(index));
+                        //双击展开或折叠
+                        if self.expandable && now - self.clicked_time < 400 { actions.push(TreeAction::Toggle(index)); }
+                    }
+                    self.clicked_time = now;
+                    ui.context.window.request_redraw();
+                }
+            }
+            _ => {}
+        }
+        if let Some(layout) = self.layout.as_mut() { layout.update(ui); }
+        Response::new(&self.id, WidgetSize::same(self.geometry.margin_width(), self.geometry.margin_height()))
+    }
+
+    fn geometry(&mut self) -> &mut Geometry {
+        &mut self.geometry
+    }
+
+    fn state(&mut self) -> &mut WidgetState {
+        &mut self.state
+    }
+}
@@ -0,0 +1,511 @@
+use crate::frame::App;
+use crate::response::Callback;
+use crate::ui::Ui;
+use crate::widgets::button::Button;
+use crate::widgets::calendar::date::{local_secs, Date, Time};
+use crate::widgets::checkbox::CheckBox;
+use crate::widgets::combo::ComboBox;
+use crate::widgets::label::Label;
+use crate::widgets::table::column::{SortOrder, TableColumn};
+use crate::widgets::table::selection::TableSelectionMode;
+use crate::widgets::table::value::{CellValue, SortKey};
+use crate::widgets::table::{TableExt, TableView};
+use crate::widgets::textedit::TextEdit;
+use crate::widgets::tree::{TreeProvider, TreeView};
+use crate::window::dialog::{MessageBox, MessageButton};
+use crate::window::inner::InnerWindow;
+use crate::*;
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+#[derive(Copy, Clone, PartialEq)]
+enum FileDialogMode {
+    Open,
+    Save,
+}
+
+///文件名过滤，支持*及?通配符，不区分大小写
+#[derive(Clone)]
+struct FileFilter {
+    name: String,
+    patterns: Vec<String>,
+}
+
+impl FileFilter {
+    fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase().chars().collect::<Vec<_>>();
+        self.patterns.iter().any(|pattern| wildcard(&pattern.to_lowercase().chars().collect::<Vec<_>>(), &name))
+    }
+}
+
+impl Display for FileFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.patterns.join(" "))
+    }
+}
+
+fn wildcard(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => wildcard(&pattern[1..], name) || (!name.is_empty() && wildcard(pattern, &name[1..])),
+        (Some('?'), Some(_)) => wildcard(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => wildcard(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name().is_some_and(|x| x.to_string_lossy().starts_with('.'))
+}
+
+///目录树，只显示目录
+struct DirProvider {
+    hidden: Rc<Cell<bool>>,
+}
+
+impl TreeProvider<PathBuf> for DirProvider {
+    fn text(&self, node: &PathBuf) -> String {
+        match node.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => node.display().to_string(),
+        }
+    }
+
+    fn has_children(&self, node: &PathBuf) -> bool {
+        node.is_dir()
+    }
+
+    fn children(&self, node: &PathBuf) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(node) else { return vec![]; };
+        let mut children = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path())
+            .filter(|path| path.is_dir() && (self.hidden.get() || !is_hidden(path))).collect::<Vec<_>>();
+        children.sort_by_key(|path| path.file_name().map(|x| x.to_string_lossy().to_lowercase()));
+        children
+    }
+}
+
+///文件列表中的一项
+struct FileEntry {
+    path: PathBuf,
+    name: String,
+    dir: bool,
+    size: u64,
+    modified: u64,
+}
+
+impl FileEntry {
+    fn new(path: PathBuf) -> FileEntry {
+        let metadata = std::fs::metadata(&path).ok();
+        FileEntry {
+            name: path.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_default(),
+            dir: path.is_dir(),
+            size: metadata.as_ref().map_or(0, |x| x.len()),
+            modified: metadata.and_then(|x| x.modified().ok()).and_then(|x| x.duration_since(UNIX_EPOCH).ok()).map_or(0, |x| x.as_secs()),
+            path,
+        }
+    }
+
+    fn size_text(&self) -> String {
+        if self.dir { return String::new(); }
+        let units = ["B", "KB", "MB", "GB", "TB"];
+        let mut size = self.size as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < units.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 { format!("{} B", self.size) } else { format!("{:.1} {}", size, units[unit]) }
+    }
+
+    ///本地时间
+    fn modified_text(&self) -> String {
+        if self.modified == 0 { return String::new(); }
+        let local = local_secs(self.modified as i64);
+        let date = Date::from_days(local.div_euclid(86400));
+        let seconds = local.rem_euclid(86400) as u32;
+        let time = Time::new(seconds / 3600, seconds % 3600 / 60, 0).unwrap_or_default();
+        format!("{} {}", date, time.format("%H:%M"))
+    }
+}
+
+impl TableExt for FileEntry {
+    fn cols(&self) -> Vec<impl ToString> {
+        let name = if self.dir { format!("{}/", self.name) } else { self.name.clone() };
+        vec![name, self.size_text(), self.modified_text()]
+    }
+
+    fn value(&self, column: usize) -> CellValue {
+        match column {
+            1 => CellValue::Number(self.size as f64),
+            2 => CellValue::Number(self.modified as f64),
+            _ => CellValue::Text(self.name.to_lowercase()),
+        }
+    }
+
+    ///目录排在文件前
+    fn sort_key(&self, column: usize) -> SortKey {
+        SortKey::Composite(vec![SortKey::Bool(!self.dir), self.value(column).sort_key()])
+    }
+}
+
+/// ### FileDialog的示例用法
+/// ```
+/// use std::path::PathBuf;
+/// use xlui::*;
+///
+/// struct XlUi;
+///
+/// impl XlUi {
+///     fn opened(&mut self, _: &mut Ui, files: Vec<PathBuf>) {
+///         //取消时为空
+///         println!("{:?}", files);
+///     }
+/// }
+///
+/// fn open(ui: &mut Ui) {
+///     //以模态窗口显示，双击目录进入，双击文件或点击打开后关闭
+///     FileDialog::open()
+///         .with_directory(PathBuf::from("/"))
+///         .with_filter("图片", &["*.png", "*.jpg"])
+///         .with_filter("所有文件", &["*"])
+///         .with_multi_select(true)
+///         .show(ui, XlUi::opened);
+///     //保存模式下文件已存在时弹出询问框确认覆盖
+///     FileDialog::save().with_file_name("untitled.txt").show(ui, XlUi::opened);
+/// }
+/// ```
+pub struct FileDialog {
+    mode: FileDialogMode,
+    title: String,
+    directory: PathBuf,
+    filters: Vec<FileFilter>,
+    filter: usize,
+    multi_select: bool,
+    hidden: Rc<Cell<bool>>,
+    file_name: String,
+    ///待确认覆盖的文件
+    overwrite: Option<PathBuf>,
+    tree: TreeView<PathBuf>,
+    table: TableView<FileEntry>,
+    path_id: String,
+    name_id: String,
+    status_id: String,
+    result: Option<Vec<PathBuf>>,
+    close: Option<Arc<AtomicBool>>,
+    size: Size,
+    position: [i32; 2],
+}
+
+impl FileDialog {
+    fn new(mode: FileDialogMode, title: &str) -> FileDialog {
+        let hidden = Rc::new(Cell::new(false));
+        let mut tree = TreeView::new(vec![], DirProvider { hidden: hidden.clone() }).with_size(180.0, 300.0);
+        tree.set_selection_callback(FileDialog::tree_selected);
+        let columns = vec![
+            TableColumn::new_name("名称").with_sort(true).with_width(220.0),
+            TableColumn::new_name("大小").with_sort(true).with_width(80.0),
+            TableColumn::new_name("修改时间").with_sort(true).with_width(130.0),
+        ];
+        let mut table = TableView::new(columns).with_size(440.0, 300.0);
+        table.set_selection_callback(FileDialog::table_selected);
+        FileDialog {
+            mode,
+            title: title.to_string(),
+            directory: std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")),
+            filters: vec![],
+            filter: 0,
+            multi_select: false,
+            hidden,
+            file_name: String::new(),
+            overwrite: None,
+            tree,
+            table,
+            path_id: gen_unique_id(),
+            name_id: gen_unique_id(),
+            status_id: gen_unique_id(),
+            result: None,
+            close: None,
+            size: Size { width: 640.0, height: 440.0 },
+            position: [0, 0],
+        }
+    }
+
+    ///选择已有文件，默认目录为当前工作目录
+    pub fn open() -> FileDialog {
+        FileDialog::new(FileDialogMode::Open, "打开")
+    }
+
+    ///输入或选择保存的文件名
+    pub fn save() -> FileDialog {
+        FileDialog::new(FileDialogMode::Save, "保存")
+    }
+
+    pub fn with_title(mut self, title: impl ToString) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    ///初始目录
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    ///添加文件名过滤，第一个为默认过滤，目录不受过滤影响
+    pub fn with_filter(mut self, name: impl ToString, patterns: &[&str]) -> Self {
+        self.filters.push(FileFilter {
+            name: name.to_string(),
+            patterns: patterns.iter().map(|x| x.to_string()).collect(),
+        });
+        self
+    }
+
+    ///打开模式下可选择多个文件
+    pub fn with_multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    ///显示以.开头的隐藏文件及目录
+    pub fn with_hidden(self, hidden: bool) -> Self {
+        self.hidden.set(hidden);
+        self
+    }
+
+    ///保存模式下的默认文件名
+    pub fn with_file_name(mut self, name: impl ToString) -> Self {
+        self.file_name = name.to_string();
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Size { width, height };
+        self
+    }
+
+    ///选择的文件，未确定时为None
+    pub fn result(&self) -> Option<&Vec<PathBuf>> {
+        self.result.as_ref()
+    }
+
+    ///在当前窗口中居中显示模态窗口，确定后关闭并通过f返回选择的文件，取消或直接关闭时为空
+    pub fn show<A: 'static>(mut self, ui: &mut Ui, f: fn(&mut A, &mut Ui, Vec<PathBuf>)) {
+        let size = ui.context.window.size();
+        self.position = [((size.width - self.size.width) / 2.0).max(0.0) as i32, ((size.height - self.size.height) / 2.0).max(0.0) as i32];
+        self.directory = std::fs::canonicalize(&self.directory).unwrap_or(self.directory);
+        if self.mode == FileDialogMode::Save { self.multi_select = false; }
+        if self.multi_select { self.table.set_selection_mode(TableSelectionMode::Multi); }
+        let root = self.directory.ancestors().last().unwrap_or(&self.directory).to_path_buf();
+        self.tree.set_roots(vec![root]);
+        self.reveal();
+        self.reload();
+        let window = ui.create_inner_window(self);
+        window.set_modal(true);
+        let close = window.request_close.clone();
+        window.app_mut::<FileDialog>().unwrap().close = Some(close);
+        window.on_close = Some(Callback::create_inner_close(move |app: &mut A, window: InnerWindow, ui: &mut Ui| {
+            let result = window.to_::<FileDialog>().result.unwrap_or_default();
+            f(app, ui, result);
+        }));
+    }
+
+    ///展开目录树到当前目录并选中
+    fn reveal(&mut self) {
+        let mut ids = self.tree.roots().to_vec();
+        while let Some(id) = ids.iter().cloned().find(|x| self.tree.node(*x).is_some_and(|path| self.directory.starts_with(path))) {
+            if self.tree.node(id) == Some(&self.directory) {
+                if self.tree.current_index() != Some(id) { self.tree.select(id); }
+                return;
+            }
+            self.tree.expand(id);
+            ids = self.tree.children(id).to_vec();
+        }
+    }
+
+    ///按过滤及隐藏设置重新读取当前目录
+    fn reload(&mut self) {
+        let filter = self.filters.get(self.filter);
+        let hidden = self.hidden.get();
+        let entries = std::fs::read_dir(&self.directory).map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| FileEntry::new(entry.path()))
+            .filter(|entry| hidden || !is_hidden(&entry.path))
+            .filter(|entry| entry.dir || filter.is_none_or(|filter| filter.matches(&entry.name)))
+            .collect::<Vec<_>>()).unwrap_or_default();
+        self.table.set_data(entries);
+        if self.table.sorts().is_empty() { self.table.sort_by_column(0, Some(SortOrder::Ascending), false); }
+    }
+
+    fn navigate(&mut self, ui: &mut Ui, directory: PathBuf) {
+        if directory == self.directory || !directory.is_dir() { return; }
+        self.directory = directory;
+        self.reveal();
+        self.reload();
+        ui.set_value(&self.path_id, self.directory.display().to_string());
+        ui.set_value(&self.status_id, String::new());
+        ui.context.window.request_redraw();
+    }
+
+    fn up(&mut self, _: &mut Button, ui: &mut Ui) {
+        if let Some(parent) = self.directory.parent() { self.navigate(ui, parent.to_path_buf()); }
+    }
+
+    fn goto(&mut self, _: &mut Button, ui: &mut Ui) {
+        let path = ui.get_widget::<TextEdit>(&self.path_id).map(|x| x.text()).unwrap_or_default();
+        let path = PathBuf::from(path.trim());
+        if path.is_dir() {
+            self.navigate(ui, std::fs::canonicalize(&path).unwrap_or(path));
+        } else {
+            ui.set_value(&self.status_id, format!("“{}”不是目录", path.display()));
+        }
+    }
+
+    fn tree_selected(&mut self, ui: &mut Ui) {
+        if let Some(directory) = self.tree.current().cloned() { self.navigate(ui, directory); }
+    }
+
+    ///保存模式下选中文件时填入文件名
+    fn table_selected(&mut self, ui: &mut Ui) {
+        if self.mode != FileDialogMode::Save { return; }
+        let Some(entry) = self.table.current() else { return; };
+        if entry.dir { return; }
+        self.file_name = entry.name.clone();
+        ui.set_value(&self.name_id, &self.file_name);
+    }
+
+    fn name_changed(&mut self, _: &mut Ui, name: String) {
+        self.file_name = name;
+    }
+
+    fn filter_changed(&mut self, _: &mut Ui, filter: &FileFilter) {
+        self.filter = self.filters.iter().position(|x| x.name == filter.name).unwrap_or(0);
+        self.reload();
+    }
+
+    fn hidden_changed(&mut self, _: &mut Ui, hidden: bool) {
+        self.hidden.set(hidden);
+        let roots = self.tree.roots().iter().filter_map(|x| self.tree.node(*x).cloned()).collect();
+        self.tree.set_roots(roots);
+        self.reveal();
+        self.reload();
+    }
+
+    ///双击或回车时进入目录或选择文件
+    fn activate(&mut self, ui: &mut Ui, index: usize) {
+        let Some(entry) = self.table.row(index) else { return; };
+        let (path, dir, name) = (entry.path.clone(), entry.dir, entry.name.clone());
+        if dir { return self.navigate(ui, path); }
+        match self.mode {
+            FileDialogMode::Open => self.finish(Some(vec![path])),
+            FileDialogMode::Save => {
+                self.file_name = name;
+                ui.set_value(&self.name_id, &self.file_name);
+                self.accept_save(ui);
+            }
+        }
+    }
+
+    fn accept(&mut self, _: &mut Button, ui: &mut Ui) {
+        match self.mode {
+            FileDialogMode::Open => self.accept_open(ui),
+            FileDialogMode::Save => self.accept_save(ui),
+        }
+    }
+
+    ///只选中一个目录时进入该目录
+    fn accept_open(&mut self, ui: &mut Ui) {
+        let selected = self.table.selected_rows();
+        let mut files = vec![];
+        let mut directory = None;
+        for entry in selected.into_iter().filter_map(|x| self.table.row(x)) {
+            if entry.dir { directory = Some(entry.path.clone()); } else { files.push(entry.path.clone()); }
+        }
+        match directory {
+            Some(directory) if files.is_empty() => self.navigate(ui, directory),
+            _ if files.is_empty() => ui.set_value(&self.status_id, "请选择文件".to_string()),
+            _ => self.finish(Some(files)),
+        }
+    }
+
+    ///文件已存在时弹出询问框确认覆盖
+    fn accept_save(&mut self, ui: &mut Ui) {
+        let name = self.file_name.trim();
+        if name.is_empty() { return ui.set_value(&self.status_id, "请输入文件名".to_string()); }
+        let path = self.directory.join(name);
+        if path.is_dir() { return self.navigate(ui, path); }
+        if path.exists() {
+            let text = format!("“{}”已存在，是否覆盖？", name);
+            self.overwrite = Some(path);
+            return MessageBox::question("保存", text).show(ui, Self::overwrite_answered);
+        }
+        self.finish(Some(vec![path]));
+    }
+
+    fn overwrite_answered(&mut self, _: &mut Ui, button: MessageButton) {
+        let Some(path) = self.overwrite.take() else { return; };
+        if button == MessageButton::Yes { self.finish(Some(vec![path])); }
+    }
+
+    fn cancel(&mut self, _: &mut Button, _: &mut Ui) {
+        self.finish(None);
+    }
+
+    fn finish(&mut self, result: Option<Vec<PathBuf>>) {
+        self.result = result;
+        if let Some(ref close) = self.close { close.store(true, AtomicOrdering::SeqCst); }
+    }
+}
+
+impl App for FileDialog {
+    fn draw(&mut self, ui: &mut Ui) {
+        let width = self.size.width - 20.0;
+        ui.horizontal(|ui| {
+            ui.add(Button::new("上级").width(50.0).connect(Self::up));
+            ui.add(TextEdit::single_edit(self.directory.display()).with_id(&self.path_id).with_width(width - 130.0));
+            ui.add(Button::new("转到").width(50.0).connect(Self::goto));
+        });
+        ui.horizontal(|ui| {
+            self.tree.show(ui);
+            self.table.show(ui);
+        });
+        if self.mode == FileDialogMode::Save {
+            ui.horizontal(|ui| {
+                ui.add(Label::new("文件名").width(50.0));
+                ui.add(TextEdit::single_edit(&self.file_name).with_id(&self.name_id).with_width(width - 60.0).connect(Self::name_changed));
+            });
+        }
+        ui.horizontal(|ui| {
+            if !self.filters.is_empty() {
+                ui.add(ComboBox::new(self.filters.clone()).with_size(180.0, 25.0).with_current_index(self.filter).connect(Self::filter_changed));
+            }
+            ui.add(CheckBox::new(self.hidden.get(), "显示隐藏文件").connect(Self::hidden_changed));
+        });
+        ui.add_layout(HorizontalLayout::right_to_left(), |ui| {
+            ui.add(Button::new("取消").width(70.0).connect(Self::cancel));
+            let text = match self.mode {
+                FileDialogMode::Open => "打开",
+                FileDialogMode::Save => "保存",
+            };
+            ui.add(Button::new(text).width(70.0).connect(Self::accept));
+            ui.add(Label::new("").with_id(&self.status_id).width(width - 170.0));
+        });
+    }
+
+    fn update(&mut self, ui: &mut Ui) {
+        self.tree.update(ui);
+        self.table.update(ui);
+        if let Some(index) = self.table.take_activated() { self.activate(ui, index); }
+    }
+
+    fn window_attributes(&self) -> WindowAttribute {
+        WindowAttribute {
+            inner_size: self.size,
+            position: self.position,
+            title: self.title.clone(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::frame::App;
+use crate::response::Callback;
+use crate::ui::Ui;
+use crate::widgets::button::Button;
+use crate::widgets::label::Label;
+use crate::window::inner::InnerWindow;
+use crate::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+pub mod file;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MessageButton {
+    Ok,
+    Cancel,
+    Yes,
+    No,
+}
+
+impl MessageButton {
+    fn text(&self) -> &'static str {
+        match self {
+            MessageButton::Ok => "确定",
+            MessageButton::Cancel => "取消",
+            MessageButton::Yes => "是",
+            MessageButton::No => "否",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum MessageKind {
+    Info,
+    Warning,
+    Question,
+}
+
+/// ### MessageBox的示例用法
+/// ```
+/// use xlui::*;
+///
+/// struct XlUi;
+///
+/// impl XlUi {
+///     fn on_result(&mut self, _: &mut Ui, button: MessageButton) {
+///         if button == MessageButton::Yes { println!("保存"); }
+///     }
+/// }
+///
+/// fn ask(ui: &mut Ui) {
+///     //以模态窗口显示，关闭前父窗口的输入被阻止
+///     MessageBox::question("提示", "是否保存修改？")
+///         .with_buttons(&[MessageButton::Yes, MessageButton::No, MessageButton::Cancel])
+///         .show(ui, XlUi::on_result);
+///     MessageBox::info("提示", "保存成功").show(ui, |_: &mut XlUi, _, _| {});
+/// }
+/// ```
+pub struct MessageBox {
+    title: String,
+    text: String,
+    kind: MessageKind,
+    buttons: Vec<MessageButton>,
+    result: Option<MessageButton>,
+    close: Option<Arc<AtomicBool>>,
+    size: Size,
+    position: [i32; 2],
+}
+
+impl MessageBox {
+    fn new(kind: MessageKind, title: impl ToString, text: impl ToString, buttons: Vec<MessageButton>) -> MessageBox {
+        MessageBox {
+            title: title.to_string(),
+            text: text.to_string(),
+            kind,
+            buttons,
+            result: None,
+            close: None,
+            size: Size { width: 320.0, height: 140.0 },
+            position: [0, 0],
+        }
+    }
+
+    ///默认按钮为确定
+    pub fn info(title: impl ToString, text: impl ToString) -> MessageBox {
+        MessageBox::new(MessageKind::Info, title, text, vec![MessageButton::Ok])
+    }
+
+    ///默认按钮为确定、取消
+    pub fn warning(title: impl ToString, text: impl ToString) -> MessageBox {
+        MessageBox::new(MessageKind::Warning, title, text, vec![MessageButton::Ok, MessageButton::Cancel])
+    }
+
+    ///默认按钮为是、否
+    pub fn question(title: impl ToString, text: impl ToString) -> MessageBox {
+        MessageBox::new(MessageKind::Question, title, text, vec![MessageButton::Yes, MessageButton::No])
+    }
+
+    ///按钮按给定顺序从左到右排列
+    pub fn with_buttons(mut self, buttons: &[MessageButton]) -> Self {
+        self.buttons = buttons.to_vec();
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Size { width, height };
+        self
+    }
+
+    ///点击的按钮，未点击时为None
+    pub fn result(&self) -> Option<MessageButton> {
+        self.result
+    }
+
+    ///在当前窗口中居中显示模态窗口，点击按钮后关闭并通过f返回结果，直接关闭窗口时结果为Cancel
+    pub fn show<A: 'static>(mut self, ui: &mut Ui, f: fn(&mut A, &mut Ui, MessageButton)) {
+        let size = ui.context.window.size();
+        self.position = [((size.width - self.size.width) / 2.0).max(0.0) as i32, ((size.height - self.size.height) / 2.0).max(0.0) as i32];
+        let window = ui.create_inner_window(self);
+        window.set_modal(true);
+        let close = window.request_close.clone();
+        window.app_mut::<MessageBox>().unwrap().close = Some(close);
+        window.on_close = Some(Callback::create_inner_close(move |app: &mut A, window: InnerWindow, ui: &mut Ui| {
+            let result = window.to_::<MessageBox>().result.unwrap_or(MessageButton::Cancel);
+            f(app, ui, result);
+        }));
+    }
+
+    fn finish(&mut self, button: MessageButton) {
+        self.result = Some(button);
+        if let Some(ref close) = self.close { close.store(true, Ordering::SeqCst); }
+    }
+}
+
+impl App for MessageBox {
+    fn draw(&mut self, ui: &mut Ui) {
+        let (icon, color) = match self.kind {
+            MessageKind::Info => ("i", Color::rgb(33, 150, 243)),
+            MessageKind::Warning => ("!", Color::rgb(251, 140, 0)),
+            MessageKind::Question => ("?", Color::rgb(67, 160, 71)),
+        };
+        ui.horizontal(|ui| {
+            ui.add(Label::new(icon.color(color).size(24.0)).width(30.0));
+            ui.add(Label::new(self.text.as_str().wrap(TextWrap::WrapAny)).width(self.size.width - 60.0));
+        });
+        ui.add_space(10.0);
+        ui.add_layout(HorizontalLayout::right_to_left(), |ui| {
+            for button in self.buttons.iter().rev().copied() {
+                ui.add(Button::new(button.text()).width(70.0).connect(move |msg: &mut MessageBox, _, _| msg.finish(button)));
+            }
+        });
+    }
+
+    fn window_attributes(&self) -> WindowAttribute {
+        WindowAttribute {
+            inner_size: self.size,
+            position: self.position,
+            title: self.title.clone(),
+            ..Default::default()
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{MousePos, Pos, Size};
-use crate::key::Key;
+use crate::key::{Key, KeyModifiers};
 #[cfg(not(feature = "gpu"))]
 use crate::ui::PaintParam;
 use crate::window::ClipboardData;
@@ -11,8 +11,8 @@ pub enum WindowEvent {
     KeyRelease(Key),
     MouseMove(MousePos),
     MouseWheel(f32),
-    MousePress(Pos),
-    MouseRelease(Pos),
+    MousePress(Pos, KeyModifiers),
+    MouseRelease(Pos, KeyModifiers),
     #[cfg(feature = "gpu")]
     Redraw,
     #[cfg(not(feature = "gpu"))]
@@ -36,6 +36,7 @@ pub struct InnerWindow {
     inner_windows: Option<Map<WindowId, InnerWindow>>,
     pub(crate) request_close: Arc<AtomicBool>,
     pub(crate) top: bool,
+    pub(crate) modal: bool,
 }
 
 impl InnerWindow {
@@ -71,6 +72,7 @@ impl InnerWindow {
             request_close: Arc::new(AtomicBool::new(false)),
             attr,
             top: false,
+            modal: false,
         };
         window.draw_title(ui);
         window.draw_context(ui);
@@ -186,6 +188,26 @@ impl InnerWindow {
         *app
     }
 
+    ///获取窗口中的App
+    pub fn app_mut<W: App>(&mut self) -> Option<&mut W> {
+        let app = self.w.as_mut() as &mut dyn Any;
+        app.downcast_mut()
+    }
+
+    ///模态窗口打开时，父窗口的输入被阻止并绘制遮罩
+    pub fn set_modal(&mut self, modal: bool) {
+        self.modal = modal;
+    }
+
+    pub fn is_modal(&self) -> bool {
+        self.modal
+    }
+
+    ///请求关闭窗口，关闭时调用on_close
+    pub fn close(&self) {
+        self.request_close.store(true, Ordering::SeqCst);
+    }
+
     pub fn on_close<A: App>(&mut self, f: impl FnMut(&mut A, InnerWindow, &mut Ui) + 'static) {
         self.on_close = Some(Callback::create_inner_close(f));
     }
@@ -215,19 +237,27 @@ impl InnerWindow {
         self.offset.x = 0.0;
         self.offset.y = 0.0;
         self.popups = nui.popups.take();
+        for inner_window in self.inner_windows.as_mut().unwrap().iter_mut() {
+            inner_window.redraw(&mut nui);
+        }
         oui.paint = nui.paint.take();
     }
 
     pub fn update(&mut self, oui: &mut Ui) {
         if self.window_update(oui) { return; }
         if !oui.device.device_input.hovered_at(self.visual.rect()) && !self.press_title { return; }
+        //模态子窗口打开时仅由子窗口处理输入
+        let modal = self.inner_windows.as_ref().unwrap().iter().any(|x| x.modal);
+        let blocked = modal && matches!(oui.update_type,
+            UpdateType::MouseMove | UpdateType::MousePress | UpdateType::MouseRelease | UpdateType::MouseWheel |
+            UpdateType::KeyPress(_) | UpdateType::KeyRelease(_) | UpdateType::IME(_));
         let mut nui = Ui {
             device: oui.device,
             context: oui.context,
             app: None,
             layout: self.layout.take(),
             popups: self.popups.take(),
-            update_type: oui.update_type.clone(),
+            update_type: if blocked { UpdateType::None } else { oui.update_type.clone() },
             can_offset: oui.can_offset,
             inner_windows: self.inner_windows.take(),
             request_update: None,
@@ -237,11 +267,25 @@ impl InnerWindow {
             disabled: false,
         };
         self.w.update(&mut nui);
+        nui.update_type = oui.update_type.clone();
         nui.app = Some(&mut self.w);
         self.inner_windows = nui.inner_windows.take();
         for inner_window in self.inner_windows.as_mut().unwrap().iter_mut() {
             inner_window.update(&mut nui);
         }
+        let closed: Vec<WindowId> = self.inner_windows.as_ref().unwrap().iter()
+            .filter(|x| x.request_close.load(Ordering::SeqCst)).map(|x| x.id).collect();
+        for id in closed {
+            let mut win = self.inner_windows.as_mut().unwrap().remove(&id).unwrap();
+            nui.context.window.request_redraw();
+            let Some(mut callback) = win.on_close.take() else { continue; };
+            nui.inner_windows = self.inner_windows.take();
+            let app = nui.app.take().unwrap();
+            callback(app, win, &mut nui);
+            nui.app.replace(app);
+            self.inner_windows = nui.inner_windows.take();
+        }
+        if blocked { nui.update_type = UpdateType::None; }
         nui.inner_windows = self.inner_windows.take();
         self.popups = nui.popups.take();
         for popup in self.popups.as_mut().unwrap().iter_mut() {
@@ -1,5 +1,6 @@
 pub mod attribute;
 pub mod inner;
+pub mod dialog;
 #[cfg(all(target_os = "linux", not(feature = "winit")))]
 pub mod x11;
 #[cfg(not(feature = "winit"))]
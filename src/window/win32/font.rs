@@ -1,15 +1,17 @@
 use crate::text::cchar::{CChar, LineChar};
+use crate::text::FontMetrics;
 use crate::window::win32::until;
 use crate::{RichText, Ui, UiResult};
 use std::mem;
 use std::mem::zeroed;
 use std::ptr::null_mut;
 use windows::core::PCWSTR;
-use windows::Win32::Graphics::Gdi::{CreateCompatibleDC, CreateFontW, DeleteDC, DeleteObject, GetCharWidth32W, GetDeviceCaps, GetTextMetricsW, SelectObject, FONT_CHARSET, FONT_CLIP_PRECISION, FONT_OUTPUT_PRECISION, FONT_QUALITY, HDC, HFONT, HGDIOBJ, LOGPIXELSY};
+use windows::Win32::Graphics::Gdi::{CreateCompatibleDC, CreateFontW, DeleteDC, DeleteObject, GetCharWidth32W, GetDeviceCaps, GetGlyphIndicesW, GetOutlineTextMetricsW, GetTextMetricsW, SelectObject, FONT_CHARSET, FONT_CLIP_PRECISION, FONT_OUTPUT_PRECISION, FONT_QUALITY, GGI_MARK_NONEXISTING_GLYPHS, HDC, HFONT, HGDIOBJ, LOGPIXELSY, OUTLINETEXTMETRICW};
 
 pub struct Win32Font {
     family: String,
     size: f32,
+    fallbacks: Vec<String>,
     hdc: HDC,
 }
 
@@ -18,6 +20,7 @@ impl Win32Font {
         Win32Font {
             family: "FangSong".to_string(),
             size: 14.0,
+            fallbacks: vec![],
             hdc: HDC(null_mut()),
         }
     }
@@ -27,6 +30,8 @@ impl Win32Font {
         let size = text.size.get_or_insert_with(|| ui.context.font.size());
         self.family = family.to_string();
         self.size = *size;
+        text.resolve_fallbacks(&mut ui.context.font);
+        self.fallbacks = text.fallbacks.clone();
         self.init();
         Ok(())
     }
@@ -63,10 +68,11 @@ impl Win32Font {
         unsafe {
             let hfont = self.create_font(text.height as i32, text.family.as_ref().unwrap());
             let old_font = SelectObject(self.hdc, HGDIOBJ::from(hfont));
+            let fallbacks: Vec<HFONT> = self.fallbacks.iter().map(|x| self.create_font(text.height as i32, x)).collect();
             let lines = text.text.replace("\r\n", "\n");
             let mut res = vec![];
             for line in lines.split("\n") {
-                let mut lines = self.measure_line(line, wrap, max_wrap).unwrap();
+                let mut lines = self.measure_line(line, wrap, max_wrap, &fallbacks).unwrap();
                 res.append(&mut lines);
             }
             if let Some(last) = res.last_mut() {
@@ -75,6 +81,9 @@ impl Win32Font {
             // 清理
             SelectObject(self.hdc, old_font);
             DeleteObject(HGDIOBJ::from(hfont)).ok()?;
+            for font in fallbacks {
+                DeleteObject(HGDIOBJ::from(font)).ok()?;
+            }
             Ok(res)
         }
     }
@@ -98,11 +107,39 @@ impl Win32Font {
         }
     }
 
-    pub fn measure_line(&self, line: &str, wrap: bool, max_wrap: f32) -> UiResult<Vec<LineChar>> {
+    ///装饰线度量，取自OUTLINETEXTMETRICW，非TrueType字体时按ascent估算
+    pub(crate) fn metrics(&self) -> UiResult<FontMetrics> {
+        //与绘制时相同，字体高度使用行高
+        let height = self.line_height()?;
+        unsafe {
+            let font = self.create_font(height as i32, &self.family);
+            let old = SelectObject(self.hdc, HGDIOBJ::from(font));
+            let mut otm: OUTLINETEXTMETRICW = zeroed();
+            let size = GetOutlineTextMetricsW(self.hdc, size_of::<OUTLINETEXTMETRICW>() as u32, Some(&mut otm));
+            let metrics = if size == 0 {
+                let mut tm = zeroed();
+                GetTextMetricsW(self.hdc, &mut tm).ok()?;
+                FontMetrics::estimate(tm.tmAscent as f32, tm.tmDescent as f32)
+            } else {
+                FontMetrics {
+                    ascent: otm.otmTextMetrics.tmAscent as f32,
+                    underline_position: -(otm.otmsUnderscorePosition as f32),
+                    underline_thickness: (otm.otmsUnderscoreSize as f32).max(1.0),
+                    strikeout_position: otm.otmsStrikeoutPosition as f32,
+                    strikeout_thickness: (otm.otmsStrikeoutSize as f32).max(1.0),
+                }
+            };
+            SelectObject(self.hdc, old);
+            DeleteObject(HGDIOBJ::from(font)).ok()?;
+            Ok(metrics)
+        }
+    }
+
+    pub fn measure_line(&self, line: &str, wrap: bool, max_wrap: f32, fallbacks: &[HFONT]) -> UiResult<Vec<LineChar>> {
         let mut res = vec![];
         let mut line_char = LineChar::new();
         for ch in line.chars() {
-            let cchar = self.measure_char(ch)?;
+            let cchar = self.measure_char_with_fallback(ch, fallbacks)?;
             if wrap && line_char.width + cchar.width >= max_wrap {
                 let mut line = mem::take(&mut line_char);
                 line.auto_wrap = true;
@@ -117,6 +154,26 @@ impl Win32Font {
         Ok(res)
     }
 
+    ///当前选入hdc的字体是否包含字符
+    fn has_glyph(&self, cc: char) -> bool {
+        let text = cc.to_string().encode_utf16().collect::<Vec<u16>>();
+        let mut indices = vec![0u16; text.len()];
+        let res = unsafe { GetGlyphIndicesW(self.hdc, PCWSTR(text.as_ptr()), text.len() as i32, indices.as_mut_ptr(), GGI_MARK_NONEXISTING_GLYPHS) };
+        res != u32::MAX && indices.iter().all(|x| *x != 0xFFFF)
+    }
+
+    ///主字体缺少字符时依次使用回退字体测量
+    fn measure_char_with_fallback(&self, cc: char, fallbacks: &[HFONT]) -> UiResult<CChar> {
+        if self.has_glyph(cc) { return self.measure_char(cc); }
+        for (index, font) in fallbacks.iter().enumerate() {
+            let old = unsafe { SelectObject(self.hdc, HGDIOBJ::from(*font)) };
+            let cchar = if self.has_glyph(cc) { Some(self.measure_char(cc)?.with_font(index + 1)) } else { None };
+            unsafe { SelectObject(self.hdc, old); }
+            if let Some(cchar) = cchar { return Ok(cchar); }
+        }
+        self.measure_char(cc)
+    }
+
     pub fn measure_char(&self, cc: char) -> UiResult<CChar> {
         let mut w = 0;
         let ch = cc.to_string().encode_utf16().collect::<Vec<u16>>();
@@ -20,7 +20,9 @@ use windows::core::PCWSTR;
 use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, POINT, WPARAM};
 use windows::Win32::Graphics::Gdi::{BitBlt, CreateCompatibleDC, CreateDIBSection, CreateFontW, DeleteDC, DeleteObject, DrawTextW, InvalidateRect, SelectObject, SetBkMode, SetTextColor, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DT_LEFT, DT_SINGLELINE, DT_TOP, FONT_CHARSET, FONT_CLIP_PRECISION, FONT_OUTPUT_PRECISION, FONT_QUALITY, HGDIOBJ, SRCCOPY, TRANSPARENT};
 #[cfg(not(feature = "gpu"))]
-use windows::Win32::Graphics::Gdi::{HDC, HFONT};
+use windows::Win32::Foundation::RECT;
+#[cfg(not(feature = "gpu"))]
+use windows::Win32::Graphics::Gdi::{CreatePen, CreateSolidBrush, FillRect, Polyline, HDC, HFONT, PS_SOLID};
 #[cfg(not(feature = "gpu"))]
 use windows::Win32::Graphics::GdiPlus::{CompositingQualityHighQuality, FillModeAlternate, GdipAddPathArc, GdipAddPathLine, GdipCreateFromHDC, GdipCreatePath, GdipCreatePen1, GdipCreateSolidFill, GdipDeleteBrush, GdipDeleteGraphics, GdipDeletePath, GdipDeletePen, GdipDrawEllipse, GdipDrawPath, GdipFillEllipse, GdipFillPath, GdipSetCompositingQuality, GdipSetSmoothingMode, GpGraphics, GpPath, GpPen, GpSolidFill, SmoothingModeAntiAlias, SmoothingModeAntiAlias8x8, UnitPixel};
 use windows::Win32::Graphics::GdiPlus::{GdipDrawPolygon, GdipFillPolygon, PointF};
@@ -91,7 +93,7 @@ impl Win32WindowHandle {
     }
 
     #[cfg(not(feature = "gpu"))]
-    pub fn paint_text(&self, hdc: HDC, lines: &Vec<LineChar>, text: &RichText, rect: Rect) -> UiResult<()> {
+    pub fn paint_text(&self, hdc: HDC, lines: &[LineChar], text: &RichText, rect: Rect, clip_x: f32, clip_y: f32) -> UiResult<()> {
         unsafe {
             SetBkMode(hdc, TRANSPARENT);
 
@@ -99,10 +101,51 @@ impl Win32WindowHandle {
             let hfont = self.create_font(text.height as i32, text.family.as_ref().unwrap());
             // 选择字体进入 HDC
             let old_font = SelectObject(hdc, HGDIOBJ::from(hfont));
+            //仅绘制可视区域内的行
+            let (first, count) = if text.height > 0.0 {
+                ((-clip_y / text.height).floor().max(0.0) as usize, (rect.height() / text.height).ceil() as usize + 1)
+            } else { (0, lines.len()) };
             let mut rect = rect.as_win32_rect();
-            for line in lines {
-                let mut utf16 = until::to_wstr(&line.line_text);
-                DrawTextW(hdc, utf16.as_mut_slice(), &mut rect, DT_SINGLELINE | DT_TOP | DT_LEFT);
+            rect.left += clip_x as i32;
+            rect.top += (clip_y + first as f32 * text.height) as i32;
+            let decorated = text.has_decoration();
+            let mut start = if decorated { LineChar::start_of(lines, first) } else { 0 };
+            for line in lines.iter().skip(first).take(count) {
+                let decorations = if decorated && line.width > 0.0 { line.decorations(start, text) } else { vec![] };
+                for (x_min, x_max, decoration) in decorations.iter() {
+                    let Some(background) = &decoration.background else { continue; };
+                    let brush = CreateSolidBrush(COLORREF(background.as_rgb_u32()));
+                    let bg = RECT { left: rect.left + *x_min as i32, top: rect.top, right: rect.left + *x_max as i32, bottom: rect.top + text.height as i32 };
+                    FillRect(hdc, &bg, brush);
+                    DeleteObject(HGDIOBJ::from(brush)).ok()?;
+                }
+                if line.is_plain() {
+                    let mut utf16 = until::to_wstr(&line.line_text);
+                    DrawTextW(hdc, utf16.as_mut_slice(), &mut rect, DT_SINGLELINE | DT_TOP | DT_LEFT);
+                } else {
+                    let left = rect.left;
+                    for (range, color, font) in line.runs() {
+                        SetTextColor(hdc, COLORREF(color.unwrap_or(&text.color).as_rgb_u32()));
+                        rect.left = left + line.get_width_in_char(range.start) as i32;
+                        let mut utf16 = until::to_wstr(&line.text_by_range(range));
+                        if font == 0 {
+                            DrawTextW(hdc, utf16.as_mut_slice(), &mut rect, DT_SINGLELINE | DT_TOP | DT_LEFT);
+                            continue;
+                        }
+                        let fallback = self.create_font(text.height as i32, text.family_by_index(font));
+                        SelectObject(hdc, HGDIOBJ::from(fallback));
+                        DrawTextW(hdc, utf16.as_mut_slice(), &mut rect, DT_SINGLELINE | DT_TOP | DT_LEFT);
+                        SelectObject(hdc, HGDIOBJ::from(hfont));
+                        DeleteObject(HGDIOBJ::from(fallback)).ok()?;
+                    }
+                    rect.left = left;
+                    SetTextColor(hdc, COLORREF(text.color.as_rgb_u32()));
+                }
+                for (x_min, x_max, decoration) in decorations.iter() {
+                    if !decoration.has_lines() { continue; }
+                    self.paint_decoration(hdc, text, decoration, rect.left as f32 + x_min, rect.top as f32 + text.metrics.ascent, x_max - x_min)?;
+                }
+                start += line.source_len() + if line.auto_wrap { 0 } else { 1 };
                 rect.top += text.height as i32;
             }
 
@@ -116,6 +159,32 @@ impl Win32WindowHandle {
         }
     }
 
+    ///绘制装饰线，y为基线位置
+    #[cfg(not(feature = "gpu"))]
+    fn paint_decoration(&self, hdc: HDC, text: &RichText, decoration: &TextDecoration, x: f32, y: f32, width: f32) -> UiResult<()> {
+        let color = COLORREF(decoration.color.as_ref().unwrap_or(&text.color).as_rgb_u32());
+        for (offset, thickness, wavy) in decoration.lines(&text.metrics) {
+            let ly = y + offset;
+            unsafe {
+                if wavy {
+                    let points: Vec<POINT> = TextDecoration::wave_points(x, x + width, ly, thickness).iter().map(|(px, py)| POINT { x: *px as i32, y: *py as i32 }).collect();
+                    let pen = CreatePen(PS_SOLID, thickness.round() as i32, color);
+                    let old = SelectObject(hdc, HGDIOBJ::from(pen));
+                    Polyline(hdc, &points).ok()?;
+                    SelectObject(hdc, old);
+                    DeleteObject(HGDIOBJ::from(pen)).ok()?;
+                } else {
+                    let brush = CreateSolidBrush(color);
+                    let top = (ly - thickness / 2.0).round() as i32;
+                    let line = RECT { left: x as i32, top, right: (x + width) as i32, bottom: top + thickness.round().max(1.0) as i32 };
+                    FillRect(hdc, &line, brush);
+                    DeleteObject(HGDIOBJ::from(brush)).ok()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[cfg(not(feature = "gpu"))]
     fn add_round_rect_path(path: &mut GpPath, rect: &Rect, radius: &Radius) {
         unsafe {
@@ -10,6 +10,7 @@ use crate::{App, TrayMenu, WindowAttribute};
 use std::ops::Index;
 use std::process::exit;
 use std::ptr::null_mut;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::{COLORREF, HINSTANCE, HWND, POINT};
@@ -90,6 +91,7 @@ impl Win32Window {
         let window = self.windows.remove(&wid);
         if let Some(ref window) = window {
             if window.handle().type_ == WindowType::ROOT { exit(0); }
+            window.closed();
         }
         if self.windows.len() == 0 { exit(0); }
         window
@@ -163,7 +165,8 @@ impl Win32Window {
         Ok(Win32WindowHandle { hwnd, clipboard: Win32Clipboard, size: RwLock::new(attr.inner_size.clone()) })
     }
 
-    pub fn create_child_window(&mut self, parent: &Arc<WindowType>, app: Box<dyn App>) -> UiResult<()> {
+    ///创建子窗口，modal为父窗口的模态子窗口数量，创建成功后增加，子窗口关闭时减少
+    pub fn create_child_window(&mut self, parent: &Arc<WindowType>, app: Box<dyn App>, modal: Option<Arc<AtomicUsize>>) -> UiResult<()> {
         let attr = app.window_attributes();
         let handle = Win32Window::create_window(&attr)?;
         let window_type = Arc::new(WindowType {
@@ -173,9 +176,14 @@ impl Win32Window {
             ime: parent.ime.clone(),
         });
         #[cfg(feature = "gpu")]
-        let window = pollster::block_on(async { LoopWindow::create_gpu_window(app, window_type, attr).await });
+        let mut window = pollster::block_on(async { LoopWindow::create_gpu_window(app, window_type, attr).await });
         #[cfg(not(feature = "gpu"))]
-        let window = LoopWindow::create_native_window(app, window_type, attr);
+        let mut window = LoopWindow::create_native_window(app, window_type, attr);
+        if let Some(ref count) = modal {
+            count.fetch_add(1, Ordering::SeqCst);
+            parent.request_redraw();
+        }
+        window.app_ctx.context.modal_parent = modal.map(|count| (count, parent.clone()));
         unsafe { SetWindowLongPtrW(window.handle().win32().hwnd, GWLP_USERDATA, self as *mut _ as isize); }
         self.windows.insert(window.window_id(), window);
         Ok(())
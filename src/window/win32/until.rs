@@ -1,5 +1,5 @@
 use crate::error::UiResult;
-use crate::key::Key;
+use crate::key::{Key, KeyModifiers};
 #[cfg(not(feature = "gpu"))]
 use crate::ui::PaintParam;
 use crate::window::event::WindowEvent;
@@ -7,13 +7,14 @@ use crate::window::ime::IMEData;
 use crate::window::win32::{Win32Window, CREATE_CHILD, REQ_UPDATE, RE_INIT, TRAY_ICON};
 use crate::window::wino::EventLoopHandle;
 use crate::*;
+use std::mem;
 use std::thread::{sleep, spawn};
 use std::time::Duration;
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM};
 use windows::Win32::Graphics::Gdi::{BeginPaint, BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, CreateFontW, CreateSolidBrush, DeleteDC, DeleteObject, DrawTextW, EndPaint, FillRect, GetDC, ReleaseDC, SelectObject, SetTextColor, DT_CENTER, DT_SINGLELINE, DT_VCENTER, FONT_CHARSET, FONT_CLIP_PRECISION, FONT_OUTPUT_PRECISION, FONT_QUALITY, HBITMAP, HDC, HGDIOBJ, PAINTSTRUCT, SRCCOPY};
 use windows::Win32::UI::Input::Ime::{ImmGetCompositionStringW, ImmGetContext, ImmReleaseContext, GCS_COMPSTR, GCS_RESULTSTR};
-use windows::Win32::UI::Input::KeyboardAndMouse::{GetKeyState, VIRTUAL_KEY, VK_BACK, VK_CONTROL, VK_DELETE, VK_DOWN, VK_END, VK_HOME, VK_LEFT, VK_RETURN, VK_RIGHT, VK_UP};
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetKeyState, VIRTUAL_KEY, VK_BACK, VK_CONTROL, VK_DELETE, VK_DOWN, VK_END, VK_ESCAPE, VK_F2, VK_F3, VK_HOME, VK_LEFT, VK_NEXT, VK_PRIOR, VK_RETURN, VK_RIGHT, VK_SHIFT, VK_TAB, VK_UP};
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 pub fn to_wstr(s: &str) -> Vec<u16> {
@@ -37,6 +38,13 @@ pub fn get_y_lparam(lp: LPARAM) -> i32 {
     ((lp.0 >> 16) as i16) as i32
 }
 
+fn key_modifiers() -> KeyModifiers {
+    KeyModifiers {
+        shift: (unsafe { GetKeyState(VK_SHIFT.0 as i32) } as u16 & 0x8000) != 0,
+        ctrl: (unsafe { GetKeyState(VK_CONTROL.0 as i32) } as u16 & 0x8000) != 0,
+    }
+}
+
 pub fn icon_to_bitmap(h_icon: HICON, width: i32, height: i32) -> UiResult<HBITMAP> {
     let hdc = unsafe { GetDC(None) };
     let hdc_mem = unsafe { CreateCompatibleDC(Some(hdc)) };
@@ -215,6 +223,11 @@ pub unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
                 window.handle_event(WindowEvent::KeyPress(Key::CtrlA));
             } else if ctrl_pressed && wparam.0 == 'X' as usize {
                 window.handle_event(WindowEvent::KeyPress(Key::CtrlX));
+            } else if ctrl_pressed && wparam.0 == 'F' as usize {
+                window.handle_event(WindowEvent::KeyPress(Key::CtrlF));
+            } else if VIRTUAL_KEY(wparam.0 as u16) == VK_F3 {
+                let shift_pressed = (unsafe { GetKeyState(VK_SHIFT.0 as i32) } as u16 & 0x8000) != 0;
+                window.handle_event(WindowEvent::KeyPress(if shift_pressed { Key::ShiftF3 } else { Key::F3 }));
             } else {
                 match VIRTUAL_KEY(wparam.0 as u16) {
                     VK_HOME => window.handle_event(WindowEvent::KeyPress(Key::Home)),
@@ -226,6 +239,13 @@ pub unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
                     VK_RIGHT => window.handle_event(WindowEvent::KeyPress(Key::RightArrow)),
                     VK_DELETE => window.handle_event(WindowEvent::KeyPress(Key::Delete)),
                     VK_BACK => window.handle_event(WindowEvent::KeyPress(Key::Backspace)),
+                    VK_TAB => window.handle_event(WindowEvent::KeyPress(Key::Tab)),
+                    VK_F2 => window.handle_event(WindowEvent::KeyPress(Key::F2)),
+                    VK_PRIOR => window.handle_event(WindowEvent::KeyPress(Key::PageUp)),
+                    VK_NEXT => window.handle_event(WindowEvent::KeyPress(Key::PageDown)),
+                    VK_ESCAPE => window.handle_event(WindowEvent::KeyPress(Key::Escape)),
+                    VK_SHIFT => window.handle_event(WindowEvent::KeyPress(Key::LShift)),
+                    VK_CONTROL => window.handle_event(WindowEvent::KeyPress(Key::LCtrl)),
                     _ => {}
                 }
             }
@@ -241,6 +261,13 @@ pub unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
                 VK_RIGHT => window.handle_event(WindowEvent::KeyRelease(Key::RightArrow)),
                 VK_DELETE => window.handle_event(WindowEvent::KeyRelease(Key::Delete)),
                 VK_BACK => window.handle_event(WindowEvent::KeyRelease(Key::Backspace)),
+                VK_TAB => window.handle_event(WindowEvent::KeyRelease(Key::Tab)),
+                VK_F2 => window.handle_event(WindowEvent::KeyRelease(Key::F2)),
+                VK_PRIOR => window.handle_event(WindowEvent::KeyRelease(Key::PageUp)),
+                VK_NEXT => window.handle_event(WindowEvent::KeyRelease(Key::PageDown)),
+                VK_ESCAPE => window.handle_event(WindowEvent::KeyRelease(Key::Escape)),
+                VK_SHIFT => window.handle_event(WindowEvent::KeyRelease(Key::LShift)),
+                VK_CONTROL => window.handle_event(WindowEvent::KeyRelease(Key::LCtrl)),
                 _ => {}
             }
         }
@@ -253,12 +280,12 @@ pub unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
         WM_LBUTTONDOWN => {
             let x = get_x_lparam(lparam) as f32;
             let y = get_y_lparam(lparam) as f32;
-            window.handle_event(WindowEvent::MousePress(Pos { x, y }));
+            window.handle_event(WindowEvent::MousePress(Pos { x, y }, key_modifiers()));
         }
         WM_LBUTTONUP => {
             let x = get_x_lparam(lparam) as f32;
             let y = get_y_lparam(lparam) as f32;
-            window.handle_event(WindowEvent::MouseRelease(Pos { x, y }))
+            window.handle_event(WindowEvent::MouseRelease(Pos { x, y }, key_modifiers()))
         }
         WM_MOUSEMOVE => {
             let x = get_x_lparam(lparam) as f32;
@@ -272,8 +299,11 @@ pub unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
         REQ_UPDATE => window.handle_event(WindowEvent::ReqUpdate),
         CREATE_CHILD => {
             if let Some(user_app) = window.app_ctx.context.new_window.take() {
+                let modal = mem::take(&mut window.app_ctx.context.new_modal).then(|| window.app_ctx.context.modal_children.clone());
                 let handle = window.handle().clone();
-                app.create_child_window(&handle, user_app).unwrap();
+                if let Err(e) = app.create_child_window(&handle, user_app, modal) {
+                    println!("{}", e.to_string());
+                }
             }
         }
         WM_ERASEBKGND => return LRESULT(1),
@@ -1,5 +1,6 @@
 use crate::frame::context::{Render, UpdateType};
 use crate::frame::App;
+use crate::key::KeyModifiers;
 use crate::window::ime::{IMEData, IME};
 use crate::window::wnit::handle::WInitWindowHandle;
 use crate::window::wnit::Window;
@@ -133,10 +134,12 @@ impl<A: App + 'static> ApplicationHandler<(super::WindowId, UserEvent)> for WIni
             WindowEvent::MouseInput { state, button, .. } => {
                 match (state, button) {
                     (ElementState::Pressed, MouseButton::Left) => {
+                        window.app_ctx.device.device_input.mouse.modifiers = window.app_ctx.device.device_input.key_modifiers();
                         window.app_ctx.device.device_input.mouse.mouse_press();
                         window.app_ctx.update(UpdateType::MousePress, &mut window.app);
                     }
                     (ElementState::Released, MouseButton::Left) => {
+                        window.app_ctx.device.device_input.mouse.modifiers = window.app_ctx.device.device_input.key_modifiers();
                         window.app_ctx.device.device_input.mouse.mouse_release();
                         window.app_ctx.update(UpdateType::MouseRelease, &mut window.app);
                         window.app_ctx.device.device_input.mouse.a = 0.0;
@@ -158,8 +161,14 @@ impl<A: App + 'static> ApplicationHandler<(super::WindowId, UserEvent)> for WIni
                 window.app_ctx.device.device_input.mouse.update((position.x, position.y).into());
                 window.app_ctx.update(UpdateType::MouseMove, &mut window.app);
             }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                let state = modifiers.state();
+                let modifiers = KeyModifiers { shift: state.shift_key(), ctrl: state.control_key() };
+                window.app_ctx.device.device_input.set_key_modifiers(modifiers);
+            }
             WindowEvent::KeyboardInput { device_id: _device_id, event, .. } => {
                 if !event.state.is_pressed() { return; }
+                let modifiers = window.app_ctx.device.device_input.key_modifiers();
                 let key = match event.logical_key {
                     Key::Named(name) => {
                         match name {
@@ -173,14 +182,33 @@ impl<A: App + 'static> ApplicationHandler<(super::WindowId, UserEvent)> for WIni
                             NamedKey::Home => crate::key::Key::Home,
                             NamedKey::Backspace => crate::key::Key::Backspace,
                             NamedKey::Delete => crate::key::Key::Delete,
+                            NamedKey::Tab => crate::key::Key::Tab,
+                            NamedKey::PageUp => crate::key::Key::PageUp,
+                            NamedKey::PageDown => crate::key::Key::PageDown,
+                            NamedKey::Escape => crate::key::Key::Escape,
+                            NamedKey::F2 => crate::key::Key::F2,
+                            NamedKey::F3 if modifiers.shift => crate::key::Key::ShiftF3,
+                            NamedKey::F3 => crate::key::Key::F3,
                             _ => return,
                         }
                     }
+                    Key::Character(c) if modifiers.ctrl => match c.to_lowercase().as_str() {
+                        "a" => crate::key::Key::CtrlA,
+                        "c" => crate::key::Key::CtrlC,
+                        "v" => crate::key::Key::CtrlV,
+                        "x" => crate::key::Key::CtrlX,
+                        "f" => crate::key::Key::CtrlF,
+                        _ => return,
+                    },
                     Key::Character(c) => crate::key::Key::Char(c.as_str().chars().next().unwrap()),
                     Key::Unidentified(_) => return,
                     Key::Dead(_) => return,
                 };
-                window.app_ctx.update(UpdateType::KeyRelease(key), &mut window.app);
+                //与x11、win32相同，组合键及F3只发送KeyPress，其它键按下时发送KeyRelease
+                let combination = matches!(key, crate::key::Key::CtrlA | crate::key::Key::CtrlC | crate::key::Key::CtrlV
+                    | crate::key::Key::CtrlX | crate::key::Key::CtrlF | crate::key::Key::F3 | crate::key::Key::ShiftF3);
+                let update_type = if combination { UpdateType::KeyPress(key) } else { UpdateType::KeyRelease(key) };
+                window.app_ctx.update(update_type, &mut window.app);
                 window.app_ctx.context.window.request_redraw();
             }
             WindowEvent::Ime(ime) => {
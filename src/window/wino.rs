@@ -3,6 +3,8 @@ use crate::frame::context::Render;
 use crate::frame::App;
 use crate::map::Map;
 use crate::ui::AppContext;
+use crate::widgets::toast::Toasts;
+use crate::widgets::tooltip::Tooltips;
 use crate::window::event::WindowEvent;
 #[cfg(feature = "gpu")]
 use crate::window::UserEvent;
@@ -12,6 +14,7 @@ use crate::*;
 use glyphon::{Cache, Resolution, Viewport};
 #[cfg(feature = "gpu")]
 use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
@@ -39,6 +42,11 @@ impl LoopWindow {
             updates: Map::new(),
             user_update: (WindowId::unique_id(), UpdateType::None),
             new_window: None,
+            tooltips: Tooltips::new(),
+            toasts: Toasts::new(),
+            modal_children: Arc::new(AtomicUsize::new(0)),
+            new_modal: false,
+            modal_parent: None,
         };
         let device = Device {
             device_input: DeviceInput::new(),
@@ -64,6 +72,11 @@ impl LoopWindow {
             updates: Map::new(),
             user_update: (WindowId(unique_id_u32()), UpdateType::None),
             new_window: None,
+            tooltips: Tooltips::new(),
+            toasts: Toasts::new(),
+            modal_children: Arc::new(AtomicUsize::new(0)),
+            new_modal: false,
+            modal_parent: None,
         };
         let mut app_ctx = AppContext::new(device, context, attr);
         app_ctx.draw(&mut app);
@@ -72,6 +85,13 @@ impl LoopWindow {
             app,
         }
     }
+    ///窗口关闭时调用，模态子窗口恢复父窗口的输入
+    pub(crate) fn closed(&self) {
+        let Some((ref count, ref parent)) = self.app_ctx.context.modal_parent else { return; };
+        count.fetch_sub(1, Ordering::SeqCst);
+        parent.request_redraw();
+    }
+
     #[cfg(feature = "gpu")]
     pub(crate) async fn rebuild_device(window: &Arc<WindowType>, size: Size) -> Result<Device, Box<dyn Error>> {
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
@@ -119,8 +139,14 @@ impl EventLoopHandle for LoopWindow {
 
     fn handle_event(&mut self, event: WindowEvent) {
         match event {
-            WindowEvent::KeyPress(key) => self.app_ctx.update(UpdateType::KeyPress(key), &mut self.app),
-            WindowEvent::KeyRelease(key) => self.app_ctx.update(UpdateType::KeyRelease(key), &mut self.app),
+            WindowEvent::KeyPress(key) => {
+                self.app_ctx.device.device_input.update_key(&key, true);
+                self.app_ctx.update(UpdateType::KeyPress(key), &mut self.app)
+            }
+            WindowEvent::KeyRelease(key) => {
+                self.app_ctx.device.device_input.update_key(&key, false);
+                self.app_ctx.update(UpdateType::KeyRelease(key), &mut self.app)
+            }
             WindowEvent::MouseMove(pos) => {
                 self.app_ctx.device.device_input.mouse.update(pos);
                 self.app_ctx.update(UpdateType::MouseMove, &mut self.app);
@@ -130,13 +156,15 @@ impl EventLoopHandle for LoopWindow {
                 self.app_ctx.update(UpdateType::MouseWheel, &mut self.app);
                 self.app_ctx.device.device_input.mouse.delta = (0.0, 0.0);
             }
-            WindowEvent::MousePress(pos) => {
+            WindowEvent::MousePress(pos, modifiers) => {
                 self.app_ctx.device.device_input.mouse.lastest.relative = pos;
+                self.app_ctx.device.device_input.mouse.modifiers = modifiers;
                 self.app_ctx.device.device_input.mouse.mouse_press();
                 self.app_ctx.update(UpdateType::MousePress, &mut self.app);
             }
-            WindowEvent::MouseRelease(pos) => {
+            WindowEvent::MouseRelease(pos, modifiers) => {
                 self.app_ctx.device.device_input.mouse.lastest.relative = pos;
+                self.app_ctx.device.device_input.mouse.modifiers = modifiers;
                 self.app_ctx.device.device_input.mouse.mouse_release();
                 self.app_ctx.update(UpdateType::MouseRelease, &mut self.app);
                 self.app_ctx.device.device_input.mouse.a = 0.0;
@@ -4,10 +4,13 @@ use crate::frame::context::{Context, Render, UpdateType};
 use crate::frame::App;
 use crate::map::Map;
 use crate::ui::AppContext;
+use crate::widgets::toast::Toasts;
+use crate::widgets::tooltip::Tooltips;
 use crate::window::{UserEvent, WindowType};
 use crate::{Device, DeviceInput, Font, Size, WindowAttribute};
 use glyphon::{Cache, Resolution, Viewport};
 use std::error::Error;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
 pub(crate) struct Window {
@@ -34,6 +37,11 @@ impl Window {
             render: Render::new(&device),
             updates: Map::new(),
             new_window: None,
+            tooltips: Tooltips::new(),
+            toasts: Toasts::new(),
+            modal_children: Arc::new(AtomicUsize::new(0)),
+            new_modal: false,
+            modal_parent: None,
         };
         let mut app_ctx = AppContext::new(device, context, attr);
         app_ctx.draw(&mut app);
@@ -104,6 +104,10 @@ impl Cairo {
         unsafe { cairo_new_path(self) }
     }
 
+    pub fn fill(&mut self) {
+        unsafe { cairo_fill(self) }
+    }
+
     pub fn fill_preserve(&mut self) {
         unsafe { cairo_fill_preserve(self) }
     }
@@ -1,19 +1,53 @@
 use crate::error::{UiError, UiResult};
 use crate::text::cchar::{CChar, LineChar};
 use crate::{RichText, Ui};
+use std::cell::RefCell;
 use std::ffi::CString;
 use std::mem;
 use std::ptr::null_mut;
-use x11::xft::{XftFont, XftFontClose, XftFontOpenName, XftTextExtentsUtf8};
+use crate::text::FontMetrics;
+use std::os::raw::{c_char, c_int, c_long, c_void};
+use x11::xft::{XftCharExists, XftFont, XftFontClose, XftFontOpenName, XftLockFace, XftTextExtentsUtf8, XftUnlockFace};
 use x11::xlib::Display;
 use x11::xrender::XGlyphInfo;
 
+///FT_FaceRec开头至underline_thickness的字段
+#[allow(dead_code)]
+#[repr(C)]
+struct FtFaceHead {
+    num_faces: c_long,
+    face_index: c_long,
+    face_flags: c_long,
+    style_flags: c_long,
+    num_glyphs: c_long,
+    family_name: *mut c_char,
+    style_name: *mut c_char,
+    num_fixed_sizes: c_int,
+    available_sizes: *mut c_void,
+    num_charmaps: c_int,
+    charmaps: *mut c_void,
+    generic_data: *mut c_void,
+    generic_finalizer: *mut c_void,
+    bbox: [c_long; 4],
+    units_per_em: u16,
+    ascender: i16,
+    descender: i16,
+    height: i16,
+    max_advance_width: i16,
+    max_advance_height: i16,
+    underline_position: i16,
+    underline_thickness: i16,
+}
+
 pub struct X11Font {
     display: *mut Display,
     font: *mut XftFont,
     screen: i32,
     family: String,
     size: f32,
+    fallbacks: Vec<String>,
+    ///与fallbacks对应，使用时再打开
+    fallback_fonts: RefCell<Vec<*mut XftFont>>,
 }
 
 impl X11Font {
@@ -24,6 +58,8 @@ impl X11Font {
             screen: 0,
             family: "FangSong".to_string(),
             size: 14.0,
+            fallbacks: vec![],
+            fallback_fonts: RefCell::new(vec![]),
         }
     }
 
@@ -32,12 +68,15 @@ impl X11Font {
         let size = text.size.get_or_insert_with(|| ui.context.font.size());
         self.family = family.to_string();
         self.size = *size;
+        text.resolve_fallbacks(&mut ui.context.font);
+        self.fallbacks = text.fallbacks.clone();
         self.init(ui)
     }
 
     pub fn init(&mut self, ui: &mut Ui) -> UiResult<()> {
         let handle = ui.context.window.x11();
         if !self.display.is_null() && !self.font.is_null() { unsafe { XftFontClose(self.display, self.font); } }
+        self.close_fallbacks();
         self.display = handle.display;
         self.font = self.get_xft_font(&self.family, self.size)?;
         Ok(())
@@ -51,12 +90,48 @@ impl X11Font {
         Ok(xft_font)
     }
 
+    fn close_fallbacks(&mut self) {
+        for font in self.fallback_fonts.get_mut().drain(..) {
+            if !self.display.is_null() && !font.is_null() { unsafe { XftFontClose(self.display, font); } }
+        }
+    }
+
+    ///查找包含字符的字体，主字体缺少时依次查找回退字体，返回(字体索引,字体)
+    fn resolve_font(&self, ch: char) -> (usize, *mut XftFont) {
+        if self.font.is_null() || unsafe { XftCharExists(self.display, self.font, ch as u32) } != 0 { return (0, self.font); }
+        let mut fonts = self.fallback_fonts.borrow_mut();
+        for (index, family) in self.fallbacks.iter().enumerate() {
+            if fonts.len() <= index { fonts.push(self.get_xft_font(family, self.size).unwrap_or(null_mut())); }
+            let font = fonts[index];
+            if !font.is_null() && unsafe { XftCharExists(self.display, font, ch as u32) } != 0 { return (index + 1, font); }
+        }
+        (0, self.font)
+    }
+
     pub(crate) fn line_height(&self) -> UiResult<f32> {
         if self.font.is_null() || self.display.is_null() { return Err(UiError::NullPtr); }
         let font = unsafe { self.font.as_ref().ok_or(format!("字体'{}'为初始化", self.family)) }?;
         Ok(font.height as f32)
     }
 
+    ///装饰线度量，下划线取自FreeType字体，删除线按ascent估算
+    pub(crate) fn metrics(&self) -> UiResult<FontMetrics> {
+        if self.font.is_null() || self.display.is_null() { return Err(UiError::NullPtr); }
+        let font = unsafe { self.font.as_ref().ok_or(UiError::NullPtr) }?;
+        let mut metrics = FontMetrics::estimate(font.ascent as f32, font.descent as f32);
+        unsafe {
+            let face = XftLockFace(self.font) as *const FtFaceHead;
+            if let Some(face) = face.as_ref() && face.units_per_em != 0 {
+                let scale = self.size / face.units_per_em as f32;
+                metrics.underline_position = -face.underline_position as f32 * scale;
+                metrics.underline_thickness = (face.underline_thickness as f32 * scale).max(1.0);
+                metrics.strikeout_thickness = metrics.underline_thickness;
+            }
+            if !face.is_null() { XftUnlockFace(self.font); }
+        }
+        Ok(metrics)
+    }
+
     pub fn measure_text(&self, text: &RichText, wrap: bool, max_wrap: f32) -> UiResult<Vec<LineChar>> {
         let mut res = vec![];
         let text = text.text.replace("\r\n", "\n");
@@ -94,16 +169,18 @@ impl X11Font {
         let char_len = char_str.len() as i32;
         let c_char_str = CString::new(char_str)?;
         let c_char_ptr = c_char_str.as_ptr() as *const u8;
+        let (index, font) = self.resolve_font(ch);
         let mut extents: XGlyphInfo = unsafe { mem::zeroed() };
         unsafe {
-            XftTextExtentsUtf8(self.display, self.font, c_char_ptr, char_len, &mut extents);
+            XftTextExtentsUtf8(self.display, font, c_char_ptr, char_len, &mut extents);
         }
-        Ok(CChar::new(ch, extents.xOff as f32))
+        Ok(CChar::new(ch, extents.xOff as f32).with_font(index))
     }
 }
 
 impl Drop for X11Font {
     fn drop(&mut self) {
+        self.close_fallbacks();
         unsafe { XftFontClose(self.display, self.font); }
     }
 }
\ No newline at end of file
@@ -126,7 +126,7 @@ impl X11WindowHandle {
     }
 
     #[cfg(not(feature = "gpu"))]
-    pub fn paint_text_by_cairo(&self, paint: &mut PaintParam, text: &RichText, lines: &Vec<LineChar>, rect: Rect, clip_x: f32, clip_y: f32) { //功能异常
+    pub fn paint_text_by_cairo(&self, paint: &mut PaintParam, text: &RichText, lines: &[LineChar], rect: Rect, clip_x: f32, clip_y: f32) { //功能异常
         paint.cairo.save();
         paint.cairo.select_font_face(text.family.as_ref().unwrap(), FontSlant::Normal, FontWeight::Normal);
         paint.cairo.set_font_size(text.font_size() as f64);
@@ -135,10 +135,40 @@ impl X11WindowHandle {
         paint.cairo.rectangle(rect.dx().min as f64, rect.dy().min as f64 - font_extents.ascent, rect.width() as f64, rect.height() as f64 + font_extents.ascent + font_extents.descent);
         paint.cairo.clip();
         let x = (rect.dx().min + clip_x) as f64;
-        let mut y = (rect.dy().min + clip_y) as f64 + font_extents.ascent;
-        for line in lines {
-            paint.cairo.move_to(x, y);
-            paint.cairo.show_text(line.line_text.as_str());
+        //仅绘制可视区域内的行
+        let (first, count) = if text.height > 0.0 {
+            ((-clip_y / text.height).floor().max(0.0) as usize, (rect.height() / text.height).ceil() as usize + 1)
+        } else { (0, lines.len()) };
+        let mut y = (rect.dy().min + clip_y) as f64 + font_extents.ascent + first as f64 * text.height as f64;
+        let decorated = text.has_decoration();
+        let mut start = if decorated { LineChar::start_of(lines, first) } else { 0 };
+        for line in lines.iter().skip(first).take(count) {
+            let decorations = if decorated && line.width > 0.0 { line.decorations(start, text) } else { vec![] };
+            for (x_min, x_max, decoration) in decorations.iter() {
+                let Some(background) = &decoration.background else { continue; };
+                paint.cairo.set_source_rgba(background.r_f64(), background.g_f64(), background.b_f64(), background.a_f64());
+                paint.cairo.rectangle(x + *x_min as f64, y - font_extents.ascent, (x_max - x_min) as f64, font_extents.ascent + font_extents.descent);
+                paint.cairo.fill();
+            }
+            if !decorations.is_empty() { paint.cairo.set_source_rgba(text.color.r_f64(), text.color.g_f64(), text.color.b_f64(), text.color.a_f64()); }
+            if line.is_plain() {
+                paint.cairo.move_to(x, y);
+                paint.cairo.show_text(line.line_text.as_str());
+            } else {
+                for (range, color, font) in line.runs() {
+                    let color = color.unwrap_or(&text.color);
+                    paint.cairo.set_source_rgba(color.r_f64(), color.g_f64(), color.b_f64(), color.a_f64());
+                    if font != 0 { paint.cairo.select_font_face(text.family_by_index(font), FontSlant::Normal, FontWeight::Normal); }
+                    paint.cairo.move_to(x + line.get_width_in_char(range.start) as f64, y);
+                    paint.cairo.show_text(line.text_by_range(range));
+                    if font != 0 { paint.cairo.select_font_face(text.family.as_ref().unwrap(), FontSlant::Normal, FontWeight::Normal); }
+                }
+                paint.cairo.set_source_rgba(text.color.r_f64(), text.color.g_f64(), text.color.b_f64(), text.color.a_f64());
+            }
+            for (x_min, x_max, decoration) in decorations.iter() {
+                if decoration.has_lines() { self.paint_decoration_by_cairo(paint, text, decoration, x + *x_min as f64, y, (x_max - x_min) as f64); }
+            }
+            start += line.source_len() + if line.auto_wrap { 0 } else { 1 };
             y += text.height as f64;
         }
 
@@ -148,6 +178,28 @@ impl X11WindowHandle {
         paint.cairo.restore();
     }
 
+    ///绘制装饰线，y为基线位置
+    #[cfg(not(feature = "gpu"))]
+    fn paint_decoration_by_cairo(&self, paint: &mut PaintParam, text: &RichText, decoration: &TextDecoration, x: f64, y: f64, width: f64) {
+        let color = decoration.color.as_ref().unwrap_or(&text.color);
+        paint.cairo.set_source_rgba(color.r_f64(), color.g_f64(), color.b_f64(), color.a_f64());
+        for (offset, thickness, wavy) in decoration.lines(&text.metrics) {
+            paint.cairo.new_path();
+            paint.cairo.set_line_width(thickness as f64);
+            let ly = y + offset as f64;
+            if wavy {
+                let points = TextDecoration::wave_points(x as f32, (x + width) as f32, ly as f32, thickness);
+                paint.cairo.move_to(points[0].0 as f64, points[0].1 as f64);
+                points.iter().skip(1).for_each(|(px, py)| paint.cairo.line_to(*px as f64, *py as f64));
+            } else {
+                paint.cairo.move_to(x, ly);
+                paint.cairo.line_to(x + width, ly);
+            }
+            paint.cairo.stroke();
+        }
+        paint.cairo.set_source_rgba(text.color.r_f64(), text.color.g_f64(), text.color.b_f64(), text.color.a_f64());
+    }
+
     // #[cfg(not(feature = "gpu"))]
     // pub fn set_clip_rect(&self, paint: &mut PaintParam, rect: &Rect) {
     //     paint.cairo.rectangle(rect.dx().min as f64, rect.dy().min as f64, rect.width() as f64, rect.height() as f64);
@@ -156,7 +208,7 @@ impl X11WindowHandle {
 
 
     #[cfg(not(feature = "gpu"))]
-    pub fn paint_text(&self, paint: &mut PaintParam, text: &RichText, lines: &Vec<LineChar>, rect: Rect, clip_x: f32, clip_y: f32) -> UiResult<()> {
+    pub fn paint_text(&self, paint: &mut PaintParam, text: &RichText, lines: &[LineChar], rect: Rect, clip_x: f32, clip_y: f32) -> UiResult<()> {
         return Ok(self.paint_text_by_cairo(paint, text, lines, rect, clip_x, clip_y));
         // unsafe {
         //     // let colormap = xlib::XCreateColormap(self.display, self.root, self.visual_info.visual, xlib::AllocNone);
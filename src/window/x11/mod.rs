@@ -1,5 +1,5 @@
 use crate::error::UiResult;
-use crate::key::Key;
+use crate::key::{Key, KeyModifiers};
 use crate::map::Map;
 #[cfg(not(feature = "gpu"))]
 use crate::ui::PaintParam;
@@ -17,6 +17,7 @@ use std::ffi::CString;
 use std::os::raw::{c_long, c_uint, c_ulong};
 use std::process::exit;
 use std::ptr::null_mut;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 #[cfg(not(feature = "gpu"))]
 use std::thread::{sleep, spawn};
@@ -185,7 +186,8 @@ impl X11Window {
         }
     }
 
-    pub fn create_child_window(&mut self, parent: &Arc<WindowType>, app: Box<dyn App>) -> UiResult<()> {
+    ///创建子窗口，modal为父窗口的模态子窗口数量，创建成功后增加，子窗口关闭时减少
+    pub fn create_child_window(&mut self, parent: &Arc<WindowType>, app: Box<dyn App>, modal: Option<Arc<AtomicUsize>>) -> UiResult<()> {
         let attr = app.window_attributes();
         let mut handle = self.create_window(parent.x11().screen, parent.x11().visual_info, &attr)?;
         handle.update_atom = parent.x11().update_atom;
@@ -196,9 +198,14 @@ impl X11Window {
             ime: parent.ime.clone(),
         });
         #[cfg(not(feature = "gpu"))]
-        let window = LoopWindow::create_native_window(app, window, attr);
+        let mut window = LoopWindow::create_native_window(app, window, attr);
         #[cfg(feature = "gpu")]
-        let window = pollster::block_on(async { LoopWindow::create_gpu_window(app, window, attr).await });
+        let mut window = pollster::block_on(async { LoopWindow::create_gpu_window(app, window, attr).await });
+        if let Some(ref count) = modal {
+            count.fetch_add(1, Ordering::SeqCst);
+            parent.request_redraw();
+        }
+        window.app_ctx.context.modal_parent = modal.map(|count| (count, parent.clone()));
         self.windows.insert(window.window_id(), window);
         Ok(())
     }
@@ -292,8 +299,9 @@ impl X11Window {
                                 0 => window.handle_event(WindowEvent::ReqUpdate),
                                 1 => {
                                     let app = window.app_ctx.context.new_window.take().unwrap();
+                                    let modal = mem::take(&mut window.app_ctx.context.new_modal).then(|| window.app_ctx.context.modal_children.clone());
                                     let handle = window.handle().clone();
-                                    self.create_child_window(&handle, app)?;
+                                    self.create_child_window(&handle, app, modal)?;
                                     continue;
                                 }
                                 2 => window.handle_event(WindowEvent::ReInit),
@@ -303,7 +311,7 @@ impl X11Window {
                         } else if xclient.data.get_long(0) as xlib::Atom == self.wm_delete_atom {
                             if window.handle().type_ == WindowType::ROOT { exit(0); }
                             let wid = self.windows.iter().find(|x| x.handle().x11().window == event.expose.window);
-                            if let Some(wid) = wid { self.windows.remove(&wid.window_id()); }
+                            if let Some(wid) = wid && let Some(window) = self.windows.remove(&wid.window_id()) { window.closed(); }
                             if self.windows.len() == 0 { exit(0); }
                             continue;
                         }
@@ -332,6 +340,13 @@ impl X11Window {
                             } else if ctrl_press && keysym == x11::keysym::XK_a as u64 {
                                 self.modify_keys.push(x11::keysym::XK_a);
                                 window.handle_event(WindowEvent::KeyPress(Key::CtrlA));
+                            } else if ctrl_press && keysym == x11::keysym::XK_f as u64 {
+                                self.modify_keys.push(x11::keysym::XK_f);
+                                window.handle_event(WindowEvent::KeyPress(Key::CtrlF));
+                            } else if keysym == x11::keysym::XK_F3 as u64 {
+                                self.modify_keys.push(x11::keysym::XK_F3);
+                                let shift_press = (event.key.state & xlib::ShiftMask) != 0;
+                                window.handle_event(WindowEvent::KeyPress(if shift_press { Key::ShiftF3 } else { Key::F3 }));
                             }
 
 
@@ -355,15 +370,18 @@ impl X11Window {
                                 continue;
                             }
                             let ctrl_press = (event.key.state & xlib::ControlMask) != 0;
-                            if !ctrl_press {
-                                window.handle_event(WindowEvent::KeyRelease(Key::from_c_ulong(event.key.keycode, &buffer[..len as usize])));
+                            let key = Key::from_c_ulong(event.key.keycode, &buffer[..len as usize]);
+                            //释放Ctrl键时state中仍有ControlMask
+                            if !ctrl_press || matches!(key, Key::LCtrl | Key::RCtrl) {
+                                window.handle_event(WindowEvent::KeyRelease(key));
                             }
                         }
                     }
                     xlib::ButtonRelease => {
                         let xb: xlib::XButtonEvent = event.button;
+                        let modifiers = KeyModifiers { shift: xb.state & xlib::ShiftMask != 0, ctrl: xb.state & xlib::ControlMask != 0 };
                         match xb.button {
-                            1 => window.handle_event(WindowEvent::MouseRelease(Pos { x: xb.x as f32, y: xb.y as f32 })),
+                            1 => window.handle_event(WindowEvent::MouseRelease(Pos { x: xb.x as f32, y: xb.y as f32 }, modifiers)),
                             2 => {} //鼠标中间键
                             3 => {} //鼠标右键
                             4 => window.handle_event(WindowEvent::MouseWheel(1.0)), //向上滚动
@@ -373,8 +391,9 @@ impl X11Window {
                     }
                     xlib::ButtonPress => {
                         let xb: xlib::XButtonEvent = event.button;
+                        let modifiers = KeyModifiers { shift: xb.state & xlib::ShiftMask != 0, ctrl: xb.state & xlib::ControlMask != 0 };
                         match xb.button {
-                            1 => window.handle_event(WindowEvent::MousePress(Pos { x: xb.x as f32, y: xb.y as f32 })),
+                            1 => window.handle_event(WindowEvent::MousePress(Pos { x: xb.x as f32, y: xb.y as f32 }, modifiers)),
                             _ => {}
                         }
                     }
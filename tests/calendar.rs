@@ -0,0 +1,38 @@
+use xlui::*;
+
+pub struct TestCalendar {}
+
+impl TestCalendar {
+    pub fn new() -> TestCalendar {
+        TestCalendar {}
+    }
+
+    fn date_changed(&mut self, _: &mut Ui, date: Date) {
+        println!("calendar {} weekday {}", date, date.weekday());
+    }
+
+    fn picker_changed(&mut self, _: &mut Ui, date: Date) {
+        println!("picker {}", date.format("%Y/%m/%d"));
+    }
+
+    fn time_changed(&mut self, _: &mut Ui, time: Time) {
+        println!("time {}", time);
+    }
+}
+
+impl App for TestCalendar {
+    fn draw(&mut self, ui: &mut Ui) {
+        let today = Date::today();
+        ui.horizontal(|ui| {
+            ui.add(DatePicker::new(today).with_format("%Y/%m/%d").with_max_date(today.add_days(60)).connect(Self::picker_changed));
+            ui.add(TimeEdit::new(Time::new(9, 30, 0).unwrap()).connect(Self::time_changed));
+        });
+        //只能选择前后一个月内的工作日
+        ui.add(Calendar::new(today).with_min_date(today.add_months(-1)).with_max_date(today.add_months(1))
+            .with_disabled_dates(|date| date.weekday() >= 5).connect(Self::date_changed));
+    }
+}
+
+fn main() {
+    TestCalendar::new().run().unwrap();
+}
@@ -0,0 +1,28 @@
+use xlui::*;
+
+pub struct TestColor {}
+
+impl TestColor {
+    pub fn new() -> TestColor {
+        TestColor {}
+    }
+
+    fn color_changed(&mut self, _: &mut Ui, color: Color) {
+        println!("picker {} {:?}", color.to_hex(), color.to_hsv());
+    }
+
+    fn button_changed(&mut self, _: &mut Ui, color: Color) {
+        println!("button {}", color.to_hex());
+    }
+}
+
+impl App for TestColor {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.add(ColorButton::new(Color::rgb(64, 128, 230)).with_recent_colors(vec![Color::RED, Color::ORANGE]).connect(Self::button_changed));
+        ui.add(ColorPicker::new(Color::rgba(255, 165, 0, 200)).connect(Self::color_changed));
+    }
+}
+
+fn main() {
+    TestColor::new().run().unwrap();
+}
@@ -0,0 +1,89 @@
+use xlui::*;
+
+pub struct TestDialog {}
+
+impl TestDialog {
+    pub fn new() -> TestDialog {
+        TestDialog {}
+    }
+
+    fn info(&mut self, _: &mut Button, ui: &mut Ui) {
+        MessageBox::info("提示", "保存成功").show(ui, Self::on_result);
+    }
+
+    fn warning(&mut self, _: &mut Button, ui: &mut Ui) {
+        MessageBox::warning("警告", "文件已存在，是否覆盖？").show(ui, Self::on_result);
+    }
+
+    fn question(&mut self, _: &mut Button, ui: &mut Ui) {
+        MessageBox::question("提示", "是否保存修改？")
+            .with_buttons(&[MessageButton::Yes, MessageButton::No, MessageButton::Cancel])
+            .show(ui, Self::on_result);
+    }
+
+    fn modal_inner(&mut self, _: &mut Button, ui: &mut Ui) {
+        ui.create_inner_window(ModalWindow::new()).set_modal(true);
+    }
+
+    fn modal_child(&mut self, _: &mut Button, ui: &mut Ui) {
+        ui.create_modal_window(ModalWindow::new());
+    }
+
+    fn on_result(&mut self, ui: &mut Ui, button: MessageButton) {
+        println!("result {:?}", button);
+        ui.toast(format!("{:?}", button), ToastKind::Info, 2000);
+    }
+}
+
+impl App for TestDialog {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(Button::new("Info").connect(Self::info));
+            ui.add(Button::new("Warning").connect(Self::warning));
+            ui.add(Button::new("Question").connect(Self::question));
+        });
+        ui.horizontal(|ui| {
+            ui.add(Button::new("模态内部窗口").connect(Self::modal_inner));
+            ui.add(Button::new("模态子窗口").connect(Self::modal_child));
+        });
+        ui.add(TextEdit::single_edit("模态窗口打开时无法输入"));
+    }
+}
+
+struct ModalWindow {
+    count: usize,
+}
+
+impl ModalWindow {
+    fn new() -> ModalWindow {
+        ModalWindow { count: 0 }
+    }
+
+    //模态窗口内的回调
+    fn click(&mut self, _: &mut Button, ui: &mut Ui) {
+        self.count += 1;
+        let label: &mut Label = ui.get_widget("modal_count").unwrap();
+        label.set_text(format!("点击了{}次", self.count));
+    }
+}
+
+impl App for ModalWindow {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.label("关闭此窗口后才能操作父窗口");
+        ui.add(Button::new("点击").connect(Self::click));
+        ui.add(Label::new("点击了0次").with_id("modal_count"));
+    }
+
+    fn window_attributes(&self) -> WindowAttribute {
+        WindowAttribute {
+            inner_size: (300, 200).into(),
+            position: [100, 100],
+            title: "Modal".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+fn main() {
+    TestDialog::new().run().unwrap();
+}
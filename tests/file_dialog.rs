@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+use xlui::*;
+
+pub struct TestFileDialog {}
+
+impl TestFileDialog {
+    pub fn new() -> TestFileDialog {
+        TestFileDialog {}
+    }
+
+    fn open(&mut self, _: &mut Button, ui: &mut Ui) {
+        FileDialog::open()
+            .with_filter("Rust源文件", &["*.rs"])
+            .with_filter("文本文件", &["*.txt", "*.md", "*.toml"])
+            .with_filter("所有文件", &["*"])
+            .show(ui, Self::on_result);
+    }
+
+    fn open_multi(&mut self, _: &mut Button, ui: &mut Ui) {
+        FileDialog::open().with_title("选择多个文件").with_multi_select(true).with_hidden(true).show(ui, Self::on_result);
+    }
+
+    fn save(&mut self, _: &mut Button, ui: &mut Ui) {
+        FileDialog::save().with_file_name("Cargo.toml").show(ui, Self::on_result);
+    }
+
+    fn on_result(&mut self, ui: &mut Ui, files: Vec<PathBuf>) {
+        println!("result {:?}", files);
+        match files.len() {
+            0 => ui.toast("已取消", ToastKind::Warning, 2000),
+            n => ui.toast(format!("选择了{}个文件", n), ToastKind::Success, 2000),
+        };
+    }
+}
+
+impl App for TestFileDialog {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(Button::new("打开").connect(Self::open));
+            ui.add(Button::new("打开多个").connect(Self::open_multi));
+            ui.add(Button::new("保存").connect(Self::save));
+        });
+    }
+
+    fn window_attributes(&self) -> WindowAttribute {
+        WindowAttribute {
+            inner_size: (800, 600).into(),
+            ..Default::default()
+        }
+    }
+}
+
+fn main() {
+    TestFileDialog::new().run().unwrap();
+}
@@ -0,0 +1,49 @@
+use xlui::*;
+
+pub struct TestList {
+    list_view: ListView<String>,
+}
+
+impl TestList {
+    pub fn new() -> TestList {
+        let data = (0..10000).map(|x| format!("item-{}", x)).collect();
+        let mut list_view = ListView::new(data).with_size(300.0, 400.0);
+        list_view.set_item_widget(|ui, datum| {
+            ui.add(Label::new(datum.as_str()).with_id("list_item"));
+        });
+        list_view.on_scrolling(|datum, layout| {
+            let label: &mut Label = layout.get_widget(&"list_item".to_string()).unwrap();
+            label.set_text(datum);
+        });
+        list_view.set_item_text(|datum| datum.clone());
+        list_view.set_selection_mode(ListSelectionMode::Extended);
+        list_view.set_selection_callback(Self::selection_changed);
+        list_view.set_reorderable(true);
+        list_view.set_reorder_callback(Self::item_moved);
+        TestList {
+            list_view
+        }
+    }
+
+    fn item_moved(&mut self, _: &mut Ui, from: usize, to: usize) {
+        println!("moved {} to {}", from, to);
+    }
+
+    fn selection_changed(&mut self, _: &mut Ui) {
+        println!("current {:?} selected {:?}", self.list_view.current_index(), self.list_view.selected_indices());
+    }
+}
+
+impl App for TestList {
+    fn draw(&mut self, ui: &mut Ui) {
+        self.list_view.show(ui);
+    }
+
+    fn update(&mut self, ui: &mut Ui) {
+        self.list_view.update(ui);
+    }
+}
+
+fn main() {
+    TestList::new().run().unwrap();
+}
@@ -0,0 +1,55 @@
+use xlui::*;
+
+pub struct TestSection {
+    list_view: ListView<String>,
+}
+
+impl TestSection {
+    pub fn new() -> TestSection {
+        let names = ["Adams", "Baker", "Clark", "Davis", "Evans", "Frank", "Green", "Hills", "Irwin", "Jones", "Klein", "Lewis",
+            "Moore", "Nolan", "Owens", "Parks", "Quinn", "Reyes", "Smith", "Turner", "Upton", "Vance", "Walsh", "Young", "Zhang"];
+        let mut data = (0..2000).map(|x| format!("{}-{}", names[x % names.len()], x)).collect::<Vec<_>>();
+        data.sort();
+        let mut list_view = ListView::new(data).with_size(300.0, 400.0).with_section_height(24.0);
+        list_view.set_item_widget(|ui, datum| {
+            ui.add(Label::new(datum.as_str()).with_id("list_item"));
+        });
+        list_view.on_scrolling(|datum, layout| {
+            let label: &mut Label = layout.get_widget(&"list_item".to_string()).unwrap();
+            label.set_text(datum);
+        });
+        //按首字母分组，当前分组头固定在顶部
+        list_view.set_section_key(|datum| datum[..1].to_string());
+        list_view.set_section_widget(|ui, title| ui.label(RichText::new(format!("— {} —", title)).size(12.0)));
+        list_view.set_index_bar(true);
+        list_view.set_selection_callback(Self::selection_changed);
+        TestSection {
+            list_view
+        }
+    }
+
+    fn selection_changed(&mut self, _: &mut Ui) {
+        let section = self.list_view.current_index().and_then(|x| self.list_view.section_of(x));
+        println!("current {:?} section {:?}", self.list_view.current(), section.map(|x| self.list_view.sections()[x]));
+    }
+
+    fn jump_last(&mut self, _: &mut Button, _: &mut Ui) {
+        let last = self.list_view.sections().len() - 1;
+        self.list_view.scroll_to_section(last);
+    }
+}
+
+impl App for TestSection {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.add(Button::new("跳转到最后一组").connect(Self::jump_last));
+        self.list_view.show(ui);
+    }
+
+    fn update(&mut self, ui: &mut Ui) {
+        self.list_view.update(ui);
+    }
+}
+
+fn main() {
+    TestSection::new().run().unwrap();
+}
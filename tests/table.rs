@@ -1,11 +1,41 @@
+use std::thread;
+use std::time::Duration;
 use xlui::*;
 
 
-pub struct TableData {}
+#[derive(Clone)]
+pub struct TableData {
+    id: usize,
+    name: String,
+    count: u32,
+    done: bool,
+}
 
 impl TableExt for TableData {
     fn cols(&self) -> Vec<impl ToString> {
-        vec![1, 2, 3, 4, 5]
+        vec![self.id.to_string(), (self.id * 7 % 5).to_string(), self.name.clone(), self.count.to_string(), self.done.to_string(), (self.id % 101).to_string(), self.id.to_string()]
+    }
+
+    fn set_col(&mut self, column: usize, value: &str) -> bool {
+        match column {
+            2 => self.name = value.to_string(),
+            3 => match value.parse() {
+                Ok(count) => self.count = count,
+                Err(_) => return false,
+            },
+            4 => self.done = value == "true",
+            _ => return false,
+        }
+        true
+    }
+
+    fn value(&self, column: usize) -> CellValue {
+        match column {
+            3 => CellValue::Number(self.count as f64),
+            4 => CellValue::Bool(self.done),
+            5 => CellValue::Progress((self.id % 101) as f32),
+            _ => CellValue::Text(self.cols()[column].to_string()),
+        }
     }
 }
 
@@ -16,30 +46,61 @@ pub struct TestTable {
 impl TestTable {
     pub fn new() -> TestTable {
         let columns = vec![
-            TableColumn::new_name("column1").with_width(80.0),
-            TableColumn::new_name("column2").with_width(100.0),
-            TableColumn::new_name("column3").with_width(150.0),
-            TableColumn::new_name("column4").with_width(150.0),
-            TableColumn::new_name("column5").with_width(200.0),
+            TableColumn::new_name("column1").with_width(80.0).with_sort(true),
+            TableColumn::new_name("column2").with_width(100.0).with_sort(true).with_filter(true),
+            TableColumn::new_name("name").with_width(150.0).with_editor(TableEditor::Text),
+            TableColumn::new_name("count").with_width(150.0).with_kind(TableCellKind::Number { decimals: 1 }).with_editor(TableEditor::Number { min: 0.0, max: 100.0, step: 1.0 }),
+            TableColumn::new_name("done").with_width(80.0).with_kind(TableCellKind::Bool).with_editor(TableEditor::Check),
+            TableColumn::new_name("progress").with_width(150.0).with_kind(TableCellKind::Progress).with_sort(true),
+            TableColumn::new_name("custom").with_width(100.0).with_kind(TableCellKind::Custom),
         ];
         let mut table_view = TableView::new(columns).with_size(780.0, 600.0);
-        let mut data = vec![];
-        for _ in 0..5 {
-            data.push(TableData {});
-        }
-        table_view.set_data(data);
+        table_view.set_edit_callback(Self::cell_edited);
+        table_view.set_selection_mode(TableSelectionMode::Multi);
+        table_view.set_selection_callback(Self::selection_changed);
+        table_view.set_cell_ui(6, |ui, datum: &TableData| {
+            ui.horizontal(|ui| {
+                ui.label(format!("#{}", datum.id));
+                if datum.done { ui.label("✔"); }
+            });
+        });
+        let data = (0..100000).map(|id| TableData { id, name: format!("name{}", id), count: id as u32, done: false }).collect();
+        let model = VecModel::new(data);
+        table_view.set_model(model.clone());
+        //后台线程追加行，表格只重设滚动范围及重新绑定受影响的可见行
+        thread::spawn(move || {
+            for batch in 0..100 {
+                thread::sleep(Duration::from_millis(200));
+                let start = 100000 + batch * 100;
+                model.extend((start..start + 100).map(|id| TableData { id, name: format!("name{}", id), count: id as u32, done: false }));
+                model.update(0, |datum| datum.count += 1);
+            }
+        });
         TestTable {
             table_view
         }
     }
+
+    fn cell_edited(&mut self, _: &mut Ui, row: usize, column: usize, value: &str) -> bool {
+        println!("edit {} {} {}", row, column, value);
+        column != 2 || !value.trim().is_empty()
+    }
+
+    fn selection_changed(&mut self, _: &mut Ui) {
+        println!("selected {:?}", self.table_view.selected_rows());
+    }
 }
 
 impl App for TestTable {
     fn draw(&mut self, ui: &mut Ui) {
         self.table_view.show(ui);
     }
+
+    fn update(&mut self, ui: &mut Ui) {
+        self.table_view.update(ui);
+    }
 }
 
 fn main() {
     TestTable::new().run().unwrap();
-}
\ No newline at end of file
+}
@@ -0,0 +1,57 @@
+use xlui::*;
+
+pub struct TestToast {
+    count: usize,
+}
+
+impl TestToast {
+    pub fn new() -> TestToast {
+        TestToast { count: 0 }
+    }
+
+    fn info(&mut self, _: &mut Button, ui: &mut Ui) {
+        self.count += 1;
+        ui.toast(format!("第{}条消息", self.count), ToastKind::Info, 3000);
+    }
+
+    fn saved(&mut self, _: &mut Button, ui: &mut Ui) {
+        ui.toast("已保存", ToastKind::Success, 3000).set_action("撤销", Self::undo);
+    }
+
+    fn warning(&mut self, _: &mut Button, ui: &mut Ui) {
+        ui.toast("磁盘空间不足", ToastKind::Warning, 5000);
+    }
+
+    fn error(&mut self, _: &mut Button, ui: &mut Ui) {
+        //不会自动关闭，点击后关闭
+        ui.toast("连接已断开", ToastKind::Error, 0).set_action("重连", Self::reconnect);
+    }
+
+    fn undo(&mut self, ui: &mut Ui) {
+        ui.toast("已撤销", ToastKind::Info, 2000);
+    }
+
+    fn reconnect(&mut self, ui: &mut Ui) {
+        ui.toast("已重新连接", ToastKind::Success, 2000);
+    }
+}
+
+impl App for TestToast {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(Button::new("Info").connect(Self::info));
+            ui.add(Button::new("Success").connect(Self::saved));
+            ui.add(Button::new("Warning").connect(Self::warning));
+            ui.add(Button::new("Error").connect(Self::error));
+        });
+        ui.horizontal(|ui| {
+            ui.add(Button::new("右上角").connect(|_: &mut Self, _, ui| ui.set_toast_align(Align::RightTop)));
+            ui.add(Button::new("右下角").connect(|_: &mut Self, _, ui| ui.set_toast_align(Align::RightBottom)));
+            ui.add(Button::new("底部居中").connect(|_: &mut Self, _, ui| ui.set_toast_align(Align::CenterBottom)));
+        });
+    }
+}
+
+fn main() {
+    TestToast::new().run().unwrap();
+}
@@ -0,0 +1,82 @@
+use xlui::*;
+
+pub struct TestToolBar {
+    search: String,
+}
+
+impl TestToolBar {
+    pub fn new() -> TestToolBar {
+        TestToolBar { search: String::new() }
+    }
+
+    fn open(&mut self, ui: &mut Ui) {
+        ui.toast("打开", ToastKind::Info, 1500);
+    }
+
+    fn save(&mut self, ui: &mut Ui) {
+        ui.toast("保存", ToastKind::Success, 1500);
+    }
+
+    fn undo(&mut self, ui: &mut Ui) {
+        ui.toast("撤销", ToastKind::Info, 1500);
+    }
+
+    fn redo(&mut self, ui: &mut Ui) {
+        ui.toast("重做", ToastKind::Info, 1500);
+    }
+
+    fn bold(&mut self, ui: &mut Ui, checked: bool) {
+        ui.toast(format!("粗体 {}", checked), ToastKind::Info, 1500);
+    }
+
+    fn italic(&mut self, ui: &mut Ui, checked: bool) {
+        ui.toast(format!("斜体 {}", checked), ToastKind::Info, 1500);
+    }
+
+    fn search_changed(&mut self, _: &mut Ui, text: String) {
+        self.search = text;
+        println!("search {}", self.search);
+    }
+
+    fn add_items(ui: &mut Ui, tool_bar: &mut ToolBar) {
+        tool_bar.add_action(ui, "logo.jpg", "打开", Self::open);
+        tool_bar.add_action(ui, "logo.jpg", "保存", Self::save);
+        tool_bar.add_separator();
+        tool_bar.add_action(ui, "logo.jpg", "撤销", Self::undo);
+        tool_bar.add_action(ui, "logo.jpg", "重做", Self::redo);
+        tool_bar.add_separator();
+        tool_bar.add_toggle(ui, "logo.jpg", "粗体", true, Self::bold);
+        tool_bar.add_toggle(ui, "logo.jpg", "斜体", false, Self::italic);
+    }
+}
+
+impl App for TestToolBar {
+    fn draw(&mut self, ui: &mut Ui) {
+        //使用窗口宽度，缩小窗口时右侧的项移到“»”弹窗中
+        let mut tool_bar = ToolBar::horizontal();
+        Self::add_items(ui, &mut tool_bar);
+        tool_bar.add_separator();
+        tool_bar.add_widget(ui, TextEdit::single_edit("").with_width(150.0).connect(Self::search_changed));
+        ui.add(tool_bar);
+        let mut tool_bar = ToolBar::horizontal().with_button_style(ToolButtonStyle::TextOnly).with_width(200.0);
+        Self::add_items(ui, &mut tool_bar);
+        ui.add(tool_bar);
+        ui.horizontal(|ui| {
+            let mut tool_bar = ToolBar::vertical().with_button_style(ToolButtonStyle::IconOnly).with_height(180.0);
+            Self::add_items(ui, &mut tool_bar);
+            ui.add(tool_bar);
+            ui.label("图标模式下文本作为悬停提示");
+        });
+    }
+
+    fn window_attributes(&self) -> WindowAttribute {
+        WindowAttribute {
+            inner_size: (800, 600).into(),
+            ..Default::default()
+        }
+    }
+}
+
+fn main() {
+    TestToolBar::new().run().unwrap();
+}
@@ -0,0 +1,32 @@
+use xlui::*;
+
+pub struct TestTooltip {}
+
+impl TestTooltip {
+    pub fn new() -> TestTooltip {
+        TestTooltip {}
+    }
+}
+
+impl App for TestTooltip {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.set_tooltip_delay(400);
+        ui.horizontal(|ui| {
+            ui.add(Button::new("保存").tooltip("保存当前文件(Ctrl+S)"));
+            ui.add(Button::new("删除").tooltip("删除后无法恢复".color(Color::RED)));
+            ui.add(CheckBox::new(false, "自动保存").tooltip(RichText::new("每5分钟保存一次").size(12.0)));
+        });
+        ui.add(Label::new("通过ID设置提示").with_id("label"));
+        ui.set_tooltip("label", "Ui::set_tooltip");
+        //靠近窗口右下角的控件，提示会向左移动或翻转到鼠标上方
+        ui.add_space(300.0);
+        ui.horizontal(|ui| {
+            ui.add_space(600.0);
+            ui.add(Button::new("右下角").tooltip("靠近窗口边缘时调整提示的位置"));
+        });
+    }
+}
+
+fn main() {
+    TestTooltip::new().run().unwrap();
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+use xlui::*;
+
+pub struct FileProvider;
+
+impl TreeProvider<PathBuf> for FileProvider {
+    fn text(&self, node: &PathBuf) -> String {
+        match node.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => node.display().to_string(),
+        }
+    }
+
+    fn has_children(&self, node: &PathBuf) -> bool {
+        node.is_dir()
+    }
+
+    fn children(&self, node: &PathBuf) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(node) else { return vec![]; };
+        let mut children = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect::<Vec<_>>();
+        children.sort_by_key(|path| (!path.is_dir(), path.clone()));
+        children
+    }
+}
+
+pub struct TestTree {
+    tree_view: TreeView<PathBuf>,
+}
+
+impl TestTree {
+    pub fn new() -> TestTree {
+        let mut tree_view = TreeView::new(vec![PathBuf::from("/")], FileProvider).with_size(400.0, 500.0);
+        tree_view.set_selection_callback(Self::selection_changed);
+        let root = tree_view.roots()[0];
+        tree_view.expand(root);
+        TestTree {
+            tree_view
+        }
+    }
+
+    fn selection_changed(&mut self, _: &mut Ui) {
+        println!("selected {:?}", self.tree_view.current());
+    }
+
+    fn expand_children(&mut self, _: &mut Button, _: &mut Ui) {
+        let root = self.tree_view.roots()[0];
+        let children = self.tree_view.children(root).to_vec();
+        for child in children {
+            self.tree_view.expand(child);
+        }
+    }
+
+    fn collapse_all(&mut self, _: &mut Button, _: &mut Ui) {
+        self.tree_view.collapse_all();
+    }
+}
+
+impl App for TestTree {
+    fn draw(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(Button::new("展开一级").connect(Self::expand_children));
+            ui.add(Button::new("全部折叠").connect(Self::collapse_all));
+        });
+        self.tree_view.show(ui);
+    }
+
+    fn update(&mut self, ui: &mut Ui) {
+        self.tree_view.update(ui);
+    }
+}
+
+fn main() {
+    TestTree::new().run().unwrap();
+}